@@ -1,4 +1,5 @@
 use {
+    super::TokenProgramAccount,
     crate::{traits::Account, util::require},
     solana_program::{account_info::AccountInfo, program_pack::Pack},
     spl_token::state::Mint as SplMint,
@@ -12,13 +13,15 @@ impl<T: Account + Copy> MintAccount<T> {
     pub fn new(account: T) -> Self {
         let info = account.get_info();
         require(
-            info.owner == &spl_token::ID,
-            "mint account should be owned by spl token program",
+            TokenProgramAccount::is_token_program(info.owner),
+            "mint account should be owned by a token program",
         );
+        // Token-2022 mints may carry extensions after the base state
         require(
-            info.data_len() == SplMint::LEN,
+            info.data_len() == SplMint::LEN
+                || (info.owner == &spl_token_2022::ID && info.data_len() > SplMint::LEN),
             "mint account should be of length Mint::LEN",
-        );        
+        );
         Self(account)
     }
 }
@@ -1,6 +1,7 @@
 use {
+    super::TokenProgramAccount,
     crate::{traits::Account, util::require},
-    solana_program::{account_info::AccountInfo, program_pack::Pack},
+    solana_program::{account_info::AccountInfo, program_pack::Pack, pubkey::Pubkey},
     spl_token::state::Account as TokenAccountState,
 };
 
@@ -11,13 +12,27 @@ pub struct TokenAccount<T: Account>(T);
 impl<T: Account + Copy> TokenAccount<T> {
     pub fn new(account: T) -> Self {
         let info = account.get_info();
-        require(info.owner == &spl_token::ID, "Invalid token account");
         require(
-            info.data_len() == TokenAccountState::LEN,
+            TokenProgramAccount::is_token_program(info.owner),
+            "Invalid token account",
+        );
+        // Token-2022 accounts may carry extensions after the base state
+        require(
+            info.data_len() == TokenAccountState::LEN
+                || (info.owner == &spl_token_2022::ID && info.data_len() > TokenAccountState::LEN),
             "Invalid token account length",
         );
         Self(account)
     }
+
+    /// Returns the wallet that owns the tokens.
+    pub fn get_owner(self) -> Pubkey {
+        let data = self.get_info().data.borrow();
+        TokenAccountState::unpack(&data[..TokenAccountState::LEN])
+            .map_err(|_| "couldn't unpack token account data")
+            .unwrap()
+            .owner
+    }
 }
 
 impl<T: Account> Account for TokenAccount<T> {
@@ -1,7 +1,7 @@
 use {
     super::Readonly,
     crate::{traits::Account, util::require},
-    solana_program::account_info::AccountInfo,
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
 };
 
 #[derive(Clone, Copy)]
@@ -10,9 +10,25 @@ pub struct TokenProgramAccount(Readonly);
 
 impl TokenProgramAccount {
     pub fn new(info: &'static AccountInfo<'static>) -> Self {
-        require(info.key == &spl_token::ID, "Invalid token program account");
+        require(
+            Self::is_token_program(info.key),
+            "Invalid token program account",
+        );
         Self(Readonly::new(info))
     }
+
+    /// Returns whether `key` is the SPL Token or the Token-2022 program.
+    pub fn is_token_program(key: &Pubkey) -> bool {
+        key == &spl_token::ID || key == &spl_token_2022::ID
+    }
+
+    /// Reverts unless the account is owned by this token program.
+    pub fn check_owner(self, account: &AccountInfo) {
+        require(
+            account.owner == self.get_info().key,
+            "Token account is not owned by the token program",
+        );
+    }
 }
 
 impl Account for TokenProgramAccount {
@@ -10,26 +10,30 @@ use {
         accounts::{
             MintAccount, Readonly, Signer, SystemProgramAccount, TokenProgramAccount, Writable,
         },
-        keys::{CollateralMintKey, SovereignKey},
+        keys::{CollateralMintKey, SovereignKey, TokenProgramKey},
         store::{Authority, Collateral, CollateralParams, World},
         token::Safe,
         traits::{Account, Command, Pod, Store},
     },
-    solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar},
+    solana_program::{
+        account_info::AccountInfo, clock::Clock, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+    },
 };
 
 /// Creates a new collateral type in the system
 ///
+/// Recovery mode tracks every collateral type, so at most 16 can be created.
+///
 /// Accounts expected:
 ///
 /// 0. `[signer]` Sovereign account (paying for account creation)
 /// 1. `[writable]` Collateral account (PDA, will be created)
 /// 2. `[writable]` Safe account (PDA, will be created)
 /// 3. `[]` Authority account (PDA)
-/// 4. `[]` World account (PDA)
+/// 4. `[writable]` World account (PDA)
 /// 5. `[]` Mint account (for the collateral token)
 /// 6. `[]` System program
-/// 7. `[]` SPL Token program
+/// 7. `[]` Token program of the mint (SPL Token or Token-2022)
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -51,11 +55,14 @@ impl CollateralCreate {
         programKey: &[u8],
         sovereignKey: &[u8],
         collateralMintKey: &[u8],
+        tokenProgramKey: &[u8],
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let sovereignKey = SovereignKey::new(b2pk(sovereignKey)?);
         let collateralMintKey = CollateralMintKey::new(b2pk(collateralMintKey)?);
-        let accounts = Self::get_accounts(programKey, (sovereignKey, collateralMintKey))
+        let tokenProgramKey = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let keys = (sovereignKey, collateralMintKey, tokenProgramKey);
+        let accounts = Self::get_accounts(programKey, keys)
             .into_iter()
             .map(AccountWasm::from)
             .collect();
@@ -67,11 +74,11 @@ unsafe impl Pod for CollateralCreate {}
 
 impl Command for CollateralCreate {
     const ID: u32 = 0xe20af14f;
-    type Keys = (SovereignKey, CollateralMintKey);
+    type Keys = (SovereignKey, CollateralMintKey, TokenProgramKey);
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (sovereign_key, collateral_mint_key) = keys;
+        let (sovereign_key, collateral_mint_key, token_program_key) = keys;
         vec![
             AccountMeta {
                 pubkey: *sovereign_key,
@@ -84,7 +91,7 @@ impl Command for CollateralCreate {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: program_key.derive_safe(&collateral_mint_key),
+                pubkey: program_key.derive_collateral_safe(&collateral_mint_key),
                 is_signer: false,
                 is_writable: true,
             },
@@ -96,7 +103,7 @@ impl Command for CollateralCreate {
             AccountMeta {
                 pubkey: program_key.derive_world(),
                 is_signer: false,
-                is_writable: false,
+                is_writable: true,
             },
             AccountMeta {
                 pubkey: *collateral_mint_key,
@@ -109,7 +116,7 @@ impl Command for CollateralCreate {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: *token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -121,19 +128,19 @@ impl Command for CollateralCreate {
         let collateral_account = Writable::new(&accounts[1]);
         let safe_account = Writable::new(&accounts[2]);
         let authority_account = Readonly::new(&accounts[3]);
-        let world_account = Readonly::new(&accounts[4]);
+        let world_account = Writable::new(&accounts[4]);
         let mint_account = MintAccount::new(Readonly::new(&accounts[5]));
         let system_program_account = SystemProgramAccount::new(&accounts[6]);
         let token_program_account = TokenProgramAccount::new(&accounts[7]);
 
-        let world_data = world_account.get_info().data.borrow();
-        let world = World::load(program_id, world_account, &world_data, ());
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
         let sovereign_auth = world.sovereign.authorize(sovereign_account);
 
         let authority = Authority::from_account(program_id, authority_account);
         let rent = Rent::get().map_err(|_| "Failed to get rent").unwrap();
 
-        let safe_account = Safe::create(
+        let safe_account = Safe::create::<Collateral>(
             program_id,
             sovereign_account,
             safe_account,
@@ -158,5 +165,7 @@ impl Command for CollateralCreate {
                 mint_account,
             },
         );
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.recovery.register(mint_account.get_info().key, &clock);
     }
 }
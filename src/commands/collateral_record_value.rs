@@ -0,0 +1,113 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Readonly, Writable},
+        keys::{CollateralMintKey, OracleKey},
+        store::{Collateral, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Records the value of a collateral held for vaults and the debt it backs,
+/// for the aggregate collateral ratio that decides recovery mode. Commands
+/// that check recovery mode need every collateral type recorded within the
+/// max age, so call this for collateral types no command priced lately.
+/// Anyone may call this.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` World account (PDA)
+/// 1. `[writable]` Collateral account (PDA)
+/// 2. `[]` Oracle account
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CollateralRecordValue {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl CollateralRecordValue {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        collateralMintKey: &[u8],
+        oracleKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let collateralMintKey = CollateralMintKey::new(b2pk(collateralMintKey)?);
+        let oracleKey = OracleKey::new(b2pk(oracleKey)?);
+        let accounts = Self::get_accounts(programKey, (collateralMintKey, oracleKey))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for CollateralRecordValue {}
+
+impl Command for CollateralRecordValue {
+    const ID: u32 = 0x8623121d;
+    type Keys = (CollateralMintKey, OracleKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (collateral_mint_key, oracle_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral(&collateral_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *oracle_key,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let world_account = Writable::new(&accounts[0]);
+        let collateral_account = Writable::new(&accounts[1]);
+        let oracle_account = Readonly::new(&accounts[2]);
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+
+        let mut collateral_data = collateral_account.get_info().data.borrow_mut();
+        let collateral =
+            Collateral::load_mut(program_id, collateral_account, &mut collateral_data[..], ());
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.recovery.track(
+            &mut [collateral],
+            &[oracle_account],
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Readonly, Writable},
+        keys::{CollateralMintKey, OracleKey},
+        store::{Collateral, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Freezes the oracle price of a collateral after global settlement
+/// Anyone may call this, once per collateral.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` World account (PDA)
+/// 1. `[writable]` Collateral account (PDA)
+/// 2. `[]` Oracle account
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CollateralSettle {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl CollateralSettle {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        collateralMintKey: &[u8],
+        oracleKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let collateralMintKey = CollateralMintKey::new(b2pk(collateralMintKey)?);
+        let oracleKey = OracleKey::new(b2pk(oracleKey)?);
+        let accounts = Self::get_accounts(programKey, (collateralMintKey, oracleKey))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for CollateralSettle {}
+
+impl Command for CollateralSettle {
+    const ID: u32 = 0x13bd9726;
+    type Keys = (CollateralMintKey, OracleKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (collateral_mint_key, oracle_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral(&collateral_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *oracle_key,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let world_account = Writable::new(&accounts[0]);
+        let collateral_account = Writable::new(&accounts[1]);
+        let oracle_account = Readonly::new(&accounts[2]);
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_settled();
+
+        let mut collateral_data = collateral_account.get_info().data.borrow_mut();
+        let collateral =
+            Collateral::load_mut(program_id, collateral_account, &mut collateral_data[..], ());
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        collateral.settle(
+            oracle_account,
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+    }
+}
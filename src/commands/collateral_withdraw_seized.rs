@@ -0,0 +1,167 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        keys::{CollateralMintKey, SovereignKey, TokenProgramKey},
+        store::{Authority, Collateral, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Sends collateral seized from failed auctions to a treasury account
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account
+/// 1. `[]` World account (PDA)
+/// 2. `[writable]` Collateral account (PDA)
+/// 3. `[writable]` Safe account (PDA)
+/// 4. `[writable]` Treasury token account
+/// 5. `[]` Collateral mint account
+/// 6. `[]` Token program of the collateral (SPL Token or Token-2022)
+/// 7. `[]` Authority account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CollateralWithdrawSeized {
+    amount: Decimal,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl CollateralWithdrawSeized {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(amount: f64) -> Vec<u8> {
+        Self {
+            amount: Decimal::from(amount),
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        collateralMintKey: &[u8],
+        treasuryTokenKey: &[u8],
+        tokenProgramKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let sovereignKey = SovereignKey::new(b2pk(sovereignKey)?);
+        let collateralMintKey = CollateralMintKey::new(b2pk(collateralMintKey)?);
+        let treasuryTokenKey = b2pk(treasuryTokenKey)?;
+        let tokenProgramKey = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let accounts = Self::get_accounts(
+            programKey,
+            (
+                sovereignKey,
+                collateralMintKey,
+                treasuryTokenKey,
+                tokenProgramKey,
+            ),
+        )
+        .into_iter()
+        .map(AccountWasm::from)
+        .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for CollateralWithdrawSeized {}
+
+impl Command for CollateralWithdrawSeized {
+    const ID: u32 = 0x1e6fc186;
+    type Keys = (SovereignKey, CollateralMintKey, Pubkey, TokenProgramKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, collateral_mint_key, treasury_token_key, token_program_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral(&collateral_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral_safe(&collateral_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: treasury_token_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *collateral_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Readonly::new(&accounts[1]);
+        let collateral_account = Writable::new(&accounts[2]);
+        let safe_account = TokenAccount::new(Writable::new(&accounts[3]));
+        let treasury_token_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let mint_account = MintAccount::new(Readonly::new(&accounts[5]));
+        let token_program_account = TokenProgramAccount::new(&accounts[6]);
+        let authority_account = Readonly::new(&accounts[7]);
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        world.settlement.require_live();
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        let mut collateral_data = collateral_account.get_info().data.borrow_mut();
+        let (collateral, collateral_auth) = Collateral::load_auth(
+            program_id,
+            collateral_account,
+            &mut collateral_data[..],
+            sovereign_auth,
+        );
+
+        let authority = Authority::from_account(program_id, authority_account);
+        collateral.withdraw_seized(
+            collateral_auth,
+            self.amount,
+            program_id,
+            safe_account,
+            treasury_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+    }
+}
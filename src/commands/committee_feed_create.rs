@@ -0,0 +1,115 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Readonly, Signer, SystemProgramAccount, Writable},
+        keys::SovereignKey,
+        oracle::{CommitteeFeed, CommitteeFeedParams},
+        store::World,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar},
+};
+
+/// Creates a committee oracle, without publishers
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account (paying for account creation)
+/// 1. `[writable]` CommitteeFeed account (PDA, will be created)
+/// 2. `[]` World account (PDA)
+/// 3. `[]` System program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CommitteeFeedCreate {
+    index: u8,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl CommitteeFeedCreate {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(index: u8) -> Vec<u8> {
+        Self { index }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        index: u8,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let sovereignKey = SovereignKey::new(b2pk(sovereignKey)?);
+        let accounts = Self::get_accounts(programKey, (sovereignKey, index))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for CommitteeFeedCreate {}
+
+impl Command for CommitteeFeedCreate {
+    const ID: u32 = 0x5563454d;
+    type Keys = (SovereignKey, u8);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, index) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_committee_feed(index),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: solana_program::system_program::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let committee_feed_account = Writable::new(&accounts[1]);
+        let world_account = Readonly::new(&accounts[2]);
+        let system_program_account = SystemProgramAccount::new(&accounts[3]);
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        CommitteeFeed::create(
+            program_id,
+            sovereign_account,
+            committee_feed_account,
+            system_program_account,
+            &[self.index],
+            &Rent::get().map_err(|_| "Failed to get rent").unwrap(),
+            CommitteeFeedParams {
+                sovereign_auth,
+                index: self.index,
+            },
+        );
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::*,
+};
+use {
+    crate::{
+        accounts::{Readonly, Signer, Writable},
+        keys::SovereignKey,
+        oracle::CommitteeFeed,
+        store::World,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Replaces the publishers of a committee oracle and its quorum
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account
+/// 1. `[writable]` CommitteeFeed account (PDA)
+/// 2. `[]` World account (PDA)
+/// 3..n. `[]` Publisher accounts
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CommitteeFeedSetPublishers {
+    quorum: u8,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl CommitteeFeedSetPublishers {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(quorum: u8) -> Vec<u8> {
+        Self { quorum }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        index: u8,
+        publisherKeys: Vec<JsValue>,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let sovereign_key = SovereignKey::new(b2pk(sovereignKey)?);
+        let publisher_keys = publisherKeys
+            .into_iter()
+            .map(|key| -> Result<Pubkey, String> {
+                let key_bytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid publisher key: {}", e))?;
+                b2pk(&key_bytes)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let accounts = Self::get_accounts(program_key, (sovereign_key, index, publisher_keys))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for CommitteeFeedSetPublishers {}
+
+impl Command for CommitteeFeedSetPublishers {
+    const ID: u32 = 0x773fdc4b;
+    type Keys = (SovereignKey, u8, Vec<Pubkey>);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, index, publisher_keys) = keys;
+        let mut v = vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_committee_feed(index),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ];
+        v.extend(publisher_keys.into_iter().map(|p| AccountMeta {
+            pubkey: p,
+            is_signer: false,
+            is_writable: false,
+        }));
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let committee_feed_account = Writable::new(&accounts[1]);
+        let world_account = Readonly::new(&accounts[2]);
+        let publisher_accounts = accounts[3..].iter().map(Readonly::new).collect::<Vec<_>>();
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        let mut committee_feed_data = committee_feed_account.get_info().data.borrow_mut();
+        let (committee_feed, committee_feed_auth) = CommitteeFeed::load_auth(
+            program_id,
+            committee_feed_account,
+            &mut committee_feed_data[..],
+            sovereign_auth,
+        );
+
+        committee_feed.set_publishers(committee_feed_auth, self.quorum, &publisher_accounts);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Signer, Writable},
+        finance::Decimal,
+        keys::UserKey,
+        oracle::CommitteeFeed,
+        traits::{Account, Command, Pod, Store},
+        util::Time,
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Posts a publisher's price to a committee oracle
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Publisher account
+/// 1. `[writable]` CommitteeFeed account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CommitteeFeedSubmit {
+    price: Decimal,
+    unix_timestamp: u64,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl CommitteeFeedSubmit {
+    #[wasm_bindgen(js_name = "getData")]
+    #[allow(non_snake_case)]
+    pub fn get_data_wasm(price: f64, unixTimestamp: f64) -> Vec<u8> {
+        Self {
+            price: Decimal::from(price),
+            unix_timestamp: unixTimestamp as u64,
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        publisherKey: &[u8],
+        index: u8,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let publisher_key = UserKey::new(b2pk(publisherKey)?);
+        let accounts = Self::get_accounts(program_key, (publisher_key, index))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for CommitteeFeedSubmit {}
+
+impl Command for CommitteeFeedSubmit {
+    const ID: u32 = 0x03201151;
+    type Keys = (UserKey, u8);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (publisher_key, index) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *publisher_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_committee_feed(index),
+                is_signer: false,
+                is_writable: true,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let publisher_account = Signer::new(&accounts[0]);
+        let committee_feed_account = Writable::new(&accounts[1]);
+
+        let mut committee_feed_data = committee_feed_account.get_info().data.borrow_mut();
+        let committee_feed = CommitteeFeed::load_mut(
+            program_id,
+            committee_feed_account,
+            &mut committee_feed_data[..],
+            (),
+        );
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let time = Time::from_unix_timestamp(self.unix_timestamp);
+        committee_feed.submit(publisher_account, self.price, time, &clock);
+    }
+}
@@ -18,7 +18,7 @@ use {
         store::{Authority, World},
         traits::{Account, Command, Pod, Store},
     },
-    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
 };
 
 /// Executes a flash mint operation
@@ -78,6 +78,7 @@ impl Command for FlashMintBegin {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dvd_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: program_key.derive_world(),
@@ -90,7 +91,10 @@ impl Command for FlashMintBegin {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -100,7 +104,7 @@ impl Command for FlashMintBegin {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -122,11 +126,14 @@ impl Command for FlashMintBegin {
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let authority = Authority::from_account(program_id, authority_account);
 
         let flash_mint_end_instruction_data = FlashMintEnd::ID.to_le_bytes();
 
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let dvd_deposits = world.get_dvd_deposits(&clock);
         world.flash_mint.begin(
             self.borrow_amount,
             &flash_mint_end_instruction_data,
@@ -138,6 +145,10 @@ impl Command for FlashMintBegin {
             instruction_sysvar_account,
             world.config.get_flash_mint_config(),
             &mut world.dvd,
+            dvd_deposits,
+            &mut world.mint_limiter,
+            world.config.get_mint_limit_config(),
+            &clock,
         );
     }
 }
@@ -68,6 +68,7 @@ impl Command for FlashMintEnd {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dvd_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -85,12 +86,15 @@ impl Command for FlashMintEnd {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -114,6 +118,10 @@ impl Command for FlashMintEnd {
             token_program_account,
             world.config.get_flash_mint_config(),
             &mut world.dvd,
+            &mut world.mint_limiter,
+            &mut world.ledger,
+            &mut world.insurance_fund,
+            world.config.get_insurance_rate(),
         );
     }
 }
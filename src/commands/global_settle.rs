@@ -0,0 +1,94 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Signer, Writable},
+        keys::SovereignKey,
+        store::World,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Shuts the protocol down for good
+///
+/// Borrowing, liquidations, auctions, offerings, flash mints, redemptions and
+/// the stability modules stop. Collateral prices are then frozen with
+/// `CollateralSettle`, and vaults settled with `VaultSettle` during the
+/// `wait`, in seconds. After the wait, DVD holders pack their DVD into a
+/// settlement claim and cash it in for each collateral and stablecoin.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account
+/// 1. `[writable]` World account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GlobalSettle {
+    wait: u64,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl GlobalSettle {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(wait: u64) -> Vec<u8> {
+        Self { wait }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let sovereign_key = SovereignKey::new(b2pk(sovereignKey)?);
+        let accounts = Self::get_accounts(program_key, sovereign_key)
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for GlobalSettle {}
+
+impl Command for GlobalSettle {
+    const ID: u32 = 0x77aab37e;
+    type Keys = SovereignKey;
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, sovereign_key: Self::Keys) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Writable::new(&accounts[1]);
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.settlement.settle(sovereign_auth, self.wait, &clock);
+    }
+}
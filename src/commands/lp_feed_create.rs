@@ -0,0 +1,169 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::oracle::OracleKind,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, SystemProgramAccount, TokenAccount, Writable},
+        keys::{CollateralMintKey, SovereignKey},
+        oracle::{LpFeed, LpFeedParams, Oracle},
+        store::World,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar},
+};
+
+/// Creates an oracle pricing the LP token of a constant-product pool
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account (paying for account creation)
+/// 1. `[writable]` LpFeed account (PDA, will be created)
+/// 2. `[]` World account (PDA)
+/// 3. `[]` LP mint account
+/// 4. `[]` Pool reserve token account of the first asset
+/// 5. `[]` Pool reserve token account of the second asset
+/// 6. `[]` Mint account of the first asset
+/// 7. `[]` Mint account of the second asset
+/// 8. `[]` System program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct LpFeedCreate {
+    oracles: [Oracle; 2],
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl LpFeedCreate {
+    #[wasm_bindgen(js_name = "getData")]
+    #[allow(non_snake_case)]
+    pub fn get_data_wasm(
+        oracleKindA: OracleKind,
+        oracleKeyA: &[u8],
+        oracleKindB: OracleKind,
+        oracleKeyB: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let oracles = [
+            Oracle::new(oracleKindA, b2pk(oracleKeyA)?),
+            Oracle::new(oracleKindB, b2pk(oracleKeyB)?),
+        ];
+        Ok(Self { oracles }.get_data())
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        lpMintKey: &[u8],
+        reserveKeyA: &[u8],
+        reserveKeyB: &[u8],
+        reserveMintKeyA: &[u8],
+        reserveMintKeyB: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let sovereignKey = SovereignKey::new(b2pk(sovereignKey)?);
+        let lpMintKey = CollateralMintKey::new(b2pk(lpMintKey)?);
+        let reserveKeys = [b2pk(reserveKeyA)?, b2pk(reserveKeyB)?];
+        let reserveMintKeys = [b2pk(reserveMintKeyA)?, b2pk(reserveMintKeyB)?];
+        let accounts = Self::get_accounts(
+            programKey,
+            (sovereignKey, lpMintKey, reserveKeys, reserveMintKeys),
+        )
+        .into_iter()
+        .map(AccountWasm::from)
+        .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for LpFeedCreate {}
+
+impl Command for LpFeedCreate {
+    const ID: u32 = 0x16d140b9;
+    type Keys = (SovereignKey, CollateralMintKey, [Pubkey; 2], [Pubkey; 2]);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, lp_mint_key, reserve_keys, reserve_mint_keys) = keys;
+        let mut v = vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_lp_feed(&lp_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *lp_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+        ];
+        v.extend(
+            reserve_keys
+                .into_iter()
+                .chain(reserve_mint_keys)
+                .map(|k| AccountMeta {
+                    pubkey: k,
+                    is_signer: false,
+                    is_writable: false,
+                }),
+        );
+        v.push(AccountMeta {
+            pubkey: solana_program::system_program::ID,
+            is_signer: false,
+            is_writable: false,
+        });
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let lp_feed_account = Writable::new(&accounts[1]);
+        let world_account = Readonly::new(&accounts[2]);
+        let lp_mint_account = MintAccount::new(Readonly::new(&accounts[3]));
+        let reserve_accounts = [
+            TokenAccount::new(Readonly::new(&accounts[4])),
+            TokenAccount::new(Readonly::new(&accounts[5])),
+        ];
+        let reserve_mint_accounts = [
+            MintAccount::new(Readonly::new(&accounts[6])),
+            MintAccount::new(Readonly::new(&accounts[7])),
+        ];
+        let system_program_account = SystemProgramAccount::new(&accounts[8]);
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        LpFeed::create(
+            program_id,
+            sovereign_account,
+            lp_feed_account,
+            system_program_account,
+            lp_mint_account,
+            &Rent::get().map_err(|_| "Failed to get rent").unwrap(),
+            LpFeedParams {
+                sovereign_auth,
+                lp_mint_account,
+                reserve_accounts,
+                reserve_mint_accounts,
+                oracles: self.oracles,
+            },
+        );
+    }
+}
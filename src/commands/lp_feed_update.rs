@@ -0,0 +1,118 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, TokenAccount, Writable},
+        keys::{CollateralMintKey, OracleKey},
+        oracle::LpFeed,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Recomputes the price of an LP token oracle.
+/// Anyone may call this, typically right before using the price.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` LpFeed account (PDA)
+/// 1. `[]` LP mint account
+/// 2. `[]` Pool reserve token account of the first asset
+/// 3. `[]` Pool reserve token account of the second asset
+/// 4. `[]` Oracle account of the first asset
+/// 5. `[]` Oracle account of the second asset
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct LpFeedUpdate {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl LpFeedUpdate {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        lpMintKey: &[u8],
+        reserveKeyA: &[u8],
+        reserveKeyB: &[u8],
+        oracleKeyA: &[u8],
+        oracleKeyB: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let lpMintKey = CollateralMintKey::new(b2pk(lpMintKey)?);
+        let reserveKeys = [b2pk(reserveKeyA)?, b2pk(reserveKeyB)?];
+        let oracleKeys = [
+            OracleKey::new(b2pk(oracleKeyA)?),
+            OracleKey::new(b2pk(oracleKeyB)?),
+        ];
+        let accounts = Self::get_accounts(programKey, (lpMintKey, reserveKeys, oracleKeys))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for LpFeedUpdate {}
+
+impl Command for LpFeedUpdate {
+    const ID: u32 = 0x12b431ea;
+    type Keys = (CollateralMintKey, [Pubkey; 2], [OracleKey; 2]);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (lp_mint_key, reserve_keys, oracle_keys) = keys;
+        let mut v = vec![
+            AccountMeta {
+                pubkey: program_key.derive_lp_feed(&lp_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *lp_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+        ];
+        v.extend(reserve_keys.into_iter().map(|k| AccountMeta {
+            pubkey: k,
+            is_signer: false,
+            is_writable: false,
+        }));
+        v.extend(oracle_keys.into_iter().map(|o| AccountMeta {
+            pubkey: *o,
+            is_signer: false,
+            is_writable: false,
+        }));
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let lp_feed_account = Writable::new(&accounts[0]);
+        let lp_mint_account = MintAccount::new(Readonly::new(&accounts[1]));
+        let reserve_accounts = [
+            TokenAccount::new(Readonly::new(&accounts[2])),
+            TokenAccount::new(Readonly::new(&accounts[3])),
+        ];
+        let oracle_accounts = [Readonly::new(&accounts[4]), Readonly::new(&accounts[5])];
+
+        let mut lp_feed_data = lp_feed_account.get_info().data.borrow_mut();
+        let lp_feed = LpFeed::load_mut(program_id, lp_feed_account, &mut lp_feed_data[..], ());
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        lp_feed.update(lp_mint_account, reserve_accounts, oracle_accounts, &clock);
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{
+            MintAccount, Readonly, Signer, SystemProgramAccount, TokenProgramAccount, Writable,
+        },
+        keys::SovereignKey,
+        store::{Authority, World},
+        token::MintMetadata,
+        traits::{Account, Command, Pod, Store},
+        util::revert,
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar},
+};
+
+/// Updates the name, symbol and URI of the DVD, DOVE or sDVD mint
+///
+/// Only mints created by `WorldCreateWithMints` or `SavingsShareCreate` carry
+/// metadata.
+///
+/// Accounts expected:
+///
+/// 0. `[signer, writable]` Sovereign account (paying for any extra space)
+/// 1. `[]` World account (PDA)
+/// 2. `[]` Authority account (PDA)
+/// 3. `[writable]` DVD, DOVE or sDVD mint account
+/// 4. `[]` System program
+/// 5. `[]` Token-2022 program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct MintUpdateMetadata {
+    metadata: MintMetadata,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl MintUpdateMetadata {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(metadata: MintMetadata) -> Vec<u8> {
+        Self { metadata }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        mintKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let sovereign_key = SovereignKey::new(b2pk(sovereignKey)?);
+        let mint_key = b2pk(mintKey)?;
+        let accounts = Self::get_accounts(program_key, (sovereign_key, mint_key))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for MintUpdateMetadata {}
+
+impl Command for MintUpdateMetadata {
+    const ID: u32 = 0x18dacedc;
+    type Keys = (SovereignKey, Pubkey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, mint_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: solana_program::system_program::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_token_program(&mint_key),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Readonly::new(&accounts[1]);
+        let authority_account = Readonly::new(&accounts[2]);
+        let mint_account = MintAccount::new(Writable::new(&accounts[3]));
+        let system_program_account = SystemProgramAccount::new(&accounts[4]);
+        let token_program_account = TokenProgramAccount::new(&accounts[5]);
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        let mint_key = mint_account.get_info().key;
+        let mint = if world.dvd.get_mint().get_key() == mint_key {
+            world.dvd.get_mint()
+        } else if world.dove.get_mint().get_key() == mint_key {
+            world.dove.get_mint()
+        } else if let Some(sdvd) = world
+            .savings_share
+            .get_token()
+            .filter(|sdvd| sdvd.get_mint().get_key() == mint_key)
+        {
+            sdvd.get_mint()
+        } else {
+            revert("Mint is not the DVD, DOVE or sDVD mint");
+        };
+
+        let authority = Authority::from_account(program_id, authority_account);
+        let rent = Rent::get().map_err(|_| "Failed to get rent").unwrap();
+        mint.update_metadata(
+            sovereign_account,
+            mint_account,
+            system_program_account,
+            token_program_account,
+            authority,
+            &self.metadata,
+            &rent,
+            sovereign_auth,
+        );
+    }
+}
@@ -1,24 +1,58 @@
 mod authority_create;
+mod collateral_clear_circuit_breaker;
 mod collateral_create;
+mod collateral_record_price;
+mod collateral_record_value;
+mod collateral_set_circuit_breaker;
 mod collateral_set_oracle;
+mod collateral_set_price_mode;
+mod collateral_set_stability_fee;
+mod collateral_settle;
 mod collateral_update_max_deposit;
+mod collateral_withdraw_seized;
+mod committee_feed_create;
+mod committee_feed_set_publishers;
+mod committee_feed_submit;
 mod config_update;
 mod flash_mint_begin;
 mod flash_mint_end;
+mod global_settle;
+mod lp_feed_create;
+mod lp_feed_update;
+mod mint_update_metadata;
 mod offering_buy;
 mod offering_end;
 mod offering_start;
+mod redeem;
+mod redemption_refresh;
+mod referrers_set;
+mod safe_reconcile;
+mod safe_sweep;
 mod savings_claim_rewards;
 mod savings_create;
 mod savings_deposit;
+mod savings_request_withdrawal;
+mod savings_share_create;
+mod savings_share_deposit;
+mod savings_share_exchange_rate;
+mod savings_share_request_withdrawal;
+mod savings_share_withdraw;
 mod savings_withdraw;
+mod settlement_cash;
+mod settlement_claim_create;
+mod settlement_pack;
 mod sovereign_update;
 mod stability_create;
 mod stability_buy_dvd;
+mod stability_pool_claim_collateral;
+mod stability_pool_create;
+mod stability_pool_deposit;
+mod stability_pool_withdraw;
 mod stability_sell_dvd;
 mod stability_update_max_deposit;
-mod user_feed_create;
-mod user_feed_set_price;
+mod term_deposit_create;
+mod term_deposit_deposit;
+mod term_deposit_withdraw;
 mod vault_borrow;
 mod vault_buy_collateral;
 mod vault_claim_rewards;
@@ -29,28 +63,59 @@ mod vault_fail_auction;
 mod vault_liquidate;
 mod vault_remove_reserve;
 mod vault_repay;
+mod vault_settle;
 mod vault_unliquidate;
 mod vault_withdraw;
 mod vesting_claim;
 mod vesting_update_recipient;
 mod world_create;
+mod world_create_with_mints;
+
+#[cfg(feature = "devnet")]
+mod user_feed_create;
+#[cfg(feature = "devnet")]
+mod user_feed_set_price;
+
+#[cfg(feature = "devnet")]
+pub use {user_feed_create::UserFeedCreate, user_feed_set_price::UserFeedSetPrice};
 pub use {
-    authority_create::AuthorityCreate, collateral_create::CollateralCreate,
-    collateral_set_oracle::CollateralSetOracle,
-    collateral_update_max_deposit::CollateralUpdateMaxDeposit, config_update::ConfigUpdate,
-    flash_mint_begin::FlashMintBegin, flash_mint_end::FlashMintEnd, offering_buy::OfferingBuy,
-    offering_end::OfferingEnd, offering_start::OfferingStart,
+    authority_create::AuthorityCreate,
+    collateral_clear_circuit_breaker::CollateralClearCircuitBreaker,
+    collateral_create::CollateralCreate, collateral_record_price::CollateralRecordPrice,
+    collateral_record_value::CollateralRecordValue,
+    collateral_set_circuit_breaker::CollateralSetCircuitBreaker,
+    collateral_set_oracle::CollateralSetOracle, collateral_set_price_mode::CollateralSetPriceMode,
+    collateral_set_stability_fee::CollateralSetStabilityFee, collateral_settle::CollateralSettle,
+    collateral_update_max_deposit::CollateralUpdateMaxDeposit,
+    collateral_withdraw_seized::CollateralWithdrawSeized,
+    committee_feed_create::CommitteeFeedCreate,
+    committee_feed_set_publishers::CommitteeFeedSetPublishers,
+    committee_feed_submit::CommitteeFeedSubmit, config_update::ConfigUpdate,
+    flash_mint_begin::FlashMintBegin, flash_mint_end::FlashMintEnd, global_settle::GlobalSettle,
+    lp_feed_create::LpFeedCreate, lp_feed_update::LpFeedUpdate,
+    mint_update_metadata::MintUpdateMetadata, offering_buy::OfferingBuy, offering_end::OfferingEnd,
+    offering_start::OfferingStart, redeem::Redeem, redemption_refresh::RedemptionRefresh,
+    referrers_set::ReferrersSet, safe_reconcile::SafeReconcile, safe_sweep::SafeSweep,
     savings_claim_rewards::SavingsClaimRewards, savings_create::SavingsCreate,
-    savings_deposit::SavingsDeposit, savings_withdraw::SavingsWithdraw,
-    sovereign_update::SovereignUpdate, stability_create::StabilityCreate,
-    stability_buy_dvd::StabilityBuyDvd, stability_sell_dvd::StabilitySellDvd,
-    stability_update_max_deposit::StabilityUpdateMaxDeposit, user_feed_create::UserFeedCreate,
-    user_feed_set_price::UserFeedSetPrice, vault_borrow::VaultBorrow,
-    vault_buy_collateral::VaultBuyCollateral, vault_claim_rewards::VaultClaimRewards,
-    vault_create::VaultCreate, vault_create_reserve::VaultCreateReserve,
-    vault_deposit::VaultDeposit, vault_fail_auction::VaultFailAuction,
-    vault_liquidate::VaultLiquidate, vault_remove_reserve::VaultRemoveReserve,
-    vault_repay::VaultRepay, vault_unliquidate::VaultUnliquidate, vault_withdraw::VaultWithdraw,
-    vesting_claim::VestingClaim, vesting_update_recipient::VestingUpdateRecipient,
-    world_create::WorldCreate,
+    savings_deposit::SavingsDeposit, savings_request_withdrawal::SavingsRequestWithdrawal,
+    savings_share_create::SavingsShareCreate, savings_share_deposit::SavingsShareDeposit,
+    savings_share_exchange_rate::SavingsShareExchangeRate,
+    savings_share_request_withdrawal::SavingsShareRequestWithdrawal,
+    savings_share_withdraw::SavingsShareWithdraw, savings_withdraw::SavingsWithdraw,
+    settlement_cash::SettlementCash, settlement_claim_create::SettlementClaimCreate,
+    settlement_pack::SettlementPack, sovereign_update::SovereignUpdate,
+    stability_create::StabilityCreate, stability_buy_dvd::StabilityBuyDvd,
+    stability_pool_claim_collateral::StabilityPoolClaimCollateral,
+    stability_pool_create::StabilityPoolCreate, stability_pool_deposit::StabilityPoolDeposit,
+    stability_pool_withdraw::StabilityPoolWithdraw, stability_sell_dvd::StabilitySellDvd,
+    stability_update_max_deposit::StabilityUpdateMaxDeposit, term_deposit_create::TermDepositCreate,
+    term_deposit_deposit::TermDepositDeposit, term_deposit_withdraw::TermDepositWithdraw,
+    vault_borrow::VaultBorrow, vault_buy_collateral::VaultBuyCollateral,
+    vault_claim_rewards::VaultClaimRewards, vault_create::VaultCreate,
+    vault_create_reserve::VaultCreateReserve, vault_deposit::VaultDeposit,
+    vault_fail_auction::VaultFailAuction, vault_liquidate::VaultLiquidate,
+    vault_remove_reserve::VaultRemoveReserve, vault_repay::VaultRepay, vault_settle::VaultSettle,
+    vault_unliquidate::VaultUnliquidate, vault_withdraw::VaultWithdraw, vesting_claim::VestingClaim,
+    vesting_update_recipient::VestingUpdateRecipient, world_create::WorldCreate,
+    world_create_with_mints::WorldCreateWithMints,
 };
@@ -76,6 +76,7 @@ impl Command for OfferingBuy {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dvd_mint_key, dove_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -93,7 +94,10 @@ impl Command for OfferingBuy {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -103,7 +107,10 @@ impl Command for OfferingBuy {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dove_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dove_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -113,7 +120,7 @@ impl Command for OfferingBuy {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -132,15 +139,20 @@ impl Command for OfferingBuy {
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let authority = Authority::from_account(program_id, authority_account);
 
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let dvd_deposits = world.get_dvd_deposits(&clock);
         world.offering.buy(
             self.requested_base_amount,
             &clock,
             &mut world.dvd,
+            dvd_deposits,
             &mut world.dove,
+            &mut world.mint_limiter,
+            world.config.get_mint_limit_config(),
             authority,
             world.config.get_auction_config(),
             user_account,
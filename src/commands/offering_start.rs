@@ -79,6 +79,7 @@ impl Command for OfferingStart {
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
         world.offering.start(
@@ -86,10 +87,13 @@ impl Command for OfferingStart {
             oracle_account,
             &mut world.debt,
             &mut world.savings,
+            &world.term_savings,
+            &world.stability_pool,
             &mut world.dvd,
             &mut world.dvd_price,
             &world.config.get_dvd_interest_rate(),
             &mut world.stable_dvd,
+            &mut world.insurance_fund,
             world.config.get_dove_oracle(),
             world.config.get_offering_config(),
             world.config.get_debt_config(),
@@ -0,0 +1,324 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::{wasm_bindgen, JsValue},
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        keys::{CollateralMintKey, DvdMintKey, OracleKey, TokenProgramKey, UserKey, VaultKey},
+        store::{Authority, Collateral, Vault, World},
+        traits::{Account, Command, Pod, Store},
+        util::require,
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Redeems DVD for collateral at oracle price, minus the redemption fee
+///
+/// The DVD cancels the debt of the given vaults, which must be the head of
+/// the redemption queue once measured, and the collateral is taken from their
+/// reserves of the first collateral type. Vaults without that collateral are
+/// skipped.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` World account (PDA)
+/// 2. `[writable]` Debt token mint account
+/// 3. `[writable]` Debt token account (to burn tokens from)
+/// 4. `[]` Token program of the debt token
+/// 5. `[]` Authority account (PDA)
+/// 6. `[writable]` Safe account of the redeemed collateral (PDA)
+/// 7. `[writable]` Collateral token account (to transfer tokens to)
+/// 8. `[]` Mint account of the redeemed collateral
+/// 9. `[]` Token program of the redeemed collateral (SPL Token or Token-2022)
+/// 10..n. `[writable]` Collateral accounts, starting with the redeemed one, for every reserve of the vaults (PDAs)
+/// n..m. `[]` Oracle accounts in the same order (PDAs)
+/// m..o. `[writable]` Vault accounts, in redemption queue order (PDAs)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Redeem {
+    amount: Decimal,
+    max_fee_rate: Decimal,
+    collateral_count: u8,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Redeem {
+    #[wasm_bindgen(js_name = "getData")]
+    #[allow(non_snake_case)]
+    pub fn get_data_wasm(amount: f64, maxFeeRate: f64, collateralCount: u8) -> Vec<u8> {
+        Self {
+            amount: Decimal::from(amount),
+            max_fee_rate: Decimal::from(maxFeeRate),
+            collateral_count: collateralCount,
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+        dvdMintKey: &[u8],
+        collateralTokenProgramKey: &[u8],
+        collateralMintKeys: Vec<JsValue>,
+        oracleKeys: Vec<JsValue>,
+        vaultKeys: Vec<JsValue>,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let userKey = UserKey::new(b2pk(userKey)?);
+        let dvdMintKey = DvdMintKey::new(b2pk(dvdMintKey)?);
+        let collateralTokenProgramKey = TokenProgramKey::new(b2pk(collateralTokenProgramKey)?)?;
+        let collateralMintKeys = collateralMintKeys
+            .into_iter()
+            .map(|key| -> Result<CollateralMintKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid collateral mint key: {}", e))?;
+                Ok(CollateralMintKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let oracleKeys = oracleKeys
+            .into_iter()
+            .map(|key| -> Result<OracleKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid oracle key: {}", e))?;
+                Ok(OracleKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let vaultKeys = vaultKeys
+            .into_iter()
+            .map(|key| -> Result<VaultKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid vault key: {}", e))?;
+                Ok(VaultKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if collateralMintKeys.is_empty() {
+            return Err("At least one collateral mint key is required".to_string());
+        }
+
+        let accounts = Self::get_accounts(
+            programKey,
+            (
+                userKey,
+                dvdMintKey,
+                collateralTokenProgramKey,
+                collateralMintKeys,
+                oracleKeys,
+                vaultKeys,
+            ),
+        )
+        .into_iter()
+        .map(AccountWasm::from)
+        .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for Redeem {}
+
+impl Command for Redeem {
+    const ID: u32 = 0x1596ea66;
+    type Keys = (
+        UserKey,
+        DvdMintKey,
+        TokenProgramKey,
+        Vec<CollateralMintKey>,
+        Vec<OracleKey>,
+        Vec<VaultKey>,
+    );
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (
+            user_key,
+            dvd_mint_key,
+            collateral_token_program_key,
+            collateral_mint_keys,
+            oracle_keys,
+            vault_keys,
+        ) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        let redeemed_mint_key = &collateral_mint_keys[0];
+        let mut v = vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *dvd_mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: dvd_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral_safe(redeemed_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    redeemed_mint_key,
+                    &collateral_token_program_key,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: **redeemed_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *collateral_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+        ];
+        v.extend(collateral_mint_keys.iter().map(|c| AccountMeta {
+            pubkey: program_key.derive_collateral(c),
+            is_signer: false,
+            is_writable: true,
+        }));
+        v.extend(oracle_keys.into_iter().map(|o| AccountMeta {
+            pubkey: *o,
+            is_signer: false,
+            is_writable: false,
+        }));
+        v.extend(vault_keys.into_iter().map(|vault_key| AccountMeta {
+            pubkey: *vault_key,
+            is_signer: false,
+            is_writable: true,
+        }));
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let world_account = Writable::new(&accounts[1]);
+        let dvd_mint_account = MintAccount::new(Writable::new(&accounts[2]));
+        let dvd_account = TokenAccount::new(Writable::new(&accounts[3]));
+        let dvd_token_program_account = TokenProgramAccount::new(&accounts[4]);
+        let authority_account = Readonly::new(&accounts[5]);
+        let safe_account = TokenAccount::new(Writable::new(&accounts[6]));
+        let collateral_token_account = TokenAccount::new(Writable::new(&accounts[7]));
+        let collateral_mint_account = MintAccount::new(Readonly::new(&accounts[8]));
+        let collateral_token_program_account = TokenProgramAccount::new(&accounts[9]);
+
+        let collateral_count = self.collateral_count as usize;
+        require(
+            accounts.len() >= 10 + 2 * collateral_count,
+            "Missing collateral or oracle accounts",
+        );
+        let (collateral_accounts, rest) = accounts[10..].split_at(collateral_count);
+        let (oracle_accounts, vault_accounts) = rest.split_at(collateral_count);
+        let oracle_accounts = oracle_accounts
+            .iter()
+            .map(Readonly::new)
+            .collect::<Vec<_>>();
+        let collateral_accounts = collateral_accounts
+            .iter()
+            .map(Writable::new)
+            .collect::<Vec<_>>();
+        let mut collateral_data = collateral_accounts
+            .iter()
+            .map(|c| c.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut collateral = collateral_accounts
+            .into_iter()
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
+        let vault_accounts = vault_accounts.iter().map(Writable::new).collect::<Vec<_>>();
+        let mut vault_data = vault_accounts
+            .iter()
+            .map(|v| v.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut vaults = vault_accounts
+            .into_iter()
+            .zip(&mut vault_data)
+            .map(|(acc, data)| Vault::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
+
+        let authority = Authority::from_account(program_id, authority_account);
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.recovery.track(
+            &mut collateral,
+            &oracle_accounts,
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+        let max_ltv = world.recovery.get_max_ltv(
+            world.config.get_max_ltv(),
+            world.config.get_recovery_config(),
+            &clock,
+        );
+        world.redemption.redeem(
+            self.amount,
+            self.max_fee_rate,
+            program_id,
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd,
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            max_ltv,
+            world.config.get_vault_config(),
+            world.config.get_redemption_config(),
+            &mut world.ledger,
+            &mut world.savings,
+            world.config.get_savings_config(),
+            &mut collateral,
+            &oracle_accounts,
+            &mut vaults,
+            user_account,
+            dvd_mint_account,
+            dvd_account,
+            dvd_token_program_account,
+            safe_account,
+            collateral_token_account,
+            collateral_mint_account,
+            collateral_token_program_account,
+            authority,
+            &clock,
+        );
+    }
+}
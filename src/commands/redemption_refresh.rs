@@ -0,0 +1,193 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::{wasm_bindgen, JsValue},
+};
+use {
+    crate::{
+        accounts::{Readonly, Writable},
+        keys::{CollateralMintKey, OracleKey, VaultKey},
+        store::{Collateral, Vault, World},
+        traits::{Account, Command, Pod, Store},
+        util::require,
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Measures the collateral ratio of vaults into the redemption queue
+///
+/// Vaults that can't be redeemed against, because they have no debt, are
+/// liquidated or are unhealthy, are taken out of the queue.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` World account (PDA)
+/// 1..n. `[writable]` Collateral accounts for every reserve of the vaults (PDAs)
+/// n..m. `[]` Oracle accounts in the same order (PDAs)
+/// m..o. `[writable]` Vault accounts (PDAs)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct RedemptionRefresh {
+    collateral_count: u8,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl RedemptionRefresh {
+    #[wasm_bindgen(js_name = "getData")]
+    #[allow(non_snake_case)]
+    pub fn get_data_wasm(collateralCount: u8) -> Vec<u8> {
+        Self {
+            collateral_count: collateralCount,
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        collateralMintKeys: Vec<JsValue>,
+        oracleKeys: Vec<JsValue>,
+        vaultKeys: Vec<JsValue>,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let collateralMintKeys = collateralMintKeys
+            .into_iter()
+            .map(|key| -> Result<CollateralMintKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid collateral mint key: {}", e))?;
+                Ok(CollateralMintKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let oracleKeys = oracleKeys
+            .into_iter()
+            .map(|key| -> Result<OracleKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid oracle key: {}", e))?;
+                Ok(OracleKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let vaultKeys = vaultKeys
+            .into_iter()
+            .map(|key| -> Result<VaultKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid vault key: {}", e))?;
+                Ok(VaultKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let accounts = Self::get_accounts(programKey, (collateralMintKeys, oracleKeys, vaultKeys))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for RedemptionRefresh {}
+
+impl Command for RedemptionRefresh {
+    const ID: u32 = 0x7b7c3eb2;
+    type Keys = (Vec<CollateralMintKey>, Vec<OracleKey>, Vec<VaultKey>);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (collateral_mint_keys, oracle_keys, vault_keys) = keys;
+        let mut v = vec![AccountMeta {
+            pubkey: program_key.derive_world(),
+            is_signer: false,
+            is_writable: true,
+        }];
+        v.extend(collateral_mint_keys.iter().map(|c| AccountMeta {
+            pubkey: program_key.derive_collateral(c),
+            is_signer: false,
+            is_writable: true,
+        }));
+        v.extend(oracle_keys.into_iter().map(|o| AccountMeta {
+            pubkey: *o,
+            is_signer: false,
+            is_writable: false,
+        }));
+        v.extend(vault_keys.into_iter().map(|vault_key| AccountMeta {
+            pubkey: *vault_key,
+            is_signer: false,
+            is_writable: true,
+        }));
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let world_account = Writable::new(&accounts[0]);
+
+        let collateral_count = self.collateral_count as usize;
+        require(
+            accounts.len() >= 1 + 2 * collateral_count,
+            "Missing collateral or oracle accounts",
+        );
+        let (collateral_accounts, rest) = accounts[1..].split_at(collateral_count);
+        let (oracle_accounts, vault_accounts) = rest.split_at(collateral_count);
+        let oracle_accounts = oracle_accounts
+            .iter()
+            .map(Readonly::new)
+            .collect::<Vec<_>>();
+        let collateral_accounts = collateral_accounts
+            .iter()
+            .map(Writable::new)
+            .collect::<Vec<_>>();
+        let mut collateral_data = collateral_accounts
+            .iter()
+            .map(|c| c.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut collateral = collateral_accounts
+            .into_iter()
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
+        let vault_accounts = vault_accounts.iter().map(Writable::new).collect::<Vec<_>>();
+        let mut vault_data = vault_accounts
+            .iter()
+            .map(|v| v.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut vaults = vault_accounts
+            .into_iter()
+            .zip(&mut vault_data)
+            .map(|(acc, data)| Vault::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.recovery.track(
+            &mut collateral,
+            &oracle_accounts,
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+        let max_ltv = world.recovery.get_max_ltv(
+            world.config.get_max_ltv(),
+            world.config.get_recovery_config(),
+            &clock,
+        );
+        world.redemption.refresh(
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            max_ltv,
+            &mut world.ledger,
+            &mut collateral,
+            &oracle_accounts,
+            &mut vaults,
+            &clock,
+        );
+    }
+}
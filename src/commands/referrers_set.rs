@@ -0,0 +1,105 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::*,
+};
+use {
+    crate::{
+        accounts::{Readonly, Signer, Writable},
+        keys::SovereignKey,
+        store::World,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Replaces the wallets that may receive referral fees
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account
+/// 1. `[writable]` World account (PDA)
+/// 2..n. `[]` Referrer wallet accounts
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct ReferrersSet {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl ReferrersSet {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        referrerKeys: Vec<JsValue>,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let sovereign_key = SovereignKey::new(b2pk(sovereignKey)?);
+        let referrer_keys = referrerKeys
+            .into_iter()
+            .map(|key| -> Result<Pubkey, String> {
+                let key_bytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid referrer key: {}", e))?;
+                b2pk(&key_bytes)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let accounts = Self::get_accounts(program_key, (sovereign_key, referrer_keys))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for ReferrersSet {}
+
+impl Command for ReferrersSet {
+    const ID: u32 = 0xd75e7b0e;
+    type Keys = (SovereignKey, Vec<Pubkey>);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, referrer_keys) = keys;
+        let mut v = vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+        ];
+        v.extend(referrer_keys.into_iter().map(|r| AccountMeta {
+            pubkey: r,
+            is_signer: false,
+            is_writable: false,
+        }));
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Writable::new(&accounts[1]);
+        let referrer_accounts = accounts[2..].iter().map(Readonly::new).collect::<Vec<_>>();
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        world.referrers.set(sovereign_auth, &referrer_accounts);
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{TokenAccount, Writable},
+        store::{Collateral, Stability},
+        token::SafeOwner,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Records the token balance of a safe next to the amount deposited in it,
+/// so that any difference between the two can be read from the owner.
+/// Anyone may call this.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` Collateral or stability account (PDA)
+/// 1. `[writable]` Safe account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SafeReconcile {
+    owner: SafeOwner,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SafeReconcile {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(owner: SafeOwner) -> Vec<u8> {
+        Self { owner }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        owner: SafeOwner,
+        mintKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let mintKey = b2pk(mintKey)?;
+        let accounts = Self::get_accounts(programKey, (owner, mintKey))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SafeReconcile {}
+
+impl Command for SafeReconcile {
+    const ID: u32 = 0x50e85324;
+    type Keys = (SafeOwner, Pubkey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (owner, mint_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: program_key.derive_safe_owner(owner, &mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_safe(owner, &mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let owner_account = Writable::new(&accounts[0]);
+        let safe_account = TokenAccount::new(Writable::new(&accounts[1]));
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let mut owner_data = owner_account.get_info().data.borrow_mut();
+        match self.owner {
+            SafeOwner::Collateral => {
+                let collateral =
+                    Collateral::load_mut(program_id, owner_account, &mut owner_data[..], ());
+                collateral.reconcile(program_id, safe_account, &clock);
+            }
+            SafeOwner::Stability => {
+                let stability =
+                    Stability::load_mut(program_id, owner_account, &mut owner_data[..], ());
+                stability.reconcile(program_id, safe_account, &clock);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        keys::{SovereignKey, TokenProgramKey},
+        store::{Authority, Collateral, Stability, World},
+        token::SafeOwner,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Moves tokens that were sent to a safe by mistake to a treasury account
+///
+/// Only the balance above the amount deposited in the safe is moved, so
+/// deposits are never touched.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account
+/// 1. `[]` World account (PDA)
+/// 2. `[writable]` Collateral or stability account (PDA)
+/// 3. `[writable]` Safe account (PDA)
+/// 4. `[writable]` Treasury token account
+/// 5. `[]` Mint account of the safe
+/// 6. `[]` Token program of the mint
+/// 7. `[]` Authority account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SafeSweep {
+    owner: SafeOwner,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SafeSweep {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(owner: SafeOwner) -> Vec<u8> {
+        Self { owner }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+        owner: SafeOwner,
+        mintKey: &[u8],
+        treasuryTokenKey: &[u8],
+        tokenProgramKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let sovereignKey = SovereignKey::new(b2pk(sovereignKey)?);
+        let mintKey = b2pk(mintKey)?;
+        let treasuryTokenKey = b2pk(treasuryTokenKey)?;
+        let tokenProgramKey = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let accounts = Self::get_accounts(
+            programKey,
+            (
+                sovereignKey,
+                owner,
+                mintKey,
+                treasuryTokenKey,
+                tokenProgramKey,
+            ),
+        )
+        .into_iter()
+        .map(AccountWasm::from)
+        .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SafeSweep {}
+
+impl Command for SafeSweep {
+    const ID: u32 = 0x7ffc26b3;
+    type Keys = (SovereignKey, SafeOwner, Pubkey, Pubkey, TokenProgramKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (sovereign_key, owner, mint_key, treasury_token_key, token_program_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_safe_owner(owner, &mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_safe(owner, &mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: treasury_token_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Readonly::new(&accounts[1]);
+        let owner_account = Writable::new(&accounts[2]);
+        let safe_account = TokenAccount::new(Writable::new(&accounts[3]));
+        let treasury_token_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let mint_account = MintAccount::new(Readonly::new(&accounts[5]));
+        let token_program_account = TokenProgramAccount::new(&accounts[6]);
+        let authority_account = Readonly::new(&accounts[7]);
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        let authority = Authority::from_account(program_id, authority_account);
+
+        let mut owner_data = owner_account.get_info().data.borrow_mut();
+        match self.owner {
+            SafeOwner::Collateral => {
+                let (collateral, collateral_auth) = Collateral::load_auth(
+                    program_id,
+                    owner_account,
+                    &mut owner_data[..],
+                    sovereign_auth,
+                );
+                collateral.sweep(
+                    collateral_auth,
+                    program_id,
+                    safe_account,
+                    treasury_token_account,
+                    mint_account,
+                    token_program_account,
+                    authority,
+                );
+            }
+            SafeOwner::Stability => {
+                let (stability, stability_auth) = Stability::load_auth(
+                    program_id,
+                    owner_account,
+                    &mut owner_data[..],
+                    sovereign_auth,
+                );
+                stability.sweep(
+                    stability_auth,
+                    program_id,
+                    safe_account,
+                    treasury_token_account,
+                    mint_account,
+                    token_program_account,
+                    authority,
+                );
+            }
+        }
+    }
+}
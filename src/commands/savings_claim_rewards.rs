@@ -68,6 +68,7 @@ impl Command for SavingsClaimRewards {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dove_mint_key) = keys;
+        let dove_token_program_key = program_key.derive_token_program(&dove_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -90,12 +91,15 @@ impl Command for SavingsClaimRewards {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dove_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dove_mint_key,
+                    &dove_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: dove_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -130,6 +134,7 @@ impl Command for SavingsClaimRewards {
         let authority = Authority::from_account(program_id, authority_account);
 
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let savings_clock = world.settlement.get_savings_clock(&clock);
         savings.claim_rewards(
             savings_auth,
             &mut world.savings,
@@ -139,7 +144,7 @@ impl Command for SavingsClaimRewards {
             dove_token_account,
             authority,
             token_program_account,
-            &clock,
+            &savings_clock,
         );
     }
 }
@@ -71,6 +71,7 @@ impl Command for SavingsDeposit {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dvd_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -93,12 +94,15 @@ impl Command for SavingsDeposit {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -123,6 +127,7 @@ impl Command for SavingsDeposit {
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
         savings.deposit(
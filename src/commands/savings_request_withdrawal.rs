@@ -0,0 +1,115 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Readonly, Signer, Writable},
+        finance::Decimal,
+        keys::UserKey,
+        store::{Savings, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Requests a withdrawal from the savings account, claimable with
+/// `SavingsWithdraw` once the cooldown is over and until the claim window
+/// closes
+///
+/// A new request replaces the previous one and restarts the cooldown.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` Savings account (PDA)
+/// 2. `[]` World account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsRequestWithdrawal {
+    amount: Decimal,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SavingsRequestWithdrawal {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(amount: f64) -> Vec<u8> {
+        Self {
+            amount: Decimal::from(amount),
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let user_key = UserKey::new(b2pk(userKey)?);
+        let accounts = Self::get_accounts(program_key, user_key)
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SavingsRequestWithdrawal {}
+
+impl Command for SavingsRequestWithdrawal {
+    const ID: u32 = 0xdc843fee;
+    type Keys = UserKey;
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, user_key: Self::Keys) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_savings(&user_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let savings_account = Writable::new(&accounts[1]);
+        let world_account = Readonly::new(&accounts[2]);
+
+        let mut savings_data = savings_account.get_info().data.borrow_mut();
+        let (savings, savings_auth) = Savings::load_auth(
+            program_id,
+            savings_account,
+            &mut savings_data[..],
+            user_account,
+        );
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        savings.request_withdrawal(
+            savings_auth,
+            self.amount,
+            world.config.get_savings_exit_config(),
+            &clock,
+        );
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Readonly, Signer, SystemProgramAccount, TokenProgramAccount, Writable},
+        keys::SovereignKey,
+        store::{Authority, World},
+        token::{Mint, MintMetadata, Token},
+        traits::{Account, Command, Pod, Store},
+        util::Expect,
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey, rent::Rent, sysvar::Sysvar},
+};
+
+/// Creates the sDVD mint, enabling tokenised savings shares
+///
+/// The mint is a Token-2022 mint at a program-derived address, with the
+/// authority as mint authority and its metadata stored in the mint.
+///
+/// Accounts expected:
+///
+/// 0. `[signer, writable]` Sovereign account
+/// 1. `[writable]` World account (PDA)
+/// 2. `[]` Authority account (PDA)
+/// 3. `[writable]` Mint account for the sDVD (PDA, will be created)
+/// 4. `[]` System program
+/// 5. `[]` Token-2022 program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsShareCreate {
+    metadata: MintMetadata,
+    decimals: u8,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SavingsShareCreate {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(metadata: MintMetadata, decimals: u8) -> Vec<u8> {
+        Self { metadata, decimals }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let sovereign_key = SovereignKey::new(b2pk(sovereignKey)?);
+        let accounts = Self::get_accounts(program_key, sovereign_key)
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SavingsShareCreate {}
+
+impl Command for SavingsShareCreate {
+    const ID: u32 = 0x6ebfd70a;
+    type Keys = SovereignKey;
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, sovereign_key: Self::Keys) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_sdvd_mint(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: solana_program::system_program::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: spl_token_2022::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Writable::new(&accounts[1]);
+        let authority_account = Readonly::new(&accounts[2]);
+        let sdvd_mint_account = Writable::new(&accounts[3]);
+        let system_program_account = SystemProgramAccount::new(&accounts[4]);
+        let token_program_account = TokenProgramAccount::new(&accounts[5]);
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        let sovereign_auth = world.sovereign.authorize(sovereign_account);
+
+        let authority = Authority::from_account(program_id, authority_account);
+        let rent = Rent::get().map_err(|_| "Failed to get rent").unwrap();
+        let sdvd_mint_account = Mint::create(
+            program_id,
+            Mint::SDVD_SEED,
+            sovereign_account,
+            sdvd_mint_account,
+            system_program_account,
+            token_program_account,
+            authority,
+            self.decimals,
+            &self.metadata,
+            &rent,
+        );
+        let sdvd = Token::from_account(sdvd_mint_account, authority, Expect::None, Expect::None);
+        world.savings_share.enable(sovereign_auth, sdvd);
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::Writable,
+        store::World,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{
+        account_info::AccountInfo, clock::Clock, program::set_return_data, pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+/// Returns the DVD redeemable for one sDVD, for other programs to read
+/// through CPI
+///
+/// The exchange rate is set as return data, as a little-endian `u128`
+/// scaled by 10^18.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` World account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsShareExchangeRate {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SavingsShareExchangeRate {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(programKey: &[u8]) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let accounts = Self::get_accounts(program_key, ())
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SavingsShareExchangeRate {}
+
+impl Command for SavingsShareExchangeRate {
+    const ID: u32 = 0x552b0b01;
+    type Keys = ();
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, _: Self::Keys) -> Vec<AccountMeta> {
+        vec![AccountMeta {
+            pubkey: program_key.derive_world(),
+            is_signer: false,
+            is_writable: true,
+        }]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let world_account = Writable::new(&accounts[0]);
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let savings_clock = world.settlement.get_savings_clock(&clock);
+        let exchange_rate = world.savings_share.get_exchange_rate(
+            &mut world.savings,
+            &world.config.get_savings_config(),
+            &savings_clock,
+        );
+        set_return_data(exchange_rate.as_bytes());
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        keys::UserKey,
+        store::{Savings, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Burns sDVD to request the withdrawal of the DVD it is worth, while the
+/// savings withdrawal cooldown is set
+///
+/// The DVD moves into the user's savings account and is added to its
+/// withdrawal request, claimable with `SavingsWithdraw` once the cooldown is
+/// over and until the claim window closes. It keeps accruing until then.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` Savings account (PDA)
+/// 2. `[writable]` World account (PDA)
+/// 3. `[writable]` sDVD mint account
+/// 4. `[writable]` sDVD token account (to burn shares from)
+/// 5. `[]` Token-2022 program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsShareRequestWithdrawal {
+    shares: Decimal,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SavingsShareRequestWithdrawal {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(shares: f64) -> Vec<u8> {
+        Self {
+            shares: Decimal::from(shares),
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let user_key = UserKey::new(b2pk(userKey)?);
+        let accounts = Self::get_accounts(program_key, user_key)
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SavingsShareRequestWithdrawal {}
+
+impl Command for SavingsShareRequestWithdrawal {
+    const ID: u32 = 0x5b1e7a23;
+    type Keys = UserKey;
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, user_key: Self::Keys) -> Vec<AccountMeta> {
+        let sdvd_mint_key = program_key.derive_sdvd_mint();
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_savings(&user_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: sdvd_mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &sdvd_mint_key,
+                    &spl_token_2022::ID,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: spl_token_2022::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let savings_account = Writable::new(&accounts[1]);
+        let world_account = Writable::new(&accounts[2]);
+        let sdvd_mint_account = MintAccount::new(Writable::new(&accounts[3]));
+        let sdvd_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let sdvd_token_program_account = TokenProgramAccount::new(&accounts[5]);
+
+        let mut savings_data = savings_account.get_info().data.borrow_mut();
+        let (savings, savings_auth) = Savings::load_auth(
+            program_id,
+            savings_account,
+            &mut savings_data[..],
+            user_account,
+        );
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let savings_clock = world.settlement.get_savings_clock(&clock);
+        let amount = world.savings_share.redeem(
+            self.shares,
+            &mut world.savings,
+            &world.config.get_savings_config(),
+            user_account,
+            sdvd_mint_account,
+            sdvd_account,
+            sdvd_token_program_account,
+            &savings_clock,
+        );
+        savings.deposit_requested(
+            savings_auth,
+            amount,
+            &mut world.savings,
+            &world.config.get_savings_config(),
+            &world
+                .settlement
+                .get_savings_exit_config(world.config.get_savings_exit_config()),
+            &savings_clock,
+        );
+    }
+}
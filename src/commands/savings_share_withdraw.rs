@@ -0,0 +1,175 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        keys::{DvdMintKey, UserKey},
+        store::{Authority, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Burns sDVD, withdrawing the DVD it is worth at the current exchange rate
+///
+/// While the savings withdrawal cooldown is set, until settlement, the
+/// withdrawal must be requested with `SavingsShareRequestWithdrawal` instead.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` World account (PDA)
+/// 2. `[]` Authority account (PDA)
+/// 3. `[writable]` Debt token mint account
+/// 4. `[writable]` Debt token account (to transfer tokens to)
+/// 5. `[]` SPL Token program of the debt token
+/// 6. `[writable]` sDVD mint account
+/// 7. `[writable]` sDVD token account (to burn shares from)
+/// 8. `[]` Token-2022 program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsShareWithdraw {
+    shares: Decimal,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SavingsShareWithdraw {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(shares: f64) -> Vec<u8> {
+        Self {
+            shares: Decimal::from(shares),
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+        dvdMintKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let user_key = UserKey::new(b2pk(userKey)?);
+        let dvd_mint_key = DvdMintKey::new(b2pk(dvdMintKey)?);
+        let accounts = Self::get_accounts(program_key, (user_key, dvd_mint_key))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SavingsShareWithdraw {}
+
+impl Command for SavingsShareWithdraw {
+    const ID: u32 = 0x412ff984;
+    type Keys = (UserKey, DvdMintKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (user_key, dvd_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        let sdvd_mint_key = program_key.derive_sdvd_mint();
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *dvd_mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: dvd_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: sdvd_mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &sdvd_mint_key,
+                    &spl_token_2022::ID,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: spl_token_2022::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let world_account = Writable::new(&accounts[1]);
+        let authority_account = Readonly::new(&accounts[2]);
+        let dvd_mint_account = MintAccount::new(Writable::new(&accounts[3]));
+        let dvd_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let dvd_token_program_account = TokenProgramAccount::new(&accounts[5]);
+        let sdvd_mint_account = MintAccount::new(Writable::new(&accounts[6]));
+        let sdvd_account = TokenAccount::new(Writable::new(&accounts[7]));
+        let sdvd_token_program_account = TokenProgramAccount::new(&accounts[8]);
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+
+        let authority = Authority::from_account(program_id, authority_account);
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let savings_clock = world.settlement.get_savings_clock(&clock);
+        world.savings_share.withdraw(
+            self.shares,
+            &mut world.savings,
+            &world.config.get_savings_config(),
+            &mut world.savings_exit,
+            &world
+                .settlement
+                .get_savings_exit_config(world.config.get_savings_exit_config()),
+            &mut world.dvd,
+            user_account,
+            sdvd_mint_account,
+            sdvd_account,
+            sdvd_token_program_account,
+            dvd_mint_account,
+            dvd_account,
+            dvd_token_program_account,
+            authority,
+            &savings_clock,
+        );
+    }
+}
@@ -18,6 +18,10 @@ use {
 
 /// Withdraws tokens from the savings account
 ///
+/// When the savings withdrawal cooldown is set, at most the amount requested
+/// with `SavingsRequestWithdrawal` can be withdrawn, once the cooldown is over
+/// and before the request lapses.
+///
 /// Accounts expected:
 ///
 /// 0. `[signer]` User account
@@ -72,6 +76,7 @@ impl Command for SavingsWithdraw {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dvd_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -94,12 +99,15 @@ impl Command for SavingsWithdraw {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -134,17 +142,22 @@ impl Command for SavingsWithdraw {
         let authority = Authority::from_account(program_id, authority_account);
 
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let savings_clock = world.settlement.get_savings_clock(&clock);
         savings.withdraw(
             savings_auth,
             self.amount,
             &mut world.savings,
             &world.config.get_savings_config(),
+            &mut world.savings_exit,
+            &world
+                .settlement
+                .get_savings_exit_config(world.config.get_savings_exit_config()),
             &mut world.dvd,
             dvd_mint_account,
             dvd_account,
             token_program_account,
             authority,
-            &clock,
+            &savings_clock,
         );
     }
 }
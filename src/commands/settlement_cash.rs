@@ -0,0 +1,189 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        keys::{TokenProgramKey, UserKey},
+        store::{Authority, Collateral, SettlementClaim, Stability, World},
+        token::SafeOwner,
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Cashes in a settlement claim for one collateral or stablecoin
+///
+/// Pays out the claim's pro-rata share of the assets seized from vaults, or
+/// held by the stability module, for the DVD packed since the claim was last
+/// cashed in for that asset.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` Settlement claim account (PDA)
+/// 2. `[]` World account (PDA)
+/// 3. `[writable]` Collateral or stability account (PDA)
+/// 4. `[writable]` Safe account (PDA)
+/// 5. `[writable]` Token account (to transfer tokens to)
+/// 6. `[]` Mint account of the safe
+/// 7. `[]` Token program of the mint
+/// 8. `[]` Authority account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SettlementCash {
+    owner: SafeOwner,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SettlementCash {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(owner: SafeOwner) -> Vec<u8> {
+        Self { owner }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+        owner: SafeOwner,
+        mintKey: &[u8],
+        tokenProgramKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let programKey = ProgramKey::new(b2pk(programKey)?);
+        let userKey = UserKey::new(b2pk(userKey)?);
+        let mintKey = b2pk(mintKey)?;
+        let tokenProgramKey = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let accounts = Self::get_accounts(programKey, (userKey, owner, mintKey, tokenProgramKey))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for SettlementCash {}
+
+impl Command for SettlementCash {
+    const ID: u32 = 0xe0bbf37a;
+    type Keys = (UserKey, SafeOwner, Pubkey, TokenProgramKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (user_key, owner, mint_key, token_program_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_settlement_claim(&user_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_safe_owner(owner, &mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_safe(owner, &mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key
+                    .derive_associated_token_address_with_program(&mint_key, &token_program_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let claim_account = Writable::new(&accounts[1]);
+        let world_account = Readonly::new(&accounts[2]);
+        let owner_account = Writable::new(&accounts[3]);
+        let safe_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let token_account = TokenAccount::new(Writable::new(&accounts[5]));
+        let mint_account = MintAccount::new(Readonly::new(&accounts[6]));
+        let token_program_account = TokenProgramAccount::new(&accounts[7]);
+        let authority_account = Readonly::new(&accounts[8]);
+
+        let world_data = world_account.get_info().data.borrow();
+        let world = World::load(program_id, world_account, &world_data, ());
+        let claims = world.settlement.get_fixed_claims();
+
+        let mut claim_data = claim_account.get_info().data.borrow_mut();
+        let (claim, claim_auth) = SettlementClaim::load_auth(
+            program_id,
+            claim_account,
+            &mut claim_data[..],
+            user_account,
+        );
+        let amount = claim.cash(claim_auth, mint_account.get_info().key);
+
+        let authority = Authority::from_account(program_id, authority_account);
+
+        let mut owner_data = owner_account.get_info().data.borrow_mut();
+        match self.owner {
+            SafeOwner::Collateral => {
+                let collateral =
+                    Collateral::load_mut(program_id, owner_account, &mut owner_data[..], ());
+                collateral.pay_settlement(
+                    amount,
+                    claims,
+                    program_id,
+                    safe_account,
+                    token_account,
+                    mint_account,
+                    token_program_account,
+                    authority,
+                );
+            }
+            SafeOwner::Stability => {
+                let stability =
+                    Stability::load_mut(program_id, owner_account, &mut owner_data[..], ());
+                stability.pay_settlement(
+                    amount,
+                    claims,
+                    program_id,
+                    safe_account,
+                    token_account,
+                    mint_account,
+                    token_program_account,
+                    authority,
+                );
+            }
+        }
+    }
+}
@@ -9,7 +9,7 @@ use {
     crate::{
         accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
-        keys::{DvdMintKey, StableMintKey, UserKey},
+        keys::{DvdMintKey, StableMintKey, TokenProgramKey, UserKey},
         store::{Authority, Stability, World},
         traits::{Account, Command, Pod, Store},
     },
@@ -29,11 +29,16 @@ use {
 /// 6. `[writable]` Stability account (PDA)
 /// 7. `[]` Authority account (PDA)
 /// 8. `[]` SPL Token program
+/// 9. `[]` Stable mint account
+/// 10. `[]` Token program of the stable token (SPL Token or Token-2022)
+/// 11. `[writable]` Referrer DVD token account, only with `has_referrer` (to receive
+///     the referral share of the fee), owned by a registered referrer other than the user
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct StabilityBuyDvd {
     amount: Decimal,
+    has_referrer: bool,
 }
 
 #[cfg(feature = "wasm")]
@@ -41,9 +46,10 @@ pub struct StabilityBuyDvd {
 impl StabilityBuyDvd {
     #[wasm_bindgen(js_name = "getData")]
     #[allow(non_snake_case)]
-    pub fn get_data_wasm(amount: f64) -> Vec<u8> {
+    pub fn get_data_wasm(amount: f64, hasReferrer: bool) -> Vec<u8> {
         Self {
             amount: Decimal::from(amount),
+            has_referrer: hasReferrer,
         }
         .get_data()
     }
@@ -55,12 +61,23 @@ impl StabilityBuyDvd {
         userKey: &[u8],
         stableMintKey: &[u8],
         dvdMintKey: &[u8],
+        stableTokenProgramKey: &[u8],
+        referrerTokenKey: Option<Vec<u8>>,
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let userKey = UserKey::new(b2pk(userKey)?);
         let stableMintKey = StableMintKey::new(b2pk(stableMintKey)?);
+        let stableTokenProgramKey = TokenProgramKey::new(b2pk(stableTokenProgramKey)?)?;
         let dvdMintKey = DvdMintKey::new(b2pk(dvdMintKey)?);
-        let accounts = Self::get_accounts(programKey, (userKey, stableMintKey, dvdMintKey))
+        let referrerTokenKey = referrerTokenKey.map(|key| b2pk(&key)).transpose()?;
+        let keys = (
+            userKey,
+            stableMintKey,
+            dvdMintKey,
+            stableTokenProgramKey,
+            referrerTokenKey,
+        );
+        let accounts = Self::get_accounts(programKey, keys)
             .into_iter()
             .map(AccountWasm::from)
             .collect();
@@ -72,28 +89,46 @@ unsafe impl Pod for StabilityBuyDvd {}
 
 impl Command for StabilityBuyDvd {
     const ID: u32 = 0x52ea0e66;
-    type Keys = (UserKey, StableMintKey, DvdMintKey);
+    type Keys = (
+        UserKey,
+        StableMintKey,
+        DvdMintKey,
+        TokenProgramKey,
+        Option<Pubkey>,
+    );
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, stable_mint_key, dvd_mint_key) = keys;
-        vec![
+        let (user_key, stable_mint_key, dvd_mint_key, stable_token_program_key, referrer_token_key) =
+            keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        let mut v = vec![
             AccountMeta::new(*user_key, true),
             AccountMeta::new(
-                user_key.derive_associated_token_address(&stable_mint_key),
+                user_key.derive_associated_token_address_with_program(
+                    &stable_mint_key,
+                    &stable_token_program_key,
+                ),
                 false,
             ),
             AccountMeta::new(
-                user_key.derive_associated_token_address(&dvd_mint_key),
+                user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 false,
             ),
             AccountMeta::new(*dvd_mint_key, false),
-            AccountMeta::new(program_key.derive_safe(&stable_mint_key), false),
+            AccountMeta::new(program_key.derive_stability_safe(&stable_mint_key), false),
             AccountMeta::new(program_key.derive_world(), false),
             AccountMeta::new(program_key.derive_stability(&stable_mint_key), false),
             AccountMeta::new_readonly(program_key.derive_authority(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ]
+            AccountMeta::new_readonly(dvd_token_program_key, false),
+            AccountMeta::new_readonly(*stable_mint_key, false),
+            AccountMeta::new_readonly(*stable_token_program_key, false),
+        ];
+        v.extend(referrer_token_key.map(|r| AccountMeta::new(r, false)));
+        v
     }
 
     fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
@@ -106,9 +141,20 @@ impl Command for StabilityBuyDvd {
         let stability_account = Writable::new(&accounts[6]);
         let authority_account = Readonly::new(&accounts[7]);
         let token_program_account = TokenProgramAccount::new(&accounts[8]);
+        let stable_mint_account = MintAccount::new(Readonly::new(&accounts[9]));
+        let stable_token_program_account = TokenProgramAccount::new(&accounts[10]);
+        let referrer_account = self
+            .has_referrer
+            .then(|| TokenAccount::new(Writable::new(&accounts[11])));
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
+        if let Some(referrer_account) = referrer_account {
+            world
+                .referrers
+                .require_valid(&referrer_account.get_owner(), user_account.get_info().key);
+        }
 
         let mut stability_data = stability_account.get_info().data.borrow_mut();
         let stability =
@@ -117,20 +163,29 @@ impl Command for StabilityBuyDvd {
         let authority = Authority::from_account(program_id, authority_account);
 
         let clock = Clock::get().unwrap();
+        let dvd_deposits = world.get_dvd_deposits(&clock);
         stability.buy_dvd(
             self.amount,
             &mut world.dvd,
+            dvd_deposits,
             &mut world.dvd_price,
             &mut world.config.get_dvd_interest_rate(),
             &mut world.stable_dvd,
+            world.config.get_origination_config(),
+            &mut world.ledger,
+            &mut world.mint_limiter,
+            world.config.get_mint_limit_config(),
             program_id,
             authority,
             user_account,
             safe_account,
             stable_token_account,
             dvd_token_account,
+            referrer_account,
             dvd_mint_account,
+            stable_mint_account,
             token_program_account,
+            stable_token_program_account,
             &clock,
         );
     }
@@ -10,7 +10,7 @@ use {
         accounts::{
             MintAccount, Readonly, Signer, SystemProgramAccount, TokenProgramAccount, Writable,
         },
-        keys::{StableMintKey, SovereignKey},
+        keys::{SovereignKey, StableMintKey, TokenProgramKey},
         store::{Authority, Stability, StabilityParams, World},
         token::Safe,
         traits::{Account, Command, Pod, Store},
@@ -29,7 +29,7 @@ use {
 /// 4. `[]` World account (PDA)
 /// 5. `[]` Stable Mint account (for the stable token)
 /// 6. `[]` System program
-/// 7. `[]` SPL Token program
+/// 7. `[]` Token program of the mint (SPL Token or Token-2022)
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -51,11 +51,14 @@ impl StabilityCreate {
         programKey: &[u8],
         sovereignKey: &[u8],
         stableMintKey: &[u8],
+        tokenProgramKey: &[u8],
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let sovereignKey = SovereignKey::new(b2pk(sovereignKey)?);
         let stableMintKey = StableMintKey::new(b2pk(stableMintKey)?);
-        let accounts = Self::get_accounts(programKey, (sovereignKey, stableMintKey))
+        let tokenProgramKey = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let keys = (sovereignKey, stableMintKey, tokenProgramKey);
+        let accounts = Self::get_accounts(programKey, keys)
             .into_iter()
             .map(AccountWasm::from)
             .collect();
@@ -67,11 +70,11 @@ unsafe impl Pod for StabilityCreate {}
 
 impl Command for StabilityCreate {
     const ID: u32 = 0x7c23bedc;
-    type Keys = (SovereignKey, StableMintKey);
+    type Keys = (SovereignKey, StableMintKey, TokenProgramKey);
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (sovereign_key, stable_mint_key) = keys;
+        let (sovereign_key, stable_mint_key, token_program_key) = keys;
         vec![
             AccountMeta {
                 pubkey: *sovereign_key,
@@ -84,7 +87,7 @@ impl Command for StabilityCreate {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: program_key.derive_safe(&stable_mint_key),
+                pubkey: program_key.derive_stability_safe(&stable_mint_key),
                 is_signer: false,
                 is_writable: true,
             },
@@ -109,7 +112,7 @@ impl Command for StabilityCreate {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: *token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -133,7 +136,7 @@ impl Command for StabilityCreate {
         let authority = Authority::from_account(program_id, authority_account);
         let rent = Rent::get().map_err(|_| "Failed to get rent").unwrap();
 
-        let safe = Safe::create(
+        let safe = Safe::create::<Stability>(
             program_id,
             sovereign_account,
             safe_account,
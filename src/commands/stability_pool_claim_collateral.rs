@@ -0,0 +1,169 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        keys::{CollateralMintKey, TokenProgramKey, UserKey},
+        store::{Authority, Collateral, StabilityPool, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Claims the collateral gained by a stability pool deposit from liquidations
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` Stability pool account (PDA)
+/// 2. `[writable]` World account (PDA)
+/// 3. `[writable]` Collateral account (PDA)
+/// 4. `[writable]` Safe account (PDA)
+/// 5. `[writable]` Collateral token account (to transfer tokens to)
+/// 6. `[]` Collateral mint account
+/// 7. `[]` Token program of the collateral (SPL Token or Token-2022)
+/// 8. `[]` Authority account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct StabilityPoolClaimCollateral {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl StabilityPoolClaimCollateral {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+        collateralMintKey: &[u8],
+        tokenProgramKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let user_key = UserKey::new(b2pk(userKey)?);
+        let collateral_mint_key = CollateralMintKey::new(b2pk(collateralMintKey)?);
+        let token_program_key = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let accounts = Self::get_accounts(
+            program_key,
+            (user_key, collateral_mint_key, token_program_key),
+        )
+        .into_iter()
+        .map(AccountWasm::from)
+        .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for StabilityPoolClaimCollateral {}
+
+impl Command for StabilityPoolClaimCollateral {
+    const ID: u32 = 0x7a44a4bd;
+    type Keys = (UserKey, CollateralMintKey, TokenProgramKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (user_key, collateral_mint_key, token_program_key) = keys;
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_stability_pool(&user_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral(&collateral_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_collateral_safe(&collateral_mint_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &collateral_mint_key,
+                    &token_program_key,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *collateral_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let stability_pool_account = Writable::new(&accounts[1]);
+        let world_account = Writable::new(&accounts[2]);
+        let collateral_account = Writable::new(&accounts[3]);
+        let safe_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let collateral_token_account = TokenAccount::new(Writable::new(&accounts[5]));
+        let mint_account = MintAccount::new(Readonly::new(&accounts[6]));
+        let token_program_account = TokenProgramAccount::new(&accounts[7]);
+        let authority_account = Readonly::new(&accounts[8]);
+
+        let mut stability_pool_data = stability_pool_account.get_info().data.borrow_mut();
+        let (stability_pool, stability_pool_auth) = StabilityPool::load_auth(
+            program_id,
+            stability_pool_account,
+            &mut stability_pool_data[..],
+            user_account,
+        );
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+
+        let mut collateral_data = collateral_account.get_info().data.borrow_mut();
+        let collateral =
+            Collateral::load_mut(program_id, collateral_account, &mut collateral_data[..], ());
+
+        let authority = Authority::from_account(program_id, authority_account);
+        stability_pool.claim_collateral(
+            stability_pool_auth,
+            &mut world.stability_pool,
+            program_id,
+            collateral,
+            safe_account,
+            collateral_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        keys::{DvdMintKey, UserKey},
+        store::{Authority, StabilityPool, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, pubkey::Pubkey},
+};
+
+/// Withdraws DVD from the stability pool, net of the debt it absorbed
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` Stability pool account (PDA)
+/// 2. `[writable]` World account (PDA)
+/// 3. `[writable]` Debt token mint account
+/// 4. `[writable]` Debt token account (to transfer tokens to)
+/// 5. `[]` SPL Token program
+/// 6. `[]` Authority account (PDA)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct StabilityPoolWithdraw {
+    amount: Decimal,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl StabilityPoolWithdraw {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm(amount: f64) -> Vec<u8> {
+        Self {
+            amount: Decimal::from(amount),
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+        dvdMintKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let user_key = UserKey::new(b2pk(userKey)?);
+        let dvd_mint_key = DvdMintKey::new(b2pk(dvdMintKey)?);
+        let accounts = Self::get_accounts(program_key, (user_key, dvd_mint_key))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for StabilityPoolWithdraw {}
+
+impl Command for StabilityPoolWithdraw {
+    const ID: u32 = 0x500124d9;
+    type Keys = (UserKey, DvdMintKey);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (user_key, dvd_mint_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_stability_pool(&user_key),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *dvd_mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: dvd_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let stability_pool_account = Writable::new(&accounts[1]);
+        let world_account = Writable::new(&accounts[2]);
+        let dvd_mint_account = MintAccount::new(Writable::new(&accounts[3]));
+        let dvd_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let token_program_account = TokenProgramAccount::new(&accounts[5]);
+        let authority_account = Readonly::new(&accounts[6]);
+
+        let mut stability_pool_data = stability_pool_account.get_info().data.borrow_mut();
+        let (stability_pool, stability_pool_auth) = StabilityPool::load_auth(
+            program_id,
+            stability_pool_account,
+            &mut stability_pool_data[..],
+            user_account,
+        );
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+
+        let authority = Authority::from_account(program_id, authority_account);
+
+        stability_pool.withdraw(
+            stability_pool_auth,
+            self.amount,
+            &mut world.dvd,
+            &mut world.stability_pool,
+            dvd_mint_account,
+            dvd_account,
+            token_program_account,
+            authority,
+        );
+    }
+}
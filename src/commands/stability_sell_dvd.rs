@@ -9,7 +9,7 @@ use {
     crate::{
         accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
-        keys::{DvdMintKey, StableMintKey, UserKey},
+        keys::{DvdMintKey, StableMintKey, TokenProgramKey, UserKey},
         store::{Authority, Stability, World},
         traits::{Account, Command, Pod, Store},
     },
@@ -29,6 +29,8 @@ use {
 /// 6. `[writable]` Stability account (PDA)
 /// 7. `[]` Authority account (PDA)
 /// 8. `[]` SPL Token program
+/// 9. `[]` Stable mint account
+/// 10. `[]` Token program of the stable token (SPL Token or Token-2022)
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -55,12 +57,15 @@ impl StabilitySellDvd {
         userKey: &[u8],
         dvdMintKey: &[u8],
         stableMintKey: &[u8],
+        stableTokenProgramKey: &[u8],
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let userKey = UserKey::new(b2pk(userKey)?);
         let dvdMintKey = DvdMintKey::new(b2pk(dvdMintKey)?);
         let stableMintKey = StableMintKey::new(b2pk(stableMintKey)?);
-        let accounts = Self::get_accounts(programKey, (userKey, dvdMintKey, stableMintKey))
+        let stableTokenProgramKey = TokenProgramKey::new(b2pk(stableTokenProgramKey)?)?;
+        let keys = (userKey, dvdMintKey, stableMintKey, stableTokenProgramKey);
+        let accounts = Self::get_accounts(programKey, keys)
             .into_iter()
             .map(AccountWasm::from)
             .collect();
@@ -72,11 +77,12 @@ unsafe impl Pod for StabilitySellDvd {}
 
 impl Command for StabilitySellDvd {
     const ID: u32 = 0x31cac1de;
-    type Keys = (UserKey, DvdMintKey, StableMintKey);
+    type Keys = (UserKey, DvdMintKey, StableMintKey, TokenProgramKey);
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, dvd_mint_key, stable_mint_key) = keys;
+        let (user_key, dvd_mint_key, stable_mint_key, stable_token_program_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -84,7 +90,10 @@ impl Command for StabilitySellDvd {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -94,12 +103,15 @@ impl Command for StabilitySellDvd {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: program_key.derive_safe(&stable_mint_key),
+                pubkey: program_key.derive_stability_safe(&stable_mint_key),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&stable_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &stable_mint_key,
+                    &stable_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -119,7 +131,17 @@ impl Command for StabilitySellDvd {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *stable_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *stable_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -136,9 +158,12 @@ impl Command for StabilitySellDvd {
         let stability_account = Writable::new(&accounts[6]);
         let authority_account = Readonly::new(&accounts[7]);
         let token_program_account = TokenProgramAccount::new(&accounts[8]);
+        let stable_mint_account = MintAccount::new(Readonly::new(&accounts[9]));
+        let stable_token_program_account = TokenProgramAccount::new(&accounts[10]);
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let mut stability_data = stability_account.get_info().data.borrow_mut();
         let stability =
@@ -160,7 +185,9 @@ impl Command for StabilitySellDvd {
             stable_destination_token_account,
             dvd_source_token_account,
             dvd_mint_account,
+            stable_mint_account,
             token_program_account,
+            stable_token_program_account,
             &clock,
         );
     }
@@ -0,0 +1,159 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::{Decimal, InterestRate},
+        keys::{DvdMintKey, UserKey},
+        store::{TermDeposit, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Locks DVD in an empty term deposit until maturity
+///
+/// The deposit earns the term deposit rate offered now, which must be at
+/// least `min_rate`.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` User account
+/// 1. `[writable]` Term deposit account (PDA)
+/// 2. `[writable]` World account (PDA)
+/// 3. `[writable]` Debt token mint account
+/// 4. `[writable]` Debt token account (to transfer tokens from)
+/// 5. `[]` SPL Token program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct TermDepositDeposit {
+    amount: Decimal,
+    min_rate: InterestRate,
+    index: u64,
+    term: u64,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl TermDepositDeposit {
+    #[wasm_bindgen(js_name = "getData")]
+    #[allow(non_snake_case)]
+    pub fn get_data_wasm(index: u64, amount: f64, term: u64, minRate: InterestRate) -> Vec<u8> {
+        Self {
+            amount: Decimal::from(amount),
+            min_rate: minRate,
+            index,
+            term,
+        }
+        .get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        userKey: &[u8],
+        dvdMintKey: &[u8],
+        index: u64,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let user_key = UserKey::new(b2pk(userKey)?);
+        let dvd_mint_key = DvdMintKey::new(b2pk(dvdMintKey)?);
+        let accounts = Self::get_accounts(program_key, (user_key, dvd_mint_key, index))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for TermDepositDeposit {}
+
+impl Command for TermDepositDeposit {
+    const ID: u32 = 0xa8746b2c;
+    type Keys = (UserKey, DvdMintKey, u64);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (user_key, dvd_mint_key, index) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        vec![
+            AccountMeta {
+                pubkey: *user_key,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_term_deposit(&user_key, index),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *dvd_mint_key,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: dvd_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let user_account = Signer::new(&accounts[0]);
+        let term_deposit_account = Writable::new(&accounts[1]);
+        let world_account = Writable::new(&accounts[2]);
+        let dvd_mint_account = MintAccount::new(Writable::new(&accounts[3]));
+        let dvd_account = TokenAccount::new(Writable::new(&accounts[4]));
+        let token_program_account = TokenProgramAccount::new(&accounts[5]);
+
+        let mut term_deposit_data = term_deposit_account.get_info().data.borrow_mut();
+        let (term_deposit, term_deposit_auth) = TermDeposit::load_auth(
+            program_id,
+            term_deposit_account,
+            &mut term_deposit_data[..],
+            user_account,
+        );
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        term_deposit.deposit(
+            term_deposit_auth,
+            self.amount,
+            self.term,
+            self.min_rate,
+            &mut world.dvd,
+            &mut world.term_savings,
+            world.config.get_term_savings_config(),
+            user_account,
+            dvd_mint_account,
+            dvd_account,
+            token_program_account,
+            &clock,
+        );
+    }
+}
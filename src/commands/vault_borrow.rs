@@ -8,7 +8,7 @@ use {
 use {
     crate::{
         accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
-        finance::Decimal,
+        finance::{Decimal, InterestRate},
         keys::{CollateralMintKey, DvdMintKey, OracleKey, UserKey},
         store::{Authority, Collateral, Vault, World},
         traits::{Account, Command, Pod, Store},
@@ -19,6 +19,13 @@ use {
 
 /// Borrows tokens from the world
 ///
+/// Blocked if it leaves the protocol in recovery mode. While recovery mode is
+/// enabled, the collateral types the vault doesn't hold must have been
+/// recorded within the max age, with `CollateralRecordValue` if no other
+/// command priced them. A non-zero
+/// `fixed_term` starts a fixed-rate term of that many seconds, which fails if
+/// the offered rate exceeds `max_fixed_rate` or the vault already has a term.
+///
 /// Accounts expected:
 ///
 /// 0. `[signer]` User account
@@ -28,13 +35,19 @@ use {
 /// 4. `[writable]` Vault account (PDA)
 /// 5. `[]` Authority account (PDA)
 /// 6. `[]` SPL Token program
-/// 7..n. `[]` Collateral accounts in order of vault reserves (PDAs)
+/// 7..n. `[writable]` Collateral accounts in order of vault reserves (PDAs)
 /// n..m. `[]` Oracle accounts in order of vault reserves (PDAs)
+/// m. `[writable]` Referrer debt token account, only with `has_referrer` (to
+///    receive the referral share of the fee), owned by a registered referrer
+///    other than the user
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct VaultBorrow {
     requested_amount: Decimal,
+    max_fixed_rate: InterestRate,
+    fixed_term: u64,
+    has_referrer: bool,
 }
 
 #[cfg(feature = "wasm")]
@@ -42,9 +55,17 @@ pub struct VaultBorrow {
 impl VaultBorrow {
     #[wasm_bindgen(js_name = "getData")]
     #[allow(non_snake_case)]
-    pub fn get_data_wasm(requestedAmount: f64) -> Vec<u8> {
+    pub fn get_data_wasm(
+        requestedAmount: f64,
+        fixedTerm: u64,
+        hasReferrer: bool,
+        maxFixedRate: Option<InterestRate>,
+    ) -> Vec<u8> {
         Self {
             requested_amount: Decimal::from(requestedAmount),
+            max_fixed_rate: maxFixedRate.unwrap_or_default(),
+            fixed_term: fixedTerm,
+            has_referrer: hasReferrer,
         }
         .get_data()
     }
@@ -57,10 +78,12 @@ impl VaultBorrow {
         dvdMintKey: &[u8],
         collateralMintKeys: Vec<JsValue>,
         oracleKeys: Vec<JsValue>,
+        referrerTokenKey: Option<Vec<u8>>,
     ) -> Result<Vec<AccountWasm>, String> {
         let program_key = ProgramKey::new(b2pk(programKey)?);
         let user_key = UserKey::new(b2pk(userKey)?);
         let dvd_mint_key = DvdMintKey::new(b2pk(dvdMintKey)?);
+        let referrer_token_key = referrerTokenKey.map(|key| b2pk(&key)).transpose()?;
         let collateral_mint_keys = collateralMintKeys
             .into_iter()
             .map(|key| -> Result<CollateralMintKey, String> {
@@ -79,7 +102,13 @@ impl VaultBorrow {
             .collect::<Result<Vec<_>, String>>()?;
         let accounts = Self::get_accounts(
             program_key,
-            (user_key, dvd_mint_key, collateral_mint_keys, oracle_keys),
+            (
+                user_key,
+                dvd_mint_key,
+                collateral_mint_keys,
+                oracle_keys,
+                referrer_token_key,
+            ),
         )
         .into_iter()
         .map(AccountWasm::from)
@@ -92,11 +121,18 @@ unsafe impl Pod for VaultBorrow {}
 
 impl Command for VaultBorrow {
     const ID: u32 = 0x0b05f1e1;
-    type Keys = (UserKey, DvdMintKey, Vec<CollateralMintKey>, Vec<OracleKey>);
+    type Keys = (
+        UserKey,
+        DvdMintKey,
+        Vec<CollateralMintKey>,
+        Vec<OracleKey>,
+        Option<Pubkey>,
+    );
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, dvd_mint_key, collateral_mint_keys, oracle_keys) = keys;
+        let (user_key, dvd_mint_key, collateral_mint_keys, oracle_keys, referrer_token_key) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         let mut v = vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -109,7 +145,10 @@ impl Command for VaultBorrow {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -129,7 +168,7 @@ impl Command for VaultBorrow {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -137,13 +176,18 @@ impl Command for VaultBorrow {
         v.extend(collateral_mint_keys.into_iter().map(|c| AccountMeta {
             pubkey: program_key.derive_collateral(&c),
             is_signer: false,
-            is_writable: false,
+            is_writable: true,
         }));
         v.extend(oracle_keys.into_iter().map(|o| AccountMeta {
             pubkey: *o,
             is_signer: false,
             is_writable: false,
         }));
+        v.extend(referrer_token_key.map(|r| AccountMeta {
+            pubkey: r,
+            is_signer: false,
+            is_writable: true,
+        }));
         v
     }
 
@@ -155,12 +199,20 @@ impl Command for VaultBorrow {
         let vault_account = Writable::new(&accounts[4]);
         let authority_account = Readonly::new(&accounts[5]);
         let token_program_account = TokenProgramAccount::new(&accounts[6]);
-        let (collateral_accounts, oracle_accounts) = {
-            let a = &accounts[7..];
+        let (referrer_account, collateral_accounts, oracle_accounts) = {
+            let (a, referrer_account) = if self.has_referrer {
+                match accounts[7..].split_last() {
+                    Some((last, a)) => (a, Some(TokenAccount::new(Writable::new(last)))),
+                    None => revert("Missing referrer account"),
+                }
+            } else {
+                (&accounts[7..], None)
+            };
             if (a.len() % 2) != 0 {
                 revert("there should be an equal # of collateral and oracle accounts");
             }
-            a.split_at(a.len() / 2)
+            let (collateral_accounts, oracle_accounts) = a.split_at(a.len() / 2);
+            (referrer_account, collateral_accounts, oracle_accounts)
         };
         let oracle_accounts = oracle_accounts
             .iter()
@@ -168,20 +220,26 @@ impl Command for VaultBorrow {
             .collect::<Vec<_>>();
         let collateral_accounts = collateral_accounts
             .iter()
-            .map(Readonly::new)
+            .map(Writable::new)
             .collect::<Vec<_>>();
-        let collateral_data = collateral_accounts
+        let mut collateral_data = collateral_accounts
             .iter()
-            .map(|c| c.get_info().data.borrow())
+            .map(|c| c.get_info().data.borrow_mut())
             .collect::<Vec<_>>();
-        let collateral = collateral_accounts
+        let mut collateral = collateral_accounts
             .into_iter()
-            .zip(&collateral_data)
-            .map(|(acc, data)| Collateral::load(program_id, acc, data, ()))
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
             .collect::<Vec<_>>();
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
+        if let Some(referrer_account) = referrer_account {
+            world
+                .referrers
+                .require_valid(&referrer_account.get_owner(), user_account.get_info().key);
+        }
 
         let mut vault_data = vault_account.get_info().data.borrow_mut();
         let (vault, vault_auth) =
@@ -190,22 +248,66 @@ impl Command for VaultBorrow {
         let authority = Authority::from_account(program_id, authority_account);
 
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.recovery.track(
+            &mut collateral,
+            &oracle_accounts,
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+        let max_ltv = world.recovery.get_max_ltv(
+            world.config.get_max_ltv(),
+            world.config.get_recovery_config(),
+            &clock,
+        );
+        let fixed_term = (self.fixed_term > 0).then(|| {
+            world.config.get_fixed_rate_config().start_term(
+                self.fixed_term,
+                self.max_fixed_rate,
+                &mut world.debt,
+                world.config.get_debt_config(),
+                &clock,
+            )
+        });
+        let dvd_deposits = world.get_dvd_deposits(&clock);
         vault.borrow(
             vault_auth,
             self.requested_amount,
+            fixed_term,
             &mut world.debt,
             &world.config.get_debt_config(),
             &mut world.dvd,
+            dvd_deposits,
             &mut world.dvd_price,
             &world.config.get_dvd_interest_rate(),
-            world.config.get_max_ltv(),
+            max_ltv,
+            world.config.get_vault_config(),
+            world.config.get_origination_config(),
+            &mut world.ledger,
+            &mut world.mint_limiter,
+            world.config.get_mint_limit_config(),
             authority,
-            &collateral,
+            &mut collateral,
             &oracle_accounts,
             mint_account,
             dvd_account,
+            referrer_account,
             token_program_account,
             &clock,
         );
+        world.recovery.track(
+            &mut collateral,
+            &oracle_accounts,
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+        world
+            .recovery
+            .require_borrow(world.config.get_recovery_config(), &clock);
     }
 }
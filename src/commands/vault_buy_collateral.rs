@@ -9,7 +9,7 @@ use {
     crate::{
         accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
-        keys::{CollateralMintKey, DvdMintKey, UserKey, VaultKey},
+        keys::{CollateralMintKey, DvdMintKey, TokenProgramKey, UserKey, VaultKey},
         store::{Authority, Collateral, Vault, World},
         traits::{Account, Command, Pod, Store},
     },
@@ -30,6 +30,8 @@ use {
 /// 7. `[]` Authority account (PDA)
 /// 8. `[]` SPL Token program
 /// 9. `[writable]` Collateral account (PDA)
+/// 10. `[]` Collateral mint account
+/// 11. `[]` Token program of the collateral (SPL Token or Token-2022)
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -59,15 +61,23 @@ impl VaultBuyCollateral {
         vaultKey: &[u8],
         dvdMintKey: &[u8],
         collateralMintKey: &[u8],
+        collateralTokenProgramKey: &[u8],
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let userKey = UserKey::new(b2pk(userKey)?);
         let vaultKey = VaultKey::new(b2pk(vaultKey)?);
         let dvdMintKey = DvdMintKey::new(b2pk(dvdMintKey)?);
         let collateralMintKey = CollateralMintKey::new(b2pk(collateralMintKey)?);
+        let collateralTokenProgramKey = TokenProgramKey::new(b2pk(collateralTokenProgramKey)?)?;
         let accounts = Self::get_accounts(
             programKey,
-            (userKey, vaultKey, dvdMintKey, collateralMintKey),
+            (
+                userKey,
+                vaultKey,
+                dvdMintKey,
+                collateralMintKey,
+                collateralTokenProgramKey,
+            ),
         )
         .into_iter()
         .map(AccountWasm::from)
@@ -80,11 +90,19 @@ unsafe impl Pod for VaultBuyCollateral {}
 
 impl Command for VaultBuyCollateral {
     const ID: u32 = 0xb91a7697;
-    type Keys = (UserKey, VaultKey, DvdMintKey, CollateralMintKey);
+    type Keys = (
+        UserKey,
+        VaultKey,
+        DvdMintKey,
+        CollateralMintKey,
+        TokenProgramKey,
+    );
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, vault_key, dvd_mint_key, collateral_mint_key) = keys;
+        let (user_key, vault_key, dvd_mint_key, collateral_mint_key, collateral_token_program_key) =
+            keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -92,7 +110,10 @@ impl Command for VaultBuyCollateral {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -102,12 +123,15 @@ impl Command for VaultBuyCollateral {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: program_key.derive_safe(&collateral_mint_key),
+                pubkey: program_key.derive_collateral_safe(&collateral_mint_key),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&collateral_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &collateral_mint_key,
+                    &collateral_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -127,7 +151,7 @@ impl Command for VaultBuyCollateral {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -136,6 +160,16 @@ impl Command for VaultBuyCollateral {
                 is_signer: false,
                 is_writable: true,
             },
+            AccountMeta {
+                pubkey: *collateral_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *collateral_token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
         ]
     }
 
@@ -150,9 +184,12 @@ impl Command for VaultBuyCollateral {
         let authority_account = Readonly::new(&accounts[7]);
         let token_program_account = TokenProgramAccount::new(&accounts[8]);
         let collateral_account = Writable::new(&accounts[9]);
+        let collateral_mint_account = MintAccount::new(Readonly::new(&accounts[10]));
+        let collateral_token_program_account = TokenProgramAccount::new(&accounts[11]);
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let mut vault_data = vault_account.get_info().data.borrow_mut();
         let vault = Vault::load_mut(program_id, vault_account, &mut vault_data[..], ());
@@ -170,14 +207,22 @@ impl Command for VaultBuyCollateral {
             collateral,
             &mut world.debt,
             &world.config.get_debt_config(),
+            &mut world.insurance_fund,
+            world.config.get_insurance_rate(),
             &mut world.dvd,
             &world.config.get_auction_config(),
+            world.config.get_vault_config(),
+            &mut world.ledger,
+            &mut world.savings,
+            world.config.get_savings_config(),
             user_account,
             dvd_token_account,
             dvd_mint_account,
             safe_account,
             collateral_destination_token_account,
+            collateral_mint_account,
             token_program_account,
+            collateral_token_program_account,
             self.collateral_index as usize,
             authority,
             &clock,
@@ -68,6 +68,7 @@ impl Command for VaultClaimRewards {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, dove_mint_key) = keys;
+        let dove_token_program_key = program_key.derive_token_program(&dove_mint_key);
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -90,12 +91,15 @@ impl Command for VaultClaimRewards {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dove_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dove_mint_key,
+                    &dove_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::ID,
+                pubkey: dove_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -1,8 +1,8 @@
 use {
     crate::{
-        accounts::{Signer, TokenAccount, TokenProgramAccount, Writable},
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
-        keys::{CollateralMintKey, UserKey},
+        keys::{CollateralMintKey, TokenProgramKey, UserKey},
         store::{Collateral, Vault},
         traits::{Account, Command, Pod, Store},
     },
@@ -27,7 +27,8 @@ use {
 /// 2. `[writable]` Collateral account (PDA)
 /// 3. `[writable]` User's token account (source of tokens)
 /// 4. `[writable]` Collateral's token account (destination for tokens)
-/// 5. `[]` SPL Token program
+/// 5. `[]` Token program of the collateral (SPL Token or Token-2022)
+/// 6. `[]` Collateral mint account
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -52,11 +53,14 @@ impl VaultDeposit {
         programKey: &[u8],
         userKey: &[u8],
         collateralMintKey: &[u8],
+        tokenProgramKey: &[u8],
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let userKey = UserKey::new(b2pk(userKey)?);
         let collateralMintKey = CollateralMintKey::new(b2pk(collateralMintKey)?);
-        let accounts = Self::get_accounts(programKey, (userKey, collateralMintKey))
+        let tokenProgramKey = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
+        let keys = (userKey, collateralMintKey, tokenProgramKey);
+        let accounts = Self::get_accounts(programKey, keys)
             .into_iter()
             .map(AccountWasm::from)
             .collect();
@@ -68,11 +72,11 @@ unsafe impl Pod for VaultDeposit {}
 
 impl Command for VaultDeposit {
     const ID: u32 = 0x295bcc0f;
-    type Keys = (UserKey, CollateralMintKey);
+    type Keys = (UserKey, CollateralMintKey, TokenProgramKey);
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, collateral_mint_key) = keys;
+        let (user_key, collateral_mint_key, token_program_key) = keys;
         vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -90,17 +94,25 @@ impl Command for VaultDeposit {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&collateral_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &collateral_mint_key,
+                    &token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: program_key.derive_safe(&collateral_mint_key),
+                pubkey: program_key.derive_collateral_safe(&collateral_mint_key),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: *token_program_key,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: *collateral_mint_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -114,6 +126,7 @@ impl Command for VaultDeposit {
         let user_token_account = TokenAccount::new(Writable::new(&accounts[3]));
         let collateral_token_account = TokenAccount::new(Writable::new(&accounts[4]));
         let token_program_account = TokenProgramAccount::new(&accounts[5]);
+        let mint_account = MintAccount::new(Readonly::new(&accounts[6]));
 
         let mut vault_data = vault_account.get_info().data.borrow_mut();
         let (vault, vault_auth) =
@@ -131,6 +144,7 @@ impl Command for VaultDeposit {
             user_account,
             user_token_account,
             collateral_token_account,
+            mint_account,
             token_program_account,
         );
     }
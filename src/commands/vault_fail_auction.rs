@@ -3,13 +3,13 @@ use {
     crate::keys::ProgramKey,
     crate::util::{b2pk, AccountWasm},
     solana_program::instruction::AccountMeta,
-    wasm_bindgen::prelude::wasm_bindgen,
+    wasm_bindgen::prelude::{wasm_bindgen, JsValue},
 };
 use {
     crate::{
         accounts::{MintAccount, Readonly, TokenAccount, TokenProgramAccount, Writable},
-        keys::{DvdMintKey, UserKey, VaultKey},
-        store::{Authority, Vault, World},
+        keys::{CollateralMintKey, DvdMintKey, UserKey, VaultKey},
+        store::{Authority, Collateral, Vault, World},
         traits::{Account, Command, Pod, Store},
     },
     solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
@@ -17,6 +17,9 @@ use {
 
 /// Fails the auction for a liquidated vault
 ///
+/// The remaining debt is written off as bad debt, and the collateral left in
+/// the vault is seized by the protocol.
+///
 /// Accounts expected:
 /// 0. `[writable]` Vault account (PDA) for which to fail the auction
 /// 1. `[writable]` World account (PDA)
@@ -24,6 +27,7 @@ use {
 /// 3. `[writable]` Debt token account (to receive auction failure reward)
 /// 4. `[]` Authority account (PDA)
 /// 5. `[]` SPL Token program
+/// 6..n. `[writable]` Collateral accounts in order of vault reserves (PDAs)
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -46,15 +50,27 @@ impl VaultFailAuction {
         userKey: &[u8],
         vaultKey: &[u8],
         dvdMintKey: &[u8],
+        collateralMintKeys: Vec<JsValue>,
     ) -> Result<Vec<AccountWasm>, String> {
         let program_key = ProgramKey::new(b2pk(programKey)?);
         let user_key = UserKey::new(b2pk(userKey)?);
         let vault_key = VaultKey::new(b2pk(vaultKey)?);
         let dvd_mint_key = DvdMintKey::new(b2pk(dvdMintKey)?);
-        let accounts = Self::get_accounts(program_key, (user_key, vault_key, dvd_mint_key))
+        let collateral_mint_keys = collateralMintKeys
             .into_iter()
-            .map(AccountWasm::from)
-            .collect();
+            .map(|key| -> Result<CollateralMintKey, String> {
+                let key_bytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid collateral mint key: {}", e))?;
+                Ok(CollateralMintKey::new(b2pk(&key_bytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let accounts = Self::get_accounts(
+            program_key,
+            (user_key, vault_key, dvd_mint_key, collateral_mint_keys),
+        )
+        .into_iter()
+        .map(AccountWasm::from)
+        .collect();
         Ok(accounts)
     }
 }
@@ -63,12 +79,13 @@ unsafe impl Pod for VaultFailAuction {}
 
 impl Command for VaultFailAuction {
     const ID: u32 = 0x9a634fdd;
-    type Keys = (UserKey, VaultKey, DvdMintKey);
+    type Keys = (UserKey, VaultKey, DvdMintKey, Vec<CollateralMintKey>);
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, vault_key, dvd_mint_key) = keys;
-        vec![
+        let (user_key, vault_key, dvd_mint_key, collateral_mint_keys) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        let mut v = vec![
             AccountMeta {
                 pubkey: *vault_key,
                 is_signer: false,
@@ -85,7 +102,10 @@ impl Command for VaultFailAuction {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -95,11 +115,17 @@ impl Command for VaultFailAuction {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
-        ]
+        ];
+        v.extend(collateral_mint_keys.into_iter().map(|c| AccountMeta {
+            pubkey: program_key.derive_collateral(&c),
+            is_signer: false,
+            is_writable: true,
+        }));
+        v
     }
 
     fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
@@ -109,12 +135,23 @@ impl Command for VaultFailAuction {
         let dvd_account = TokenAccount::new(Writable::new(&accounts[3]));
         let authority_account = Readonly::new(&accounts[4]);
         let token_program_account = TokenProgramAccount::new(&accounts[5]);
+        let collateral_accounts = accounts[6..].iter().map(Writable::new).collect::<Vec<_>>();
+        let mut collateral_data = collateral_accounts
+            .iter()
+            .map(|c| c.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut collateral = collateral_accounts
+            .into_iter()
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
 
         let mut vault_data = vault_account.get_info().data.borrow_mut();
         let vault = Vault::load_mut(program_id, vault_account, &mut vault_data[..], ());
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let authority = Authority::from_account(program_id, authority_account);
 
@@ -122,9 +159,12 @@ impl Command for VaultFailAuction {
         vault.fail_auction(
             &mut world.debt,
             &world.config.get_debt_config(),
+            &mut world.ledger,
+            &mut world.insurance_fund,
             &world.config.get_vault_config(),
             &world.config.get_auction_config(),
             &mut world.dvd,
+            &mut collateral,
             dvd_mint_account,
             dvd_account,
             token_program_account,
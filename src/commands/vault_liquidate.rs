@@ -18,6 +18,11 @@ use {
 
 /// Liquidates a vault
 ///
+/// The stability pool absorbs the debt when it can, otherwise the vault goes
+/// up for auction. In recovery mode, vaults are held to the tighter `max_ltv`.
+/// With recovery mode enabled, collateral types the vault doesn't hold must
+/// have been recorded within the max age.
+///
 /// Accounts expected:
 ///
 /// 0. `[writable]` Debt token mint account
@@ -26,7 +31,7 @@ use {
 /// 3. `[writable]` Vault account (PDA) to be liquidated
 /// 4. `[]` Authority account (PDA)
 /// 5. `[]` SPL Token program
-/// 6..n. `[]` Collateral accounts in order of vault reserves (PDAs)
+/// 6..n. `[writable]` Collateral accounts in order of vault reserves (PDAs)
 /// n..m. `[]` Oracle accounts in order of vault reserves (PDAs)
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -106,6 +111,7 @@ impl Command for VaultLiquidate {
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
         let (user_key, vault_key, dvd_mint_key, collateral_mint_keys, oracle_keys) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
         let mut v = vec![
             AccountMeta {
                 pubkey: *dvd_mint_key,
@@ -113,7 +119,10 @@ impl Command for VaultLiquidate {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -133,7 +142,7 @@ impl Command for VaultLiquidate {
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -141,7 +150,7 @@ impl Command for VaultLiquidate {
         v.extend(collateral_mint_keys.into_iter().map(|c| AccountMeta {
             pubkey: program_key.derive_collateral(&c),
             is_signer: false,
-            is_writable: false,
+            is_writable: true,
         }));
         v.extend(oracle_keys.into_iter().map(|o| AccountMeta {
             pubkey: *o,
@@ -171,35 +180,54 @@ impl Command for VaultLiquidate {
             .collect::<Vec<_>>();
         let collateral_accounts = collateral_accounts
             .iter()
-            .map(Readonly::new)
+            .map(Writable::new)
             .collect::<Vec<_>>();
-        let collateral_data = collateral_accounts
+        let mut collateral_data = collateral_accounts
             .iter()
-            .map(|c| c.get_info().data.borrow())
+            .map(|c| c.get_info().data.borrow_mut())
             .collect::<Vec<_>>();
-        let collateral = collateral_accounts
+        let mut collateral = collateral_accounts
             .into_iter()
-            .zip(&collateral_data)
-            .map(|(acc, data)| Collateral::load(program_id, acc, data, ()))
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
             .collect::<Vec<_>>();
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        world.settlement.require_live();
 
         let mut vault_data = vault_account.get_info().data.borrow_mut();
         let vault = Vault::load_mut(program_id, vault_account, &mut vault_data[..], ());
 
         let authority = Authority::from_account(program_id, authority_account);
         let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
-        vault.liquidate(
+        world.recovery.track(
+            &mut collateral,
+            &oracle_accounts,
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.dvd_price,
+            world.config.get_dvd_interest_rate(),
+            &clock,
+        );
+        let max_ltv = world.recovery.get_max_ltv(
             world.config.get_max_ltv(),
+            world.config.get_recovery_config(),
+            &clock,
+        );
+        vault.liquidate(
+            max_ltv,
             &mut world.debt,
             &world.config.get_debt_config(),
+            &mut world.ledger,
+            &mut world.savings,
+            world.config.get_savings_config(),
+            &mut world.stability_pool,
             &world.config.get_vault_config(),
             &mut world.dvd,
             &mut world.dvd_price,
             &world.config.get_dvd_interest_rate(),
-            &collateral,
+            &mut collateral,
             &oracle_accounts,
             dvd_mint_account,
             dvd_account,
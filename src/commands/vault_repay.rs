@@ -3,14 +3,14 @@ use {
     crate::keys::ProgramKey,
     crate::util::{b2pk, AccountWasm},
     solana_program::instruction::AccountMeta,
-    wasm_bindgen::prelude::wasm_bindgen,
+    wasm_bindgen::prelude::{wasm_bindgen, JsValue},
 };
 use {
     crate::{
         accounts::{MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
-        keys::{DvdMintKey, UserKey},
-        store::{Vault, World},
+        keys::{CollateralMintKey, DvdMintKey, UserKey},
+        store::{Collateral, Vault, World},
         traits::{Account, Command, Pod, Store},
     },
     solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
@@ -25,6 +25,7 @@ use {
 /// 3. `[writable]` World account (PDA)
 /// 4. `[writable]` Vault account (PDA)
 /// 5. `[]` SPL Token program
+/// 6..n. `[writable]` Collateral accounts of the reserves whose stability fees to charge, in any order, optional (PDAs)
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -50,11 +51,20 @@ impl VaultRepay {
         programKey: &[u8],
         userKey: &[u8],
         dvdMintKey: &[u8],
+        collateralMintKeys: Vec<JsValue>,
     ) -> Result<Vec<AccountWasm>, String> {
         let programKey = ProgramKey::new(b2pk(programKey)?);
         let userKey = UserKey::new(b2pk(userKey)?);
         let dvdMintKey = DvdMintKey::new(b2pk(dvdMintKey)?);
-        let accounts = Self::get_accounts(programKey, (userKey, dvdMintKey))
+        let collateralMintKeys = collateralMintKeys
+            .into_iter()
+            .map(|key| -> Result<CollateralMintKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid collateral mint key: {}", e))?;
+                Ok(CollateralMintKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let accounts = Self::get_accounts(programKey, (userKey, dvdMintKey, collateralMintKeys))
             .into_iter()
             .map(AccountWasm::from)
             .collect();
@@ -66,19 +76,23 @@ unsafe impl Pod for VaultRepay {}
 
 impl Command for VaultRepay {
     const ID: u32 = 0x1af52fc3;
-    type Keys = (UserKey, DvdMintKey);
+    type Keys = (UserKey, DvdMintKey, Vec<CollateralMintKey>);
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, dvd_mint_key) = keys;
-        vec![
+        let (user_key, dvd_mint_key, collateral_mint_keys) = keys;
+        let dvd_token_program_key = program_key.derive_token_program(&dvd_mint_key);
+        let mut v = vec![
             AccountMeta {
                 pubkey: *user_key,
                 is_signer: true,
                 is_writable: false,
             },
             AccountMeta {
-                pubkey: user_key.derive_associated_token_address(&dvd_mint_key),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    &dvd_mint_key,
+                    &dvd_token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
@@ -98,11 +112,17 @@ impl Command for VaultRepay {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: dvd_token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
-        ]
+        ];
+        v.extend(collateral_mint_keys.into_iter().map(|c| AccountMeta {
+            pubkey: program_key.derive_collateral(&c),
+            is_signer: false,
+            is_writable: true,
+        }));
+        v
     }
 
     fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
@@ -112,6 +132,16 @@ impl Command for VaultRepay {
         let world_account = Writable::new(&accounts[3]);
         let vault_account = Writable::new(&accounts[4]);
         let token_program_account = TokenProgramAccount::new(&accounts[5]);
+        let collateral_accounts = accounts[6..].iter().map(Writable::new).collect::<Vec<_>>();
+        let mut collateral_data = collateral_accounts
+            .iter()
+            .map(|c| c.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut collateral = collateral_accounts
+            .into_iter()
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
 
         let mut world_data = world_account.get_info().data.borrow_mut();
         let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
@@ -126,7 +156,12 @@ impl Command for VaultRepay {
             self.requested_amount,
             &mut world.debt,
             &world.config.get_debt_config(),
+            world.config.get_vault_config(),
             &mut world.dvd,
+            &mut world.ledger,
+            &mut world.savings,
+            world.config.get_savings_config(),
+            &mut collateral,
             user_account,
             mint_account,
             dvd_account,
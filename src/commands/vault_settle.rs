@@ -0,0 +1,129 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::{wasm_bindgen, JsValue},
+};
+use {
+    crate::{
+        accounts::Writable,
+        keys::{CollateralMintKey, VaultKey},
+        store::{Collateral, Vault, World},
+        traits::{Account, Command, Pod, Store},
+    },
+    solana_program::{account_info::AccountInfo, clock::Clock, pubkey::Pubkey, sysvar::Sysvar},
+};
+
+/// Cancels the debt of a vault after global settlement
+///
+/// Collateral worth the debt at the frozen prices is seized from the vault,
+/// and any shortfall is recorded as bad debt. The owner can withdraw what is
+/// left. Anyone may call this until the settlement wait is over, after
+/// which the seized collateral is split between the fixed claims.
+///
+/// Accounts expected:
+///
+/// 0. `[writable]` World account (PDA)
+/// 1. `[writable]` Vault account (PDA)
+/// 2..n. `[writable]` Collateral accounts in order of vault reserves (PDAs)
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct VaultSettle {
+    _private: (),
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl VaultSettle {
+    #[wasm_bindgen(js_name = "getData")]
+    pub fn get_data_wasm() -> Vec<u8> {
+        Self { _private: () }.get_data()
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        vaultKey: &[u8],
+        collateralMintKeys: Vec<JsValue>,
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let vault_key = VaultKey::new(b2pk(vaultKey)?);
+        let collateral_mint_keys = collateralMintKeys
+            .into_iter()
+            .map(|key| -> Result<CollateralMintKey, String> {
+                let keyBytes: [u8; 32] = serde_wasm_bindgen::from_value(key)
+                    .map_err(|e| format!("Invalid collateral mint key: {}", e))?;
+                Ok(CollateralMintKey::new(b2pk(&keyBytes)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let accounts = Self::get_accounts(program_key, (vault_key, collateral_mint_keys))
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for VaultSettle {}
+
+impl Command for VaultSettle {
+    const ID: u32 = 0x385800b7;
+    type Keys = (VaultKey, Vec<CollateralMintKey>);
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
+        let (vault_key, collateral_mint_keys) = keys;
+        let mut v = vec![
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *vault_key,
+                is_signer: false,
+                is_writable: true,
+            },
+        ];
+        v.extend(collateral_mint_keys.into_iter().map(|c| AccountMeta {
+            pubkey: program_key.derive_collateral(&c),
+            is_signer: false,
+            is_writable: true,
+        }));
+        v
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let world_account = Writable::new(&accounts[0]);
+        let vault_account = Writable::new(&accounts[1]);
+        let collateral_accounts = accounts[2..].iter().map(Writable::new).collect::<Vec<_>>();
+        let mut collateral_data = collateral_accounts
+            .iter()
+            .map(|c| c.get_info().data.borrow_mut())
+            .collect::<Vec<_>>();
+        let mut collateral = collateral_accounts
+            .into_iter()
+            .zip(&mut collateral_data)
+            .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
+            .collect::<Vec<_>>();
+
+        let mut world_data = world_account.get_info().data.borrow_mut();
+        let world = World::load_mut(program_id, world_account, &mut world_data[..], ());
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        world.settlement.require_waiting(&clock);
+
+        let mut vault_data = vault_account.get_info().data.borrow_mut();
+        let vault = Vault::load_mut(program_id, vault_account, &mut vault_data[..], ());
+
+        vault.settle(
+            &mut world.debt,
+            world.config.get_debt_config(),
+            &mut world.ledger,
+            &mut collateral,
+            &clock,
+        );
+    }
+}
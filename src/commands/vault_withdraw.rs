@@ -7,9 +7,9 @@ use {
 };
 use {
     crate::{
-        accounts::{Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
-        keys::{CollateralMintKey, OracleKey, UserKey},
+        keys::{CollateralMintKey, OracleKey, TokenProgramKey, UserKey},
         store::{Authority, Collateral, Vault, World},
         traits::{Account, Command, Pod, Store},
         util::revert,
@@ -19,15 +19,20 @@ use {
 
 /// Withdraws tokens from a vault
 ///
+/// In recovery mode, the vault must stay within the stricter withdrawal LTV.
+/// With recovery mode enabled, a vault with debt needs the collateral types
+/// it doesn't hold recorded within the max age.
+///
 /// Accounts expected:
 /// 0. `[signer]` User account
 /// 1. `[writable]` Vault account (PDA)
 /// 2. `[writable]` World account (PDA)
 /// 3. `[writable]` User's token account (destination for tokens)
 /// 4. `[writable]` Safe account (source of tokens)
-/// 5. `[]` SPL Token program
+/// 5. `[]` Token program of the withdrawn collateral (SPL Token or Token-2022)
 /// 6. `[]` Authority account (PDA)
-/// 7..n. `[writable]` Collateral accounts for reserves in the vault, in order (PDAs)
+/// 7. `[]` Mint account of the withdrawn collateral
+/// 8..n. `[writable]` Collateral accounts for reserves in the vault, in order (PDAs)
 /// n..m. `[]` Oracle accounts for reserves in the vault, in order
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -58,9 +63,11 @@ impl VaultWithdraw {
         collateralMintKeys: Vec<JsValue>,
         oracleKeys: Vec<JsValue>,
         reserveIndex: u8,
+        tokenProgramKey: &[u8],
     ) -> Result<Vec<AccountWasm>, String> {
         let program_key = ProgramKey::new(b2pk(programKey)?);
         let user_key = UserKey::new(b2pk(userKey)?);
+        let token_program_key = TokenProgramKey::new(b2pk(tokenProgramKey)?)?;
         let collateral_mint_keys = collateralMintKeys
             .into_iter()
             .map(|key| -> Result<CollateralMintKey, String> {
@@ -80,7 +87,13 @@ impl VaultWithdraw {
 
         let accounts = Self::get_accounts(
             program_key,
-            (user_key, collateral_mint_keys, oracle_keys, reserveIndex),
+            (
+                user_key,
+                collateral_mint_keys,
+                oracle_keys,
+                reserveIndex,
+                token_program_key,
+            ),
         )
         .into_iter()
         .map(AccountWasm::from)
@@ -93,11 +106,18 @@ unsafe impl Pod for VaultWithdraw {}
 
 impl Command for VaultWithdraw {
     const ID: u32 = 0x4d771aa0;
-    type Keys = (UserKey, Vec<CollateralMintKey>, Vec<OracleKey>, u8);
+    type Keys = (
+        UserKey,
+        Vec<CollateralMintKey>,
+        Vec<OracleKey>,
+        u8,
+        TokenProgramKey,
+    );
 
     #[cfg(feature = "wasm")]
     fn get_accounts(program_key: ProgramKey, keys: Self::Keys) -> Vec<AccountMeta> {
-        let (user_key, collateral_mint_keys, oracle_keys, reserve_index) = keys;
+        let (user_key, collateral_mint_keys, oracle_keys, reserve_index, token_program_key) = keys;
+        let collateral_mint_key = &collateral_mint_keys[reserve_index as usize];
         let mut v = vec![
             AccountMeta {
                 pubkey: *user_key,
@@ -115,18 +135,20 @@ impl Command for VaultWithdraw {
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: user_key
-                    .derive_associated_token_address(&collateral_mint_keys[reserve_index as usize]),
+                pubkey: user_key.derive_associated_token_address_with_program(
+                    collateral_mint_key,
+                    &token_program_key,
+                ),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: program_key.derive_safe(&collateral_mint_keys[reserve_index as usize]),
+                pubkey: program_key.derive_collateral_safe(collateral_mint_key),
                 is_signer: false,
                 is_writable: true,
             },
             AccountMeta {
-                pubkey: spl_token::id(),
+                pubkey: *token_program_key,
                 is_signer: false,
                 is_writable: false,
             },
@@ -135,6 +157,11 @@ impl Command for VaultWithdraw {
                 is_signer: false,
                 is_writable: false,
             },
+            AccountMeta {
+                pubkey: **collateral_mint_key,
+                is_signer: false,
+                is_writable: false,
+            },
         ];
         v.extend(collateral_mint_keys.into_iter().map(|key| AccountMeta {
             pubkey: program_key.derive_collateral(&key),
@@ -157,8 +184,9 @@ impl Command for VaultWithdraw {
         let program_token_account = TokenAccount::new(Writable::new(&accounts[4]));
         let token_program_account = TokenProgramAccount::new(&accounts[5]);
         let authority_account = Readonly::new(&accounts[6]);
+        let mint_account = MintAccount::new(Readonly::new(&accounts[7]));
         let (collateral_accounts, oracle_accounts) = {
-            let a = &accounts[7..];
+            let a = &accounts[8..];
             if (a.len() % 2) != 0 {
                 revert("there should be an equal # of collateral and oracle accounts");
             }
@@ -186,13 +214,32 @@ impl Command for VaultWithdraw {
             .iter()
             .map(|account| account.get_info().data.borrow_mut())
             .collect();
-        let collateral = collateral_accounts
+        let mut collateral = collateral_accounts
             .into_iter()
             .zip(&mut collateral_data)
             .map(|(acc, data)| Collateral::load_mut(program_id, acc, data, ()))
             .collect::<Vec<_>>();
 
         let clock = Clock::get().map_err(|_| "could not get clock").unwrap();
+        // Withdrawing from a vault without debt needs no price
+        let max_ltv = if vault.has_debt() {
+            world.recovery.track(
+                &mut collateral,
+                &oracle_accounts,
+                &mut world.debt,
+                world.config.get_debt_config(),
+                &mut world.dvd_price,
+                world.config.get_dvd_interest_rate(),
+                &clock,
+            );
+            world.recovery.get_withdraw_max_ltv(
+                world.config.get_max_ltv(),
+                world.config.get_recovery_config(),
+                &clock,
+            )
+        } else {
+            world.config.get_max_ltv()
+        };
         vault.withdraw(
             vault_auth,
             self.requested_amount,
@@ -200,10 +247,12 @@ impl Command for VaultWithdraw {
             &world.config.get_debt_config(),
             &mut world.dvd_price,
             &world.config.get_dvd_interest_rate(),
-            world.config.get_max_ltv(),
+            max_ltv,
+            &mut world.ledger,
             program_id,
             program_token_account,
             destination_token_account,
+            mint_account,
             token_program_account,
             authority,
             collateral,
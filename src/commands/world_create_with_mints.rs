@@ -0,0 +1,190 @@
+#[cfg(feature = "wasm")]
+use {
+    crate::keys::ProgramKey,
+    crate::util::{b2pk, AccountWasm},
+    solana_program::instruction::AccountMeta,
+    wasm_bindgen::prelude::wasm_bindgen,
+};
+use {
+    crate::{
+        accounts::{Readonly, Signer, SystemProgramAccount, TokenProgramAccount, Writable},
+        finance::Schedule,
+        keys::SovereignKey,
+        store::{Authority, World, WorldParams},
+        token::{Mint, MintMetadata},
+        traits::{Command, Pod, Store},
+    },
+    solana_program::{
+        account_info::AccountInfo, clock::Clock, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+    },
+};
+
+/// Creates the program's world account, along with the DVD and DOVE mints
+///
+/// The mints are Token-2022 mints at program-derived addresses, with the
+/// authority as mint authority and their metadata stored in the mint.
+///
+/// Accounts expected:
+///
+/// 0. `[signer]` Sovereign account
+/// 1. `[writable]` World account (PDA, will be created)
+/// 2. `[]` Authority account (PDA)
+/// 3. `[writable]` Mint account for the DVD (PDA, will be created)
+/// 4. `[writable]` Mint account for the DOVE (PDA, will be created)
+/// 5. `[]` System program
+/// 6. `[]` Token-2022 program
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct WorldCreateWithMints {
+    vesting_recipient: Pubkey,
+    vesting_schedule: Schedule,
+    dvd_metadata: MintMetadata,
+    dove_metadata: MintMetadata,
+    dvd_decimals: u8,
+    dove_decimals: u8,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl WorldCreateWithMints {
+    #[wasm_bindgen(js_name = "getData")]
+    #[allow(non_snake_case)]
+    pub fn get_data_wasm(
+        vestingRecipient: Vec<u8>,
+        vestingSchedule: Schedule,
+        dvdMetadata: MintMetadata,
+        doveMetadata: MintMetadata,
+        dvdDecimals: u8,
+        doveDecimals: u8,
+    ) -> Result<Vec<u8>, String> {
+        Ok(Self {
+            vesting_recipient: b2pk(&vestingRecipient)?,
+            vesting_schedule: vestingSchedule,
+            dvd_metadata: dvdMetadata,
+            dove_metadata: doveMetadata,
+            dvd_decimals: dvdDecimals,
+            dove_decimals: doveDecimals,
+        }
+        .get_data())
+    }
+
+    #[wasm_bindgen(js_name = "getAccounts")]
+    #[allow(non_snake_case)]
+    pub fn get_accounts_wasm(
+        programKey: &[u8],
+        sovereignKey: &[u8],
+    ) -> Result<Vec<AccountWasm>, String> {
+        let program_key = ProgramKey::new(b2pk(programKey)?);
+        let sovereign_key = SovereignKey::new(b2pk(sovereignKey)?);
+        let accounts = Self::get_accounts(program_key, sovereign_key)
+            .into_iter()
+            .map(AccountWasm::from)
+            .collect();
+        Ok(accounts)
+    }
+}
+
+unsafe impl Pod for WorldCreateWithMints {}
+
+impl Command for WorldCreateWithMints {
+    const ID: u32 = 0x725584b4;
+    type Keys = SovereignKey;
+
+    #[cfg(feature = "wasm")]
+    fn get_accounts(program_key: ProgramKey, sovereign_key: Self::Keys) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta {
+                pubkey: *sovereign_key,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_world(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_authority(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_dvd_mint(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: program_key.derive_dove_mint(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: solana_program::system_program::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: spl_token_2022::ID,
+                is_signer: false,
+                is_writable: false,
+            },
+        ]
+    }
+
+    fn execute(self, program_id: &'static Pubkey, accounts: &'static [AccountInfo]) {
+        let sovereign_account = Signer::new(&accounts[0]);
+        let world_account = Writable::new(&accounts[1]);
+        let authority_account = Readonly::new(&accounts[2]);
+        let dvd_mint_account = Writable::new(&accounts[3]);
+        let dove_mint_account = Writable::new(&accounts[4]);
+        let system_program_account = SystemProgramAccount::new(&accounts[5]);
+        let token_program_account = TokenProgramAccount::new(&accounts[6]);
+
+        let authority = Authority::from_account(program_id, authority_account);
+
+        let clock = Clock::get().map_err(|_| "Failed to get clock").unwrap();
+        let rent = Rent::get().map_err(|_| "Failed to get rent").unwrap();
+        let dvd_mint_account = Mint::create(
+            program_id,
+            Mint::DVD_SEED,
+            sovereign_account,
+            dvd_mint_account,
+            system_program_account,
+            token_program_account,
+            authority,
+            self.dvd_decimals,
+            &self.dvd_metadata,
+            &rent,
+        );
+        let dove_mint_account = Mint::create(
+            program_id,
+            Mint::DOVE_SEED,
+            sovereign_account,
+            dove_mint_account,
+            system_program_account,
+            token_program_account,
+            authority,
+            self.dove_decimals,
+            &self.dove_metadata,
+            &rent,
+        );
+        World::create(
+            program_id,
+            sovereign_account,
+            world_account,
+            system_program_account,
+            (),
+            &rent,
+            WorldParams {
+                sovereign_account,
+                dove_mint_account,
+                dvd_mint_account,
+                authority,
+                clock,
+                vesting_recipient: self.vesting_recipient,
+                vesting_schedule: self.vesting_schedule,
+            },
+        );
+    }
+}
@@ -1,14 +1,26 @@
+#[cfg(feature = "devnet")]
+use crate::commands::{UserFeedCreate, UserFeedSetPrice};
 use {
     crate::{
         commands::{
-            AuthorityCreate, CollateralCreate, CollateralSetOracle, CollateralUpdateMaxDeposit,
-            ConfigUpdate, FlashMintBegin, FlashMintEnd, OfferingBuy, OfferingEnd, OfferingStart,
-            SavingsClaimRewards, SavingsCreate, SavingsDeposit, SavingsWithdraw, SovereignUpdate,
-            StabilityBuyDvd, StabilityCreate, StabilitySellDvd, StabilityUpdateMaxDeposit,
-            UserFeedCreate, UserFeedSetPrice, VaultBorrow, VaultBuyCollateral, VaultClaimRewards,
-            VaultCreate, VaultCreateReserve, VaultDeposit, VaultFailAuction, VaultLiquidate,
-            VaultRemoveReserve, VaultRepay, VaultUnliquidate, VaultWithdraw, VestingClaim,
-            VestingUpdateRecipient, WorldCreate,
+            AuthorityCreate, CollateralClearCircuitBreaker, CollateralCreate,
+            CollateralRecordPrice, CollateralRecordValue, CollateralSetCircuitBreaker,
+            CollateralSetOracle, CollateralSetPriceMode, CollateralSetStabilityFee,
+            CollateralSettle, CollateralUpdateMaxDeposit, CollateralWithdrawSeized,
+            CommitteeFeedCreate, CommitteeFeedSetPublishers, CommitteeFeedSubmit, ConfigUpdate,
+            FlashMintBegin, FlashMintEnd, GlobalSettle, LpFeedCreate, LpFeedUpdate,
+            MintUpdateMetadata, OfferingBuy, OfferingEnd, OfferingStart, Redeem, RedemptionRefresh,
+            ReferrersSet, SafeReconcile, SafeSweep, SavingsClaimRewards, SavingsCreate,
+            SavingsDeposit, SavingsRequestWithdrawal, SavingsShareCreate, SavingsShareDeposit,
+            SavingsShareExchangeRate, SavingsShareRequestWithdrawal, SavingsShareWithdraw,
+            SavingsWithdraw, SettlementCash, SettlementClaimCreate, SettlementPack,
+            SovereignUpdate, StabilityBuyDvd, StabilityCreate, StabilityPoolClaimCollateral,
+            StabilityPoolCreate, StabilityPoolDeposit, StabilityPoolWithdraw, StabilitySellDvd,
+            StabilityUpdateMaxDeposit, TermDepositCreate, TermDepositDeposit, TermDepositWithdraw,
+            VaultBorrow, VaultBuyCollateral, VaultClaimRewards, VaultCreate, VaultCreateReserve,
+            VaultDeposit, VaultFailAuction, VaultLiquidate, VaultRemoveReserve, VaultRepay,
+            VaultSettle, VaultUnliquidate, VaultWithdraw, VestingClaim, VestingUpdateRecipient,
+            WorldCreate, WorldCreateWithMints,
         },
         traits::{Command, Pod},
         util::revert,
@@ -36,25 +48,63 @@ pub fn process_instruction(
     let id = u32::from_le_bytes(id_data.try_into().unwrap());
     match id {
         AuthorityCreate::ID => AuthorityCreate::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralClearCircuitBreaker::ID => CollateralClearCircuitBreaker::cast_from(cmd_data).execute(program_id, accounts),
         CollateralCreate::ID => CollateralCreate::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralRecordPrice::ID => CollateralRecordPrice::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralRecordValue::ID => CollateralRecordValue::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralSetCircuitBreaker::ID => CollateralSetCircuitBreaker::cast_from(cmd_data).execute(program_id, accounts),
         CollateralSetOracle::ID => CollateralSetOracle::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralSetPriceMode::ID => CollateralSetPriceMode::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralSetStabilityFee::ID => CollateralSetStabilityFee::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralSettle::ID => CollateralSettle::cast_from(cmd_data).execute(program_id, accounts),
         CollateralUpdateMaxDeposit::ID => CollateralUpdateMaxDeposit::cast_from(cmd_data).execute(program_id, accounts),
+        CollateralWithdrawSeized::ID => CollateralWithdrawSeized::cast_from(cmd_data).execute(program_id, accounts),
+        CommitteeFeedCreate::ID => CommitteeFeedCreate::cast_from(cmd_data).execute(program_id, accounts),
+        CommitteeFeedSetPublishers::ID => CommitteeFeedSetPublishers::cast_from(cmd_data).execute(program_id, accounts),
+        CommitteeFeedSubmit::ID => CommitteeFeedSubmit::cast_from(cmd_data).execute(program_id, accounts),
         ConfigUpdate::ID => ConfigUpdate::cast_from(cmd_data).execute(program_id, accounts),
         FlashMintBegin::ID => FlashMintBegin::cast_from(cmd_data).execute(program_id, accounts),
         FlashMintEnd::ID => FlashMintEnd::cast_from(cmd_data).execute(program_id, accounts),
+        GlobalSettle::ID => GlobalSettle::cast_from(cmd_data).execute(program_id, accounts),
+        LpFeedCreate::ID => LpFeedCreate::cast_from(cmd_data).execute(program_id, accounts),
+        LpFeedUpdate::ID => LpFeedUpdate::cast_from(cmd_data).execute(program_id, accounts),
+        MintUpdateMetadata::ID => MintUpdateMetadata::cast_from(cmd_data).execute(program_id, accounts),
         OfferingBuy::ID => OfferingBuy::cast_from(cmd_data).execute(program_id, accounts),
         OfferingEnd::ID => OfferingEnd::cast_from(cmd_data).execute(program_id, accounts),
         OfferingStart::ID => OfferingStart::cast_from(cmd_data).execute(program_id, accounts),
+        Redeem::ID => Redeem::cast_from(cmd_data).execute(program_id, accounts),
+        RedemptionRefresh::ID => RedemptionRefresh::cast_from(cmd_data).execute(program_id, accounts),
+        ReferrersSet::ID => ReferrersSet::cast_from(cmd_data).execute(program_id, accounts),
+        SafeReconcile::ID => SafeReconcile::cast_from(cmd_data).execute(program_id, accounts),
+        SafeSweep::ID => SafeSweep::cast_from(cmd_data).execute(program_id, accounts),
         SavingsClaimRewards::ID => SavingsClaimRewards::cast_from(cmd_data).execute(program_id, accounts),
         SavingsCreate::ID => SavingsCreate::cast_from(cmd_data).execute(program_id, accounts),
         SavingsDeposit::ID => SavingsDeposit::cast_from(cmd_data).execute(program_id, accounts),
+        SavingsRequestWithdrawal::ID => SavingsRequestWithdrawal::cast_from(cmd_data).execute(program_id, accounts),
+        SavingsShareCreate::ID => SavingsShareCreate::cast_from(cmd_data).execute(program_id, accounts),
+        SavingsShareDeposit::ID => SavingsShareDeposit::cast_from(cmd_data).execute(program_id, accounts),
+        SavingsShareExchangeRate::ID => SavingsShareExchangeRate::cast_from(cmd_data).execute(program_id, accounts),
+        SavingsShareRequestWithdrawal::ID => SavingsShareRequestWithdrawal::cast_from(cmd_data).execute(program_id, accounts),
+        SavingsShareWithdraw::ID => SavingsShareWithdraw::cast_from(cmd_data).execute(program_id, accounts),
         SavingsWithdraw::ID => SavingsWithdraw::cast_from(cmd_data).execute(program_id, accounts),
+        SettlementCash::ID => SettlementCash::cast_from(cmd_data).execute(program_id, accounts),
+        SettlementClaimCreate::ID => SettlementClaimCreate::cast_from(cmd_data).execute(program_id, accounts),
+        SettlementPack::ID => SettlementPack::cast_from(cmd_data).execute(program_id, accounts),
         SovereignUpdate::ID => SovereignUpdate::cast_from(cmd_data).execute(program_id, accounts),
         StabilityCreate::ID => StabilityCreate::cast_from(cmd_data).execute(program_id, accounts),
         StabilityBuyDvd::ID => StabilityBuyDvd::cast_from(cmd_data).execute(program_id, accounts),
+        StabilityPoolClaimCollateral::ID => StabilityPoolClaimCollateral::cast_from(cmd_data).execute(program_id, accounts),
+        StabilityPoolCreate::ID => StabilityPoolCreate::cast_from(cmd_data).execute(program_id, accounts),
+        StabilityPoolDeposit::ID => StabilityPoolDeposit::cast_from(cmd_data).execute(program_id, accounts),
+        StabilityPoolWithdraw::ID => StabilityPoolWithdraw::cast_from(cmd_data).execute(program_id, accounts),
         StabilitySellDvd::ID => StabilitySellDvd::cast_from(cmd_data).execute(program_id, accounts),
         StabilityUpdateMaxDeposit::ID => StabilityUpdateMaxDeposit::cast_from(cmd_data).execute(program_id, accounts),
+        TermDepositCreate::ID => TermDepositCreate::cast_from(cmd_data).execute(program_id, accounts),
+        TermDepositDeposit::ID => TermDepositDeposit::cast_from(cmd_data).execute(program_id, accounts),
+        TermDepositWithdraw::ID => TermDepositWithdraw::cast_from(cmd_data).execute(program_id, accounts),
+        #[cfg(feature = "devnet")]
         UserFeedCreate::ID => UserFeedCreate::cast_from(cmd_data).execute(program_id, accounts),
+        #[cfg(feature = "devnet")]
         UserFeedSetPrice::ID => UserFeedSetPrice::cast_from(cmd_data).execute(program_id, accounts),
         VaultBorrow::ID => VaultBorrow::cast_from(cmd_data).execute(program_id, accounts),
         VaultBuyCollateral::ID => VaultBuyCollateral::cast_from(cmd_data).execute(program_id, accounts),
@@ -66,11 +116,13 @@ pub fn process_instruction(
         VaultLiquidate::ID => VaultLiquidate::cast_from(cmd_data).execute(program_id, accounts),
         VaultRemoveReserve::ID => VaultRemoveReserve::cast_from(cmd_data).execute(program_id, accounts),
         VaultRepay::ID => VaultRepay::cast_from(cmd_data).execute(program_id, accounts),
+        VaultSettle::ID => VaultSettle::cast_from(cmd_data).execute(program_id, accounts),
         VaultUnliquidate::ID => VaultUnliquidate::cast_from(cmd_data).execute(program_id, accounts),
         VaultWithdraw::ID => VaultWithdraw::cast_from(cmd_data).execute(program_id, accounts),
         VestingClaim::ID => VestingClaim::cast_from(cmd_data).execute(program_id, accounts),
         VestingUpdateRecipient::ID => VestingUpdateRecipient::cast_from(cmd_data).execute(program_id, accounts),
         WorldCreate::ID => WorldCreate::cast_from(cmd_data).execute(program_id, accounts),
+        WorldCreateWithMints::ID => WorldCreateWithMints::cast_from(cmd_data).execute(program_id, accounts),
         _ => revert("Invalid command ID"),
     };
     Ok(())
@@ -16,6 +16,12 @@ use wasm_bindgen::prelude::wasm_bindgen;
 pub struct BookConfig {
     interest_rate: InterestRate,
     reward_schedule: Schedule,
+    // The share of the interest and fees repaid on vault debt paid to the
+    // Book as interest. Zero disables the link, leaving the rate to
+    // `interest_rate`.
+    link_share: Decimal,
+    // The highest rate the Book pays out of its linked revenue.
+    link_max_rate: InterestRate,
 }
 
 #[cfg(feature = "wasm")]
@@ -23,11 +29,21 @@ pub struct BookConfig {
 impl BookConfig {
     #[wasm_bindgen(constructor)]
     #[allow(non_snake_case)]
-    pub fn new(interestRate: InterestRate, rewardSchedule: Schedule) -> Self {
-        Self {
+    pub fn new(
+        interestRate: InterestRate,
+        rewardSchedule: Schedule,
+        linkShare: f64,
+        linkMaxRate: InterestRate,
+    ) -> Result<BookConfig, String> {
+        if !(0.0..=1.0).contains(&linkShare) {
+            return Err("Link share must be between 0 and 1".to_string());
+        }
+        Ok(Self {
             interest_rate: interestRate,
             reward_schedule: rewardSchedule,
-        }
+            link_share: Decimal::from(linkShare),
+            link_max_rate: linkMaxRate,
+        })
     }
     #[wasm_bindgen(getter, js_name = interestRate)]
     pub fn interest_rate(&self) -> InterestRate {
@@ -37,6 +53,14 @@ impl BookConfig {
     pub fn reward_schedule(&self) -> Schedule {
         self.reward_schedule
     }
+    #[wasm_bindgen(getter, js_name = linkShare)]
+    pub fn link_share(&self) -> f64 {
+        self.link_share.to_f64()
+    }
+    #[wasm_bindgen(getter, js_name = linkMaxRate)]
+    pub fn link_max_rate(&self) -> InterestRate {
+        self.link_max_rate
+    }
 }
 
 #[repr(C)]
@@ -47,10 +71,16 @@ pub struct Book {
     total: Decimal,
     // Total amount of rewards distributed.
     rewards: Decimal,
+    // Total interest accrued, paid by borrowers or owed to savers.
+    interest: Decimal,
     // The interest multiplier, defined as the total interest accrued on 1 DVD deposited at the protocol's inception.
     multiplier: Decimal,
     // The reward accumulator, defined as the total rewards accrued on 1 DVD deposited at the protocol's inception.
     accumulator: Decimal,
+    // The principal that earns interest but no rewards, like pooled sDVD.
+    unrewarded: Decimal,
+    // Revenue shared through the link and not yet paid out as interest.
+    linked: Decimal,
     // The time at which this Book was created.
     creation_time: Time,
     // The time at which this Book was last updated.
@@ -63,8 +93,11 @@ impl Book {
         Self {
             total: Decimal::zero(),
             rewards: Decimal::zero(),
+            interest: Decimal::zero(),
             multiplier: Decimal::one(),
             accumulator: Decimal::zero(),
+            unrewarded: Decimal::zero(),
+            linked: Decimal::zero(),
             creation_time: now,
             last_update: now,
         }
@@ -73,11 +106,26 @@ impl Book {
     fn project_total_and_multiplier(&self, config: &BookConfig, time: Time) -> (Decimal, Decimal) {
         let secs_elapsed = time.secs_since(self.last_update);
         let interest_factor = config.interest_rate.get_accumulation_factor(secs_elapsed);
-        let new_total = self.total * interest_factor;
-        let new_multiplier = self.multiplier * interest_factor;
+        let mut new_total = self.total * interest_factor;
+        let mut new_multiplier = self.multiplier * interest_factor;
+        let payout = self.project_linked_payout(config, secs_elapsed);
+        if !payout.is_zero() {
+            new_multiplier *= (new_total + payout) / new_total;
+            new_total += payout;
+        }
         (new_total, new_multiplier)
     }
 
+    /// Returns the linked revenue paid out over `secs_elapsed`, no more than
+    /// the link's maximum rate would have paid.
+    fn project_linked_payout(&self, config: &BookConfig, secs_elapsed: u64) -> Decimal {
+        if self.linked.is_zero() || self.total.is_zero() {
+            return Decimal::zero();
+        }
+        let max_factor = config.link_max_rate.get_accumulation_factor(secs_elapsed);
+        self.linked.min(self.total * max_factor.saturating_sub(Decimal::one()))
+    }
+
     fn project_rewards_and_accumulator(
         &self,
         config: &BookConfig,
@@ -91,7 +139,8 @@ impl Book {
             Decimal::from(secs_since_creation) / SECS_PER_DAY,
         );
         let new_rewards_total = self.rewards + new_rewards;
-        if self.total < Decimal::one() {
+        let rewarded_total = self.total.saturating_sub(self.unrewarded * self.multiplier);
+        if rewarded_total < Decimal::one() {
             // too small total, rewards are thrown into abyss
             return (self.rewards, self.accumulator);
         }
@@ -99,7 +148,7 @@ impl Book {
         // accumulator = rewards / principal
         // accumulator = rewards / (total / multiplier)
         // accumulator = rewards * (multiplier / total)
-        let new_accumulator = self.accumulator + ((new_rewards * self.multiplier) / self.total);
+        let new_accumulator = self.accumulator + ((new_rewards * self.multiplier) / rewarded_total);
         (new_rewards_total, new_accumulator)
     }
 
@@ -109,10 +158,13 @@ impl Book {
             return;
         }
 
-        if !config.interest_rate.is_zero() {
+        let payout = self.project_linked_payout(config, now.secs_since(self.last_update));
+        if !config.interest_rate.is_zero() || !payout.is_zero() {
             let (new_total, new_multiplier) = self.project_total_and_multiplier(config, now);
+            self.interest += new_total - self.total;
             self.total = new_total;
             self.multiplier = new_multiplier;
+            self.linked -= payout;
         }
 
         let (new_rewards, new_accumulator) = self.project_rewards_and_accumulator(config, now);
@@ -141,6 +193,12 @@ impl Book {
         rewards.to_f64()
     }
 
+    #[wasm_bindgen(js_name = "projectInterest")]
+    pub fn project_interest(&self, config: &BookConfig, unixTimestamp: f64) -> f64 {
+        let time = Time::from_unix_timestamp(unixTimestamp as u64);
+        self.project_interest_at(config, time).to_f64()
+    }
+
     #[wasm_bindgen(getter, js_name = "creationTime")]
     pub fn creation_time(&self) -> f64 {
         self.creation_time.to_unix_timestamp() as f64
@@ -149,7 +207,17 @@ impl Book {
 
 #[cfg(feature = "wasm")]
 impl Book {
-    pub(super) fn project_multiplier(&self, config: &BookConfig, time: Time) -> Decimal {
+    pub fn project_total_at(&self, config: &BookConfig, time: Time) -> Decimal {
+        let (total, _) = self.project_total_and_multiplier(config, time);
+        total
+    }
+
+    pub fn project_interest_at(&self, config: &BookConfig, time: Time) -> Decimal {
+        let (total, _) = self.project_total_and_multiplier(config, time);
+        self.interest + (total - self.total)
+    }
+
+    pub fn project_multiplier(&self, config: &BookConfig, time: Time) -> Decimal {
         let (_, new_multiplier) = self.project_total_and_multiplier(config, time);
         new_multiplier
     }
@@ -160,6 +228,40 @@ impl Book {
     }
 }
 
+impl BookConfig {
+    #[cfg(test)]
+    pub fn from_interest_rate(interest_rate: InterestRate) -> Self {
+        Self {
+            interest_rate,
+            ..Self::default()
+        }
+    }
+
+    #[cfg(test)]
+    pub fn from_reward_schedule(reward_schedule: Schedule) -> Self {
+        Self {
+            reward_schedule,
+            ..Self::default()
+        }
+    }
+
+    #[cfg(test)]
+    pub fn from_link(link_share: Decimal, link_max_rate: InterestRate) -> Self {
+        Self {
+            link_share,
+            link_max_rate,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the link share is at most 1, and the link and the configured
+    /// rate are not both set, as the Book would be paid twice.
+    pub fn is_valid(&self) -> bool {
+        self.link_share <= Decimal::one()
+            && (self.link_share.is_zero() || self.interest_rate.is_zero())
+    }
+}
+
 // public functions, should all have accrue as first statement
 impl Book {
     pub fn get_total(&mut self, config: &BookConfig, clock: &Clock) -> Decimal {
@@ -167,6 +269,55 @@ impl Book {
         self.total
     }
 
+    pub fn get_multiplier(&mut self, config: &BookConfig, clock: &Clock) -> Decimal {
+        self.accrue(config, clock);
+        self.multiplier
+    }
+
+    #[cfg(test)]
+    pub fn get_interest(&mut self, config: &BookConfig, clock: &Clock) -> Decimal {
+        self.accrue(config, clock);
+        self.interest
+    }
+
+    /// Shares the link's part of `revenue`, the interest and fees repaid on
+    /// vault debt, with the Book. It is paid out as interest in the
+    /// following accruals, no faster than the link's maximum rate.
+    pub fn share_revenue(&mut self, revenue: Decimal, config: &BookConfig) {
+        self.linked += revenue * config.link_share;
+    }
+
+    /// Adds `amount` that earns interest but no rewards, returning its principal.
+    pub fn add_unrewarded(
+        &mut self,
+        amount: Decimal,
+        config: &BookConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        self.accrue(config, clock);
+        let principal = amount / self.multiplier;
+        self.total += amount;
+        self.unrewarded += principal;
+        principal
+    }
+
+    /// Takes off `amount` added with `add_unrewarded`, returning its principal.
+    pub fn subtract_unrewarded(
+        &mut self,
+        amount: Decimal,
+        config: &BookConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        self.accrue(config, clock);
+        if amount > self.total {
+            revert("Insufficient balance");
+        }
+        let principal = (amount / self.multiplier).min(self.unrewarded);
+        self.total -= amount;
+        self.unrewarded -= principal;
+        principal
+    }
+
     pub(super) fn get_multiplier_and_accumulator(
         &mut self,
         config: &BookConfig,
@@ -188,4 +339,133 @@ impl Book {
         }
         self.total -= amount;
     }
+
+    /// Adds `amount` of interest charged outside of the configured rate.
+    pub(super) fn add_interest(&mut self, amount: Decimal, config: &BookConfig, clock: &Clock) {
+        self.accrue(config, clock);
+        self.total += amount;
+        self.interest += amount;
+    }
+
+    /// Takes off `amount` of interest accrued at the configured rate but not owed.
+    pub(super) fn subtract_interest(
+        &mut self,
+        amount: Decimal,
+        config: &BookConfig,
+        clock: &Clock,
+    ) {
+        self.accrue(config, clock);
+        if amount > self.total {
+            revert("Insufficient balance");
+        }
+        self.total -= amount;
+        self.interest = self.interest.saturating_sub(amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::finance::Page};
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    const DAY: i64 = SECS_PER_DAY as i64;
+
+    #[test]
+    fn test_unrewarded_earns_no_rewards() {
+        let config = BookConfig::from_reward_schedule(Schedule::flat(Decimal::from(100u64), 10));
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        page.add(Decimal::from(100u64), &mut book, &config, &clock(0));
+        book.add_unrewarded(Decimal::from(300u64), &config, &clock(0));
+
+        assert_eq!(
+            page.claim_rewards(&mut book, &config, &clock(DAY)),
+            Decimal::from(100u64)
+        );
+    }
+
+    #[test]
+    fn test_unrewarded_alone_earns_no_rewards() {
+        let config = BookConfig::from_reward_schedule(Schedule::flat(Decimal::from(100u64), 10));
+        let mut book = Book::new(&clock(0));
+        book.add_unrewarded(Decimal::from(100u64), &config, &clock(0));
+        let mut page = Page::new();
+        page.add(Decimal::from(100u64), &mut book, &config, &clock(DAY));
+
+        assert_eq!(
+            page.claim_rewards(&mut book, &config, &clock(2 * DAY)),
+            Decimal::from(100u64)
+        );
+    }
+
+    #[test]
+    fn test_unrewarded_earns_interest() {
+        let config = BookConfig::from_interest_rate(InterestRate::from_rate_per_sec(
+            Decimal::one() / 1_000u64,
+        ));
+        let mut book = Book::new(&clock(0));
+        let amount = Decimal::from(100u64);
+        let principal = book.add_unrewarded(amount, &config, &clock(0));
+        let total = principal * book.get_multiplier(&config, &clock(10));
+        assert_eq!(total, book.get_total(&config, &clock(10)));
+
+        let epsilon = Decimal::one() / 1_000_000_000u64;
+        let subtracted = book.subtract_unrewarded(total, &config, &clock(10));
+        assert!(subtracted.abs_diff(principal) < epsilon);
+        assert!(book.unrewarded < epsilon);
+        assert!(book.get_total(&config, &clock(10)).is_zero());
+    }
+
+    #[test]
+    fn test_linked_revenue_is_paid_out() {
+        let max_rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let config = BookConfig::from_link(Decimal::one() / 2u64, max_rate);
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        page.add(Decimal::from(100u64), &mut book, &config, &clock(0));
+        book.share_revenue(Decimal::from(10u64), &config);
+        assert_eq!(
+            page.get_total(&mut book, &config, &clock(100)),
+            Decimal::from(105u64)
+        );
+        assert!(book.linked.is_zero());
+    }
+
+    #[test]
+    fn test_linked_revenue_waits_for_max_rate() {
+        let config = BookConfig::from_link(Decimal::one() / 2u64, InterestRate::zero());
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        page.add(Decimal::from(100u64), &mut book, &config, &clock(0));
+        book.share_revenue(Decimal::from(10u64), &config);
+        assert_eq!(
+            page.get_total(&mut book, &config, &clock(100)),
+            Decimal::from(100u64)
+        );
+        // The revenue is kept until the maximum rate allows paying it out
+        let max_rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let config = BookConfig::from_link(Decimal::one() / 2u64, max_rate);
+        assert_eq!(
+            page.get_total(&mut book, &config, &clock(200)),
+            Decimal::from(105u64)
+        );
+    }
+
+    #[test]
+    fn test_link_excludes_interest_rate() {
+        let max_rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        assert!(BookConfig::from_link(Decimal::one(), max_rate).is_valid());
+        assert!(!BookConfig {
+            interest_rate: max_rate,
+            ..BookConfig::from_link(Decimal::one(), max_rate)
+        }
+        .is_valid());
+        assert!(!BookConfig::from_link(Decimal::from(2u64), max_rate).is_valid());
+    }
 }
@@ -1,4 +1,5 @@
 use {
+    crate::traits::Pod,
     std::{
         fmt::Display,
         ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
@@ -57,6 +58,12 @@ impl Decimal {
 
         ret
     }
+
+    /// Calculates the square root, rounded down
+    pub fn sqrt(self) -> Self {
+        let result = (U192::from(self.0) * U192::from(WAD)).integer_sqrt();
+        Self(result.as_u128())
+    }
     pub fn from_token_amount(amount: u64, decimals: u8) -> Self {
         if decimals > 18 {
             panic!("Decimals must be 18 or less");
@@ -85,7 +92,6 @@ impl Decimal {
         val
     }
 
-    #[cfg(test)]
     /// Returns the absolute difference between two `Decimal` values
     pub fn abs_diff(self, rhs: Self) -> Self {
         Self(self.0.abs_diff(rhs.0))
@@ -107,6 +113,10 @@ impl Decimal {
     }
 }
 
+unsafe impl Pod for Decimal {
+    const NAME: &'static str = "Decimal";
+}
+
 impl From<u64> for Decimal {
     fn from(val: u64) -> Self {
         Self(val as u128 * WAD)
@@ -319,4 +329,13 @@ mod tests {
         a /= 2u64;
         assert_eq!(a, Decimal::from(4));
     }
+
+    #[test]
+    fn test_decimal_sqrt() {
+        assert_eq!(Decimal::from(16).sqrt(), Decimal::from(4));
+        assert_eq!(Decimal::zero().sqrt(), Decimal::zero());
+        assert_eq!((Decimal::one() / 4u64).sqrt(), Decimal::one() / 2u64);
+        let two = Decimal::from(2).sqrt();
+        assert!((two * two).abs_diff(Decimal::from(2)) < Decimal(10));
+    }
 }
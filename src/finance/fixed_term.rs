@@ -0,0 +1,240 @@
+use {
+    crate::{
+        finance::{Book, BookConfig, Decimal, InterestRate, Page},
+        util::Time,
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// A part of a vault's debt borrowed at a fixed rate until maturity.
+///
+/// The debt stays on the vault's `Page`, where it accrues at the floating
+/// rate of the `Book`. Whenever the vault is touched, the difference to the
+/// fixed rate is added to or taken off the page, and to the interest of the
+/// `Book`. At maturity, the term ends and the debt is left to the page. The
+/// page's growth over the update that spans maturity is split pro rata by
+/// time, so only the part before maturity is replaced by the fixed rate.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct FixedTerm {
+    // The fixed-rate debt, as of `last_update`.
+    amount: Decimal,
+    rate: InterestRate,
+    // The multiplier of the Book, as of `last_update`.
+    multiplier: Decimal,
+    last_update: Time,
+    maturity: Time,
+}
+
+impl FixedTerm {
+    pub fn new(
+        rate: InterestRate,
+        term: u64,
+        book: &mut Book,
+        config: &BookConfig,
+        clock: &Clock,
+    ) -> Self {
+        let (multiplier, _) = book.get_multiplier_and_accumulator(config, clock);
+        let now = Time::now(clock);
+        Self {
+            amount: Decimal::zero(),
+            rate,
+            multiplier,
+            last_update: now,
+            maturity: now.add_secs(term),
+        }
+    }
+
+    /// Charges the fixed rate instead of the floating one on the fixed-rate
+    /// debt since the last update. Returns whether the term is over.
+    pub fn accrue(
+        &mut self,
+        page: &mut Page,
+        book: &mut Book,
+        config: &BookConfig,
+        clock: &Clock,
+    ) -> bool {
+        let now = Time::now(clock);
+        let (multiplier, _) = book.get_multiplier_and_accumulator(config, clock);
+        let growth = multiplier / self.multiplier;
+        let secs = now.secs_since(self.last_update);
+        let fixed_secs = now.min(self.maturity).secs_since(self.last_update);
+        let fixed_growth = if fixed_secs < secs {
+            Decimal::one() + (growth - Decimal::one()) * fixed_secs / secs
+        } else {
+            growth
+        };
+        let floating = self.amount * growth;
+        let target = floating * self.rate.get_accumulation_factor(fixed_secs) / fixed_growth;
+        if target > floating {
+            page.add_interest(target - floating, book, config, clock);
+        } else {
+            page.subtract_interest(floating - target, book, config, clock);
+        }
+        self.amount = target;
+        self.multiplier = multiplier;
+        self.last_update = now;
+        now >= self.maturity
+    }
+
+    /// Adds newly borrowed debt. The term must be accrued first.
+    pub fn add(&mut self, amount: Decimal) {
+        self.amount += amount;
+    }
+
+    /// Caps the fixed-rate debt to the debt left after a repayment, as
+    /// floating-rate debt is repaid first. Returns whether none is left.
+    pub fn cap(&mut self, debt: Decimal) -> bool {
+        self.amount = self.amount.min(debt);
+        self.amount.is_zero()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl FixedTerm {
+    #[wasm_bindgen(getter)]
+    pub fn amount(&self) -> f64 {
+        self.amount.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rate(&self) -> InterestRate {
+        self.rate
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn maturity(&self) -> f64 {
+        self.maturity.to_unix_timestamp() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_fixed_rate_until_maturity() {
+        let rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let config = BookConfig::default();
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        let amount = Decimal::from(100u64);
+        let mut term = FixedTerm::new(rate, 10, &mut book, &config, &clock(0));
+        page.add(amount, &mut book, &config, &clock(0));
+        term.add(amount);
+
+        assert!(!term.accrue(&mut page, &mut book, &config, &clock(5)));
+        assert_eq!(
+            page.get_total(&mut book, &config, &clock(5)),
+            amount * rate.get_accumulation_factor(5)
+        );
+        assert!(term.accrue(&mut page, &mut book, &config, &clock(20)));
+        assert_eq!(
+            page.get_total(&mut book, &config, &clock(20)),
+            amount * rate.get_accumulation_factor(5) * rate.get_accumulation_factor(5)
+        );
+    }
+
+    #[test]
+    fn test_fixed_rate_is_booked_as_interest() {
+        let fixed_rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let config = BookConfig::from_interest_rate(InterestRate::from_rate_per_sec(
+            Decimal::one() / 2_000u64,
+        ));
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        let amount = Decimal::from(100u64);
+        let mut term = FixedTerm::new(fixed_rate, 10, &mut book, &config, &clock(0));
+        page.add(amount, &mut book, &config, &clock(0));
+        term.add(amount);
+
+        term.accrue(&mut page, &mut book, &config, &clock(5));
+        assert_eq!(
+            book.get_interest(&config, &clock(5)),
+            page.get_total(&mut book, &config, &clock(5)) - amount
+        );
+    }
+
+    #[test]
+    fn test_fixed_rate_below_floating_rate() {
+        let config = BookConfig::from_interest_rate(InterestRate::from_rate_per_sec(
+            Decimal::one() / 1_000u64,
+        ));
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        let amount = Decimal::from(100u64);
+        let mut term = FixedTerm::new(InterestRate::zero(), 10, &mut book, &config, &clock(0));
+        page.add(amount, &mut book, &config, &clock(0));
+        term.add(amount);
+
+        term.accrue(&mut page, &mut book, &config, &clock(5));
+        let epsilon = Decimal::one() / 1_000_000_000u64;
+        let total = page.get_total(&mut book, &config, &clock(5));
+        assert!(total.abs_diff(amount) < epsilon);
+        assert!(book.get_interest(&config, &clock(5)) < epsilon);
+    }
+
+    #[test]
+    fn test_floating_rate_after_maturity() {
+        let fixed_rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let floating_rate = InterestRate::from_rate_per_sec(Decimal::one() / 2_000u64);
+        let config = BookConfig::from_interest_rate(floating_rate);
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        let amount = Decimal::from(100u64);
+        let mut term = FixedTerm::new(fixed_rate, 10, &mut book, &config, &clock(0));
+        page.add(amount, &mut book, &config, &clock(0));
+        term.add(amount);
+
+        assert!(term.accrue(&mut page, &mut book, &config, &clock(10)));
+        let at_maturity = page.get_total(&mut book, &config, &clock(10));
+        assert_eq!(
+            page.get_total(&mut book, &config, &clock(30)),
+            at_maturity * floating_rate.get_accumulation_factor(20)
+        );
+    }
+
+    #[test]
+    fn test_update_spanning_maturity() {
+        let fixed_rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let config = BookConfig::from_interest_rate(InterestRate::from_rate_per_sec(
+            Decimal::one() / 2_000u64,
+        ));
+        let mut book = Book::new(&clock(0));
+        let mut page = Page::new();
+        let amount = Decimal::from(100u64);
+        let mut term = FixedTerm::new(fixed_rate, 10, &mut book, &config, &clock(0));
+        page.add(amount, &mut book, &config, &clock(0));
+        term.add(amount);
+
+        assert!(term.accrue(&mut page, &mut book, &config, &clock(20)));
+        let floating = page.get_total(&mut book, &config, &clock(20));
+        let fixed_part = amount * fixed_rate.get_accumulation_factor(10);
+        assert!(floating > fixed_part);
+        assert_eq!(book.get_interest(&config, &clock(20)), floating - amount);
+    }
+
+    #[test]
+    fn test_cap_after_repayment() {
+        let config = BookConfig::default();
+        let mut book = Book::new(&clock(0));
+        let mut term = FixedTerm::new(InterestRate::zero(), 10, &mut book, &config, &clock(0));
+        term.add(Decimal::from(100u64));
+        assert!(!term.cap(Decimal::from(150u64)));
+        assert_eq!(term.amount, Decimal::from(100u64));
+        assert!(!term.cap(Decimal::from(40u64)));
+        assert!(term.cap(Decimal::zero()));
+    }
+}
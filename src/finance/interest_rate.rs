@@ -5,7 +5,7 @@ use {crate::util::SECS_PER_YEAR, wasm_bindgen::prelude::wasm_bindgen};
 
 /// A continuously compounding interest rate.
 #[repr(C)]
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct InterestRate {
     rate_per_sec: Decimal,
@@ -18,6 +18,15 @@ impl InterestRate {
     pub const fn is_zero(&self) -> bool {
         self.rate_per_sec.is_zero()
     }
+    pub const fn zero() -> Self {
+        Self {
+            rate_per_sec: Decimal::zero(),
+        }
+    }
+    #[cfg(test)]
+    pub const fn from_rate_per_sec(rate_per_sec: Decimal) -> Self {
+        Self { rate_per_sec }
+    }
 }
 
 #[cfg(feature = "wasm")]
@@ -41,10 +50,8 @@ impl InterestRate {
         (self.rate_per_sec * SECS_PER_YEAR).to_f64().exp() - 1.0
     }
 
-    #[wasm_bindgen(getter)]
-    pub fn zero() -> Self {
-        Self {
-            rate_per_sec: Decimal::zero(),
-        }
+    #[wasm_bindgen(getter, js_name = "zero")]
+    pub fn zero_wasm() -> Self {
+        Self::zero()
     }
 }
@@ -1,15 +1,21 @@
 mod auction;
 mod book;
 mod decimal;
+mod fixed_term;
 mod interest_rate;
 mod page;
+mod pool;
 mod schedule;
+mod stability_fee;
 
 pub use {
     auction::{Auction, AuctionConfig},
     book::{Book, BookConfig},
     decimal::Decimal,
+    fixed_term::FixedTerm,
     interest_rate::InterestRate,
     page::Page,
+    pool::{Pool, Share},
     schedule::Schedule,
+    stability_fee::StabilityFee,
 };
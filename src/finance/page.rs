@@ -64,6 +64,18 @@ impl Page {
         self.total
     }
 
+    /// Returns the total in terms of the multiplier of the `Book`, which only
+    /// changes when the page is added to or subtracted from.
+    pub fn get_principal(
+        &mut self,
+        book: &mut Book,
+        config: &BookConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        self.accrue(book, config, clock);
+        self.total / self.multiplier
+    }
+
     pub fn claim_rewards(
         &mut self,
         book: &mut Book,
@@ -95,6 +107,33 @@ impl Page {
         book.subtract(amount, config, clock);
     }
 
+    pub(super) fn add_interest(
+        &mut self,
+        amount: Decimal,
+        book: &mut Book,
+        config: &BookConfig,
+        clock: &Clock,
+    ) {
+        self.accrue(book, config, clock);
+        self.total += amount;
+        book.add_interest(amount, config, clock);
+    }
+
+    pub(super) fn subtract_interest(
+        &mut self,
+        amount: Decimal,
+        book: &mut Book,
+        config: &BookConfig,
+        clock: &Clock,
+    ) {
+        self.accrue(book, config, clock);
+        if amount > self.total {
+            revert("Insufficient balance");
+        }
+        self.total -= amount;
+        book.subtract_interest(amount, config, clock);
+    }
+
     pub fn take(&mut self, book: &mut Book, config: &BookConfig, clock: &Clock) -> Decimal {
         self.accrue(book, config, clock);
         let total = self.total.take();
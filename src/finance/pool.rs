@@ -0,0 +1,349 @@
+use {
+    super::Decimal,
+    crate::util::{require, revert, List},
+    solana_program::pubkey::Pubkey,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The maximum number of collateral types a pool can hold.
+pub const MAX_POOL_ASSETS: usize = 16;
+
+/// Liquidations that would bring the product below 1 / MIN_PRODUCT_INVERSE are
+/// left to auctions, so that deposits keep enough precision.
+const MIN_PRODUCT_INVERSE: u64 = 1_000_000_000;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct PoolAsset {
+    mint: Pubkey,
+    // Collateral gained per DVD deposited when the product was 1.
+    sum: Decimal,
+}
+
+/// The stability pool, where DVD is deposited to cancel the debt of liquidated
+/// vaults in exchange for their collateral.
+///
+/// Deposits are tracked with a product/sum pair of accumulators: a deposit
+/// `d` made at product `p` and sum `s` is now worth `d * product / p` DVD,
+/// plus `d * (sum - s) / p` of collateral.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Pool {
+    // Total amount deposited, net of absorbed debt.
+    total: Decimal,
+    // The fraction of 1 DVD deposited at the pool's inception left after absorbing debt.
+    product: Decimal,
+    // The number of shares with a deposit or unclaimed collateral.
+    depositors: u64,
+    assets: List<PoolAsset, MAX_POOL_ASSETS>,
+}
+
+impl Pool {
+    pub const fn new() -> Self {
+        Self {
+            total: Decimal::zero(),
+            product: Decimal::one(),
+            depositors: 0,
+            assets: List::new(),
+        }
+    }
+
+    pub fn get_total(&self) -> Decimal {
+        self.total
+    }
+
+    /// Returns whether the pool can cancel `debt` entirely, and has room for
+    /// the mints of `collateral` it does not hold yet.
+    pub fn can_absorb(&self, debt: Decimal, collateral: &[(Pubkey, Decimal)]) -> bool {
+        let new_assets = collateral
+            .iter()
+            .filter(|(mint, amount)| {
+                !amount.is_zero() && self.assets.iter().all(|a| &a.mint != mint)
+            })
+            .count();
+        debt < self.total
+            && self.assets.len() + new_assets <= MAX_POOL_ASSETS
+            && self.product * ((self.total - debt) / self.total)
+                >= Decimal::one() / MIN_PRODUCT_INVERSE
+    }
+
+    /// Cancels `debt` with deposits, and hands `collateral` out to depositors
+    /// in proportion to their deposits.
+    pub fn absorb(&mut self, debt: Decimal, collateral: &[(Pubkey, Decimal)]) {
+        require(
+            self.can_absorb(debt, collateral),
+            "Stability pool can't absorb debt",
+        );
+        for &(mint, amount) in collateral {
+            if amount.is_zero() {
+                continue;
+            }
+            let gain = amount * self.product / self.total;
+            match self.assets.iter().position(|a| a.mint == mint) {
+                Some(index) => self.assets[index].sum += gain,
+                None => self.assets.push(PoolAsset { mint, sum: gain }),
+            }
+        }
+        self.product *= (self.total - debt) / self.total;
+        self.total -= debt;
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Pool {
+    fn get_sum(&self, mint: &Pubkey) -> Decimal {
+        self.assets
+            .iter()
+            .find(|a| &a.mint == mint)
+            .map_or(Decimal::zero(), |a| a.sum)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Pool {
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> f64 {
+        self.total.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn depositors(&self) -> f64 {
+        self.depositors as f64
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ShareAsset {
+    mint: Pubkey,
+    // The pool sum at the last update.
+    sum: Decimal,
+    // Collateral gained and not yet claimed.
+    gain: Decimal,
+}
+
+/// A deposit in the `Pool`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Share {
+    // Amount deposited, net of absorbed debt.
+    total: Decimal,
+    // The pool product at the last update.
+    product: Decimal,
+    assets: List<ShareAsset, MAX_POOL_ASSETS>,
+}
+
+impl Share {
+    pub const fn new() -> Self {
+        Self {
+            total: Decimal::zero(),
+            product: Decimal::one(),
+            assets: List::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.total.is_zero() && self.assets.iter().all(|a| a.gain.is_zero())
+    }
+
+    fn accrue(&mut self, pool: &Pool) {
+        for pool_asset in pool.assets.iter() {
+            let index = match self.assets.iter().position(|a| a.mint == pool_asset.mint) {
+                Some(index) => index,
+                None => {
+                    self.assets.push(ShareAsset {
+                        mint: pool_asset.mint,
+                        sum: Decimal::zero(),
+                        gain: Decimal::zero(),
+                    });
+                    self.assets.len() - 1
+                }
+            };
+            let asset = &mut self.assets[index];
+            if pool_asset.sum < asset.sum {
+                revert("Sum cannot decrease");
+            }
+            asset.gain += self.total * (pool_asset.sum - asset.sum) / self.product;
+            asset.sum = pool_asset.sum;
+        }
+        if pool.product != self.product {
+            self.total = self.total * pool.product / self.product;
+            self.product = pool.product;
+        }
+    }
+
+    /// Removes the share from the depositor count once it is empty, and
+    /// resets the pool once nobody is left.
+    fn close_if_empty(&mut self, pool: &mut Pool) {
+        if !self.is_empty() {
+            return;
+        }
+        pool.depositors -= 1;
+        if pool.depositors == 0 {
+            *pool = Pool::new();
+        }
+        *self = Share::new();
+    }
+}
+
+// public functions, should all have accrue as first statement
+impl Share {
+    pub fn get_total(&mut self, pool: &Pool) -> Decimal {
+        self.accrue(pool);
+        self.total
+    }
+
+    pub fn add(&mut self, amount: Decimal, pool: &mut Pool) {
+        self.accrue(pool);
+        require(!amount.is_zero(), "Amount must be greater than zero");
+        if self.is_empty() {
+            pool.depositors += 1;
+        }
+        self.total += amount;
+        pool.total += amount;
+    }
+
+    /// Withdraws up to `requested_amount`, returning the amount withdrawn.
+    pub fn subtract(&mut self, requested_amount: Decimal, pool: &mut Pool) -> Decimal {
+        self.accrue(pool);
+        let amount = requested_amount.min(self.total);
+        require(!amount.is_zero(), "Insufficient deposit");
+        self.total -= amount;
+        // Rounding can leave the pool total slightly below the sum of its shares
+        pool.total = pool.total.saturating_sub(amount);
+        self.close_if_empty(pool);
+        amount
+    }
+
+    /// Claims the collateral gained in `mint`.
+    pub fn claim(&mut self, mint: &Pubkey, pool: &mut Pool) -> Decimal {
+        self.accrue(pool);
+        let amount = self
+            .assets
+            .iter_mut()
+            .find(|a| &a.mint == mint)
+            .map_or(Decimal::zero(), |a| a.gain.take());
+        require(!amount.is_zero(), "No collateral to claim");
+        self.close_if_empty(pool);
+        amount
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Share {
+    #[wasm_bindgen(js_name = "projectTotal")]
+    pub fn project_total_wasm(&self, pool: &Pool) -> f64 {
+        (self.total * pool.product / self.product).to_f64()
+    }
+
+    #[wasm_bindgen(js_name = "projectGain")]
+    #[allow(non_snake_case)]
+    pub fn project_gain_wasm(&self, pool: &Pool, mintKey: &[u8]) -> Result<f64, String> {
+        let mint = crate::util::b2pk(mintKey)?;
+        let (sum, gain) = self
+            .assets
+            .iter()
+            .find(|a| a.mint == mint)
+            .map_or((Decimal::zero(), Decimal::zero()), |a| (a.sum, a.gain));
+        let new_gain = self.total * pool.get_sum(&mint).saturating_sub(sum) / self.product;
+        Ok((gain + new_gain).to_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dvd(amount: u64) -> Decimal {
+        Decimal::from(amount)
+    }
+
+    fn assert_close(actual: Decimal, expected: Decimal) {
+        let tolerance = Decimal::one() / 1_000_000u64;
+        assert!(
+            actual.abs_diff(expected) <= tolerance,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_absorb_shares_losses_and_collateral_pro_rata() {
+        let mint = Pubkey::new_unique();
+        let mut pool = Pool::new();
+        let mut alice = Share::new();
+        let mut bob = Share::new();
+        alice.add(dvd(300), &mut pool);
+        bob.add(dvd(100), &mut pool);
+
+        pool.absorb(dvd(200), &[(mint, dvd(8))]);
+
+        assert_close(alice.get_total(&pool), dvd(150));
+        assert_close(bob.get_total(&pool), dvd(50));
+        assert_close(alice.claim(&mint, &mut pool), dvd(6));
+        assert_close(bob.claim(&mint, &mut pool), dvd(2));
+    }
+
+    #[test]
+    fn test_late_deposit_gets_no_earlier_collateral() {
+        let mint = Pubkey::new_unique();
+        let mut pool = Pool::new();
+        let mut alice = Share::new();
+        let mut bob = Share::new();
+        alice.add(dvd(100), &mut pool);
+        pool.absorb(dvd(50), &[(mint, dvd(1))]);
+        bob.add(dvd(50), &mut pool);
+        pool.absorb(dvd(50), &[(mint, dvd(1))]);
+
+        assert_close(alice.get_total(&pool), dvd(25));
+        assert_close(bob.get_total(&pool), dvd(25));
+        assert_close(alice.claim(&mint, &mut pool), dvd(3) / 2);
+        assert_close(bob.claim(&mint, &mut pool), dvd(1) / 2);
+    }
+
+    #[test]
+    fn test_cannot_absorb_entire_pool() {
+        let mut pool = Pool::new();
+        let mut share = Share::new();
+        share.add(dvd(100), &mut pool);
+        assert!(pool.can_absorb(dvd(99), &[]));
+        assert!(!pool.can_absorb(dvd(100), &[]));
+        assert!(!Pool::new().can_absorb(dvd(1), &[]));
+    }
+
+    #[test]
+    fn test_cannot_absorb_new_mint_when_full() {
+        let mut pool = Pool::new();
+        let mut share = Share::new();
+        share.add(dvd(1_000), &mut pool);
+        for _ in 0..MAX_POOL_ASSETS {
+            pool.absorb(dvd(1), &[(Pubkey::new_unique(), dvd(1))]);
+        }
+        let held = pool.assets[0].mint;
+        assert!(pool.can_absorb(dvd(1), &[(held, dvd(1))]));
+        assert!(pool.can_absorb(dvd(1), &[(Pubkey::new_unique(), Decimal::zero())]));
+        assert!(!pool.can_absorb(dvd(1), &[(held, dvd(1)), (Pubkey::new_unique(), dvd(1))]));
+    }
+
+    #[test]
+    fn test_pool_resets_when_last_depositor_leaves() {
+        let mint = Pubkey::new_unique();
+        let mut pool = Pool::new();
+        let mut share = Share::new();
+        share.add(dvd(100), &mut pool);
+        pool.absorb(dvd(50), &[(mint, dvd(1))]);
+        share.subtract(dvd(100), &mut pool);
+        assert_eq!(pool.depositors, 1);
+        share.claim(&mint, &mut pool);
+        assert_eq!(pool.depositors, 0);
+        assert_eq!(pool.product, Decimal::one());
+        assert_eq!(pool.assets.len(), 0);
+    }
+}
@@ -13,6 +13,16 @@ pub struct Schedule {
 }
 
 impl Schedule {
+    /// A schedule paying `maximum` per day for `days`, without warmup.
+    #[cfg(test)]
+    pub fn flat(maximum: Decimal, days: u64) -> Self {
+        Self {
+            maximum,
+            warmup_length: Decimal::zero(),
+            total_length: Decimal::from(days),
+        }
+    }
+
     pub fn integrate(&self, t1: Decimal, t2: Decimal) -> Decimal {
         let w = self.warmup_length;
         let l = self.total_length;
@@ -0,0 +1,102 @@
+use {
+    crate::{
+        finance::{Decimal, InterestRate},
+        util::Time,
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// The stability fee of a collateral type, charged on top of the interest
+/// rate of the debt `Book`.
+///
+/// Like the `Book` multiplier, the multiplier is the fee accrued on 1 DVD of
+/// debt since the collateral was created. Vaults snapshot it per reserve, and
+/// add the growth since to their debt.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct StabilityFee {
+    rate: InterestRate,
+    multiplier: Decimal,
+    last_update: Time,
+}
+
+impl StabilityFee {
+    pub const fn new() -> Self {
+        Self {
+            rate: InterestRate::zero(),
+            multiplier: Decimal::one(),
+            last_update: Time::from_unix_timestamp(0),
+        }
+    }
+
+    /// Changes the rate, accruing the fee at the old rate first.
+    pub fn set_rate(&mut self, rate: InterestRate, clock: &Clock) {
+        self.accrue(clock);
+        self.rate = rate;
+    }
+
+    fn project_multiplier_at(&self, time: Time) -> Decimal {
+        let secs_elapsed = time.secs_since(self.last_update);
+        self.multiplier * self.rate.get_accumulation_factor(secs_elapsed)
+    }
+
+    fn accrue(&mut self, clock: &Clock) {
+        let now = Time::now(clock);
+        self.multiplier = self.project_multiplier_at(now);
+        self.last_update = now;
+    }
+
+    pub fn get_multiplier(&mut self, clock: &Clock) -> Decimal {
+        self.accrue(clock);
+        self.multiplier
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl StabilityFee {
+    #[wasm_bindgen(getter)]
+    pub fn rate(&self) -> InterestRate {
+        self.rate
+    }
+
+    #[wasm_bindgen(js_name = projectMultiplier)]
+    #[allow(non_snake_case)]
+    pub fn project_multiplier(&self, unixTimestamp: f64) -> f64 {
+        self.project_multiplier_at(Time::from_unix_timestamp(unixTimestamp as u64))
+            .to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_zero_rate_keeps_multiplier() {
+        let mut fee = StabilityFee::new();
+        assert_eq!(fee.get_multiplier(&clock(1_000)), Decimal::one());
+    }
+
+    #[test]
+    fn test_rate_change_accrues_old_rate() {
+        let rate = InterestRate::from_rate_per_sec(Decimal::one() / 1_000u64);
+        let mut fee = StabilityFee::new();
+        fee.set_rate(rate, &clock(0));
+        fee.set_rate(InterestRate::zero(), &clock(10));
+        let multiplier = rate.get_accumulation_factor(10);
+        assert_eq!(fee.get_multiplier(&clock(10)), multiplier);
+        assert_eq!(fee.get_multiplier(&clock(1_000)), multiplier);
+    }
+}
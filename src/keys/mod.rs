@@ -4,6 +4,7 @@ mod dvd_mint_key;
 mod oracle_key;
 mod sovereign_key;
 mod stable_mint_key;
+mod token_program_key;
 mod user_key;
 mod vault_key;
 
@@ -15,5 +16,5 @@ pub use program_key::ProgramKey;
 pub use {
     collateral_mint_key::CollateralMintKey, dove_mint_key::DoveMintKey, dvd_mint_key::DvdMintKey,
     oracle_key::OracleKey, sovereign_key::SovereignKey, stable_mint_key::StableMintKey,
-    user_key::UserKey, vault_key::VaultKey,
+    token_program_key::TokenProgramKey, user_key::UserKey, vault_key::VaultKey,
 };
@@ -1,9 +1,14 @@
+#[cfg(feature = "devnet")]
+use crate::oracle::UserFeed;
 use {
     super::{CollateralMintKey, StableMintKey, UserKey},
     crate::{
-        oracle::UserFeed,
-        store::{Authority, Collateral, Savings, Stability, Vault, World},
-        token::Safe,
+        oracle::{CommitteeFeed, LpFeed},
+        store::{
+            Authority, Collateral, Savings, SettlementClaim, Stability, StabilityPool, TermDeposit,
+            Vault, World,
+        },
+        token::{Mint, Safe, SafeOwner},
         traits::Store,
     },
     solana_program::pubkey::Pubkey,
@@ -23,21 +28,74 @@ impl ProgramKey {
     pub fn derive_collateral(&self, collateral_mint_key: &CollateralMintKey) -> Pubkey {
         Collateral::derive_address(&self.0, collateral_mint_key)
     }
+    pub fn derive_collateral_safe(&self, collateral_mint_key: &CollateralMintKey) -> Pubkey {
+        Safe::derive_address::<Collateral>(&self.0, collateral_mint_key)
+    }
+    pub fn derive_committee_feed(&self, index: u8) -> Pubkey {
+        CommitteeFeed::derive_address(&self.0, &[index])
+    }
+    pub fn derive_dove_mint(&self) -> Pubkey {
+        Mint::derive_address(&self.0, Mint::DOVE_SEED)
+    }
+    pub fn derive_dvd_mint(&self) -> Pubkey {
+        Mint::derive_address(&self.0, Mint::DVD_SEED)
+    }
+    pub fn derive_lp_feed(&self, lp_mint_key: &CollateralMintKey) -> Pubkey {
+        LpFeed::derive_address(&self.0, lp_mint_key)
+    }
+    pub fn derive_safe(&self, owner: SafeOwner, mint_key: &Pubkey) -> Pubkey {
+        match owner {
+            SafeOwner::Collateral => Safe::derive_address::<Collateral>(&self.0, mint_key),
+            SafeOwner::Stability => Safe::derive_address::<Stability>(&self.0, mint_key),
+        }
+    }
+    /// Returns the store that owns the safe of `mint_key`.
+    pub fn derive_safe_owner(&self, owner: SafeOwner, mint_key: &Pubkey) -> Pubkey {
+        match owner {
+            SafeOwner::Collateral => Collateral::derive_address(&self.0, mint_key),
+            SafeOwner::Stability => Stability::derive_address(&self.0, mint_key),
+        }
+    }
     pub fn derive_savings(&self, user_key: &UserKey) -> Pubkey {
         Savings::derive_address(&self.0, user_key)
     }
-    pub fn derive_safe(&self, mint: &Pubkey) -> Pubkey {
-        Safe::derive_address(&self.0, &mint)
+    pub fn derive_sdvd_mint(&self) -> Pubkey {
+        Mint::derive_address(&self.0, Mint::SDVD_SEED)
+    }
+    pub fn derive_settlement_claim(&self, user_key: &UserKey) -> Pubkey {
+        SettlementClaim::derive_address(&self.0, user_key)
     }
     pub fn derive_stability(&self, stable_mint_key: &StableMintKey) -> Pubkey {
         Stability::derive_address(&self.0, stable_mint_key)
     }
+    pub fn derive_stability_pool(&self, user_key: &UserKey) -> Pubkey {
+        StabilityPool::derive_address(&self.0, user_key)
+    }
+    pub fn derive_stability_safe(&self, stable_mint_key: &StableMintKey) -> Pubkey {
+        Safe::derive_address::<Stability>(&self.0, stable_mint_key)
+    }
     pub fn derive_world(&self) -> Pubkey {
         World::derive_address(&self.0, ())
     }
+    #[cfg(feature = "devnet")]
     pub fn derive_user_feed(&self, user_key: &UserKey, index: u8) -> Pubkey {
         UserFeed::derive_address(&self.0, (user_key, &[index]))
     }
+    /// Returns the token program of the DVD or DOVE mint: Token-2022 for the
+    /// mints created by the program, SPL Token otherwise.
+    pub fn derive_token_program(&self, mint: &Pubkey) -> Pubkey {
+        if mint == &self.derive_dvd_mint()
+            || mint == &self.derive_dove_mint()
+            || mint == &self.derive_sdvd_mint()
+        {
+            spl_token_2022::ID
+        } else {
+            spl_token::ID
+        }
+    }
+    pub fn derive_term_deposit(&self, user_key: &UserKey, index: u64) -> Pubkey {
+        TermDeposit::derive_address(&self.0, (user_key, &index.to_le_bytes()))
+    }
     pub fn derive_vault(&self, user_key: &UserKey) -> Pubkey {
         Vault::derive_address(&self.0, user_key)
     }
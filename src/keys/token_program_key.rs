@@ -0,0 +1,22 @@
+use {solana_program::pubkey::Pubkey, std::ops::Deref};
+
+/// The token program that owns a mint, either SPL Token or Token-2022.
+pub struct TokenProgramKey(Pubkey);
+
+#[cfg(feature = "wasm")]
+impl TokenProgramKey {
+    pub fn new(pubkey: Pubkey) -> Result<Self, String> {
+        if pubkey != spl_token::ID && pubkey != spl_token_2022::ID {
+            return Err("Invalid token program key".to_string());
+        }
+        Ok(Self(pubkey))
+    }
+}
+
+impl Deref for TokenProgramKey {
+    type Target = Pubkey;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
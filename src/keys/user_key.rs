@@ -10,6 +10,17 @@ impl UserKey {
     pub fn derive_associated_token_address(&self, address: &Pubkey) -> Pubkey {
         spl_associated_token_account::get_associated_token_address(&self.0, address)
     }
+    pub fn derive_associated_token_address_with_program(
+        &self,
+        address: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            &self.0,
+            address,
+            token_program,
+        )
+    }
 }
 
 impl Deref for UserKey {
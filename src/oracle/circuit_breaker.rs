@@ -0,0 +1,267 @@
+use crate::{finance::Decimal, oracle::PricePurpose, util::Time};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Holds back sudden oracle price moves until they are confirmed.
+///
+/// A price that differs by more than `max_move` from the last accepted price
+/// trips the breaker, however old that price is, so that a feed that went
+/// quiet cannot jump straight to a new price. The breaker resets when the price
+/// returns within range of the last accepted price, or once the new price has
+/// held for `window` seconds, in which case it becomes the accepted price.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CircuitBreaker {
+    last_price: Decimal,
+    last_time: Time,
+    pending_price: Decimal,
+    tripped_at: Time,
+    // Maximum relative price move, zero disables the breaker.
+    max_move: Decimal,
+    // Seconds a tripping price must hold before it is accepted.
+    window: u64,
+    tripped: bool,
+    blocks_liquidation: bool,
+}
+
+impl CircuitBreaker {
+    pub const fn new() -> Self {
+        Self {
+            last_price: Decimal::zero(),
+            last_time: Time::from_unix_timestamp(0),
+            pending_price: Decimal::zero(),
+            tripped_at: Time::from_unix_timestamp(0),
+            max_move: Decimal::zero(),
+            window: 0,
+            tripped: false,
+            blocks_liquidation: false,
+        }
+    }
+
+    pub fn configure(&mut self, max_move: Decimal, window: u64, blocks_liquidation: bool) {
+        self.max_move = max_move;
+        self.window = window;
+        self.blocks_liquidation = blocks_liquidation;
+        if max_move.is_zero() {
+            self.tripped = false;
+        }
+    }
+
+    /// Forgets all observed prices, keeping the configuration.
+    pub fn reset(&mut self) {
+        *self = Self {
+            max_move: self.max_move,
+            window: self.window,
+            blocks_liquidation: self.blocks_liquidation,
+            ..Self::new()
+        };
+    }
+
+    /// Accepts the price that tripped the breaker.
+    pub fn clear(&mut self) {
+        if self.tripped {
+            self.tripped = false;
+            self.last_price = self.pending_price;
+            self.last_time = self.tripped_at;
+        }
+    }
+
+    pub const fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    /// Returns whether a price may not be used for `purpose`.
+    pub const fn blocks(&self, purpose: PricePurpose) -> bool {
+        match purpose {
+            PricePurpose::Borrow | PricePurpose::Redeem => self.tripped,
+            PricePurpose::Liquidate => self.tripped && self.blocks_liquidation,
+        }
+    }
+
+    /// Observes a new oracle price, tripping or resetting the breaker.
+    pub fn update(&mut self, price: Decimal, time: Time) {
+        let is_disabled = self.max_move.is_zero() || self.last_price.is_zero();
+        if is_disabled {
+            self.accept(price, time);
+        } else if self.is_within(price, self.last_price) {
+            self.tripped = false;
+            self.accept(price, time);
+        } else if !self.tripped || !self.is_within(price, self.pending_price) {
+            self.tripped = true;
+            self.pending_price = price;
+            self.tripped_at = time;
+        } else if time.secs_since(self.tripped_at) >= self.window {
+            self.tripped = false;
+            self.accept(price, time);
+        }
+    }
+
+    fn accept(&mut self, price: Decimal, time: Time) {
+        self.last_price = price;
+        self.last_time = time;
+    }
+
+    fn is_within(&self, price: Decimal, reference: Decimal) -> bool {
+        price.abs_diff(reference) <= reference * self.max_move
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[allow(non_snake_case)]
+#[wasm_bindgen]
+impl CircuitBreaker {
+    #[wasm_bindgen(getter, js_name = lastPrice)]
+    pub fn last_price(&self) -> f64 {
+        self.last_price.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = lastUnixTimestamp)]
+    pub fn last_unix_timestamp(&self) -> f64 {
+        self.last_time.to_unix_timestamp() as f64
+    }
+
+    #[wasm_bindgen(getter, js_name = pendingPrice)]
+    pub fn pending_price(&self) -> Option<f64> {
+        self.tripped.then(|| self.pending_price.to_f64())
+    }
+
+    #[wasm_bindgen(getter, js_name = trippedUnixTimestamp)]
+    pub fn tripped_unix_timestamp(&self) -> Option<f64> {
+        self.tripped
+            .then(|| self.tripped_at.to_unix_timestamp() as f64)
+    }
+
+    #[wasm_bindgen(getter, js_name = maxMove)]
+    pub fn max_move(&self) -> f64 {
+        self.max_move.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn window(&self) -> f64 {
+        self.window as f64
+    }
+
+    #[wasm_bindgen(getter, js_name = isTripped)]
+    pub fn is_tripped_wasm(&self) -> bool {
+        self.tripped
+    }
+
+    #[wasm_bindgen(getter, js_name = blocksLiquidation)]
+    pub fn blocks_liquidation(&self) -> bool {
+        self.blocks_liquidation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> Time {
+        Time::from_unix_timestamp(secs)
+    }
+
+    fn breaker() -> CircuitBreaker {
+        let mut breaker = CircuitBreaker::new();
+        // 10% within 100 seconds
+        breaker.configure(Decimal::one() / 10u64, 100, false);
+        breaker.update(Decimal::from(100), at(1000));
+        breaker
+    }
+
+    #[test]
+    fn test_disabled_accepts_any_move() {
+        let mut breaker = CircuitBreaker::new();
+        breaker.update(Decimal::from(100), at(1000));
+        breaker.update(Decimal::from(1), at(1001));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(1));
+    }
+
+    #[test]
+    fn test_small_move_is_accepted() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(109), at(1001));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(109));
+    }
+
+    #[test]
+    fn test_large_move_trips() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(80), at(1001));
+        assert!(breaker.is_tripped());
+        assert!(breaker.blocks(PricePurpose::Borrow));
+        assert!(breaker.blocks(PricePurpose::Redeem));
+        assert!(!breaker.blocks(PricePurpose::Liquidate));
+        assert_eq!(breaker.last_price, Decimal::from(100));
+    }
+
+    #[test]
+    fn test_move_from_stale_reference_trips() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(80), at(5000));
+        assert!(breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(100));
+        breaker.update(Decimal::from(80), at(5100));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(80));
+    }
+
+    #[test]
+    fn test_small_move_from_stale_reference_is_accepted() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(95), at(5000));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(95));
+    }
+
+    #[test]
+    fn test_blocks_liquidation_when_configured() {
+        let mut breaker = breaker();
+        breaker.configure(Decimal::one() / 10u64, 100, true);
+        breaker.update(Decimal::from(80), at(1001));
+        assert!(breaker.blocks(PricePurpose::Liquidate));
+    }
+
+    #[test]
+    fn test_reverting_move_resets() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(80), at(1001));
+        breaker.update(Decimal::from(101), at(1002));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(101));
+    }
+
+    #[test]
+    fn test_held_move_is_confirmed() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(80), at(1001));
+        breaker.update(Decimal::from(81), at(1050));
+        assert!(breaker.is_tripped());
+        breaker.update(Decimal::from(79), at(1101));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(79));
+    }
+
+    #[test]
+    fn test_new_move_restarts_confirmation() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(80), at(1001));
+        breaker.update(Decimal::from(50), at(1050));
+        breaker.update(Decimal::from(50), at(1101));
+        assert!(breaker.is_tripped());
+        breaker.update(Decimal::from(50), at(1150));
+        assert!(!breaker.is_tripped());
+    }
+
+    #[test]
+    fn test_clear_accepts_pending_price() {
+        let mut breaker = breaker();
+        breaker.update(Decimal::from(80), at(1001));
+        breaker.clear();
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.last_price, Decimal::from(80));
+    }
+}
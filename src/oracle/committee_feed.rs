@@ -0,0 +1,291 @@
+#[cfg(feature = "wasm")]
+use {crate::util::b2pk, wasm_bindgen::prelude::wasm_bindgen};
+use {
+    crate::{
+        accounts::{Readonly, Signer},
+        finance::Decimal,
+        state::SovereignAuth,
+        traits::{Account, Pod, Store, StoreAuth},
+        util::{require, revert, List, Time},
+    },
+    solana_program::{clock::Clock, pubkey::Pubkey},
+};
+
+/// Maximum number of publishers of a `CommitteeFeed`.
+pub const MAX_PUBLISHERS: usize = 16;
+
+/// Seconds after which a submission no longer counts towards the quorum.
+const SUBMISSION_MAX_AGE: u64 = 120;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Submission {
+    publisher: Pubkey,
+    price: Decimal,
+    time: Time,
+}
+
+unsafe impl Pod for Submission {}
+
+/// A price oracle run by a committee of registered publishers.
+///
+/// Each publisher posts its own price and timestamp. The feed reports the
+/// median of fresh submissions once at least `quorum` of them are fresh,
+/// so that no single publisher can set the price.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CommitteeFeed {
+    initialized: bool,
+    nonce: u8,
+    index: [u8; 1],
+    quorum: u8,
+    submissions: List<Submission, MAX_PUBLISHERS>,
+}
+
+pub struct CommitteeFeedParams {
+    pub sovereign_auth: SovereignAuth,
+    pub index: u8,
+}
+
+impl Store for CommitteeFeed {
+    const SEED_PREFIX: &'static str = "committee_feed";
+    type Params = CommitteeFeedParams;
+    type DeriveData<'a> = &'a [u8; 1];
+    type CreateData<'a> = &'a [u8; 1];
+    type LoadData = ();
+    type LoadAuthData = SovereignAuth;
+
+    fn get_seeds_on_derive<'a>(index: Self::DeriveData<'a>) -> [&'a [u8]; 2] {
+        [index, &[]]
+    }
+    fn get_seeds_on_create<'a>(index: Self::CreateData<'a>) -> [&'a [u8]; 2] {
+        [index, &[]]
+    }
+    fn get_seeds_on_load<'a>(&'a self, _: ()) -> [&'a [u8]; 2] {
+        [&self.index, &[]]
+    }
+    fn get_seeds_on_load_auth<'a>(&'a self, sovereign_auth: SovereignAuth) -> [&'a [u8]; 2] {
+        _ = sovereign_auth;
+        [&self.index, &[]]
+    }
+
+    fn initialize(&mut self, nonce: u8, params: Self::Params) {
+        _ = params.sovereign_auth;
+        self.initialized = true;
+        self.nonce = nonce;
+        self.index = [params.index];
+        self.quorum = 0;
+        self.submissions = List::new();
+    }
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+    fn get_nonce(&self) -> u8 {
+        self.nonce
+    }
+}
+
+unsafe impl Pod for CommitteeFeed {
+    const NAME: &'static str = "CommitteeFeed";
+}
+
+// Authorized functions
+impl CommitteeFeed {
+    /// Replaces the set of publishers.
+    /// Submissions of publishers that remain registered are kept.
+    pub fn set_publishers(
+        &mut self,
+        auth: StoreAuth<Self>,
+        quorum: u8,
+        publisher_accounts: &[Readonly],
+    ) {
+        _ = auth;
+        require(quorum > 0, "Quorum must be positive");
+        require(
+            quorum as usize <= publisher_accounts.len(),
+            "Quorum exceeds number of publishers",
+        );
+        let mut submissions = List::new();
+        for publisher_account in publisher_accounts {
+            let publisher = publisher_account.get_info().key;
+            require(
+                submissions
+                    .iter()
+                    .all(|s: &Submission| &s.publisher != publisher),
+                "Duplicate publisher",
+            );
+            let submission = self
+                .submissions
+                .iter()
+                .find(|s| &s.publisher == publisher)
+                .copied()
+                .unwrap_or(Submission {
+                    publisher: *publisher,
+                    price: Decimal::zero(),
+                    time: Time::from_unix_timestamp(0),
+                });
+            submissions.push(submission);
+        }
+        self.quorum = quorum;
+        self.submissions = submissions;
+    }
+}
+
+// Unauthorized functions
+impl CommitteeFeed {
+    /// Posts the price observed by a publisher at `time`.
+    pub fn submit(&mut self, publisher_account: Signer, price: Decimal, time: Time, clock: &Clock) {
+        require(price > Decimal::zero(), "Price must be positive");
+        require(time <= Time::now(clock), "Submission time is in the future");
+        let publisher = publisher_account.get_info().key;
+        let Some(submission) = self
+            .submissions
+            .iter_mut()
+            .find(|s| &s.publisher == publisher)
+        else {
+            revert("Publisher is not registered");
+        };
+        require(
+            time > submission.time,
+            "Submission is not newer than the previous one",
+        );
+        submission.price = price;
+        submission.time = time;
+    }
+}
+
+// For internal use only
+impl CommitteeFeed {
+    pub fn query(data: &[u8], time: Time) -> Result<(Decimal, Time), &'static str> {
+        // Provided oracle account key assumed valid
+        let committee_feed = Self::load_unchecked(data)?;
+        committee_feed.median(time)
+    }
+
+    /// Returns the median price of fresh submissions, and the time of the oldest one.
+    fn median(&self, time: Time) -> Result<(Decimal, Time), &'static str> {
+        let mut prices = [Decimal::zero(); MAX_PUBLISHERS];
+        let mut count = 0;
+        let mut oldest = time;
+        for submission in self.submissions.iter() {
+            let is_fresh = time.secs_since(submission.time) <= SUBMISSION_MAX_AGE;
+            if submission.price.is_zero() || !is_fresh {
+                continue;
+            }
+            prices[count] = submission.price;
+            count += 1;
+            oldest = oldest.min(submission.time);
+        }
+        if count == 0 || count < self.quorum as usize {
+            return Err("Committee feed has not reached quorum");
+        }
+        let prices = &mut prices[..count];
+        prices.sort_unstable();
+        let median = match count % 2 {
+            0 => (prices[count / 2 - 1] + prices[count / 2]) / 2u64,
+            _ => prices[count / 2],
+        };
+        Ok((median, oldest))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[allow(non_snake_case)]
+#[wasm_bindgen]
+impl CommitteeFeed {
+    #[wasm_bindgen(js_name = deriveKey)]
+    pub fn derive_key(programKey: &[u8], index: u8) -> Result<Vec<u8>, String> {
+        Ok(Self::derive_address_raw(&b2pk(programKey)?, &[index]))
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<CommitteeFeed, String> {
+        Self::try_cast_from(bytes)
+            .map(|x| *x)
+            .map_err(|e| format!("Invalid committee feed: {}", e))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn quorum(&self) -> u8 {
+        self.quorum
+    }
+
+    #[wasm_bindgen(getter, js_name = publisherCount)]
+    pub fn publisher_count(&self) -> usize {
+        self.submissions.len()
+    }
+
+    #[wasm_bindgen(js_name = publisherKey)]
+    pub fn publisher_key(&self, index: usize) -> Option<Vec<u8>> {
+        self.submissions
+            .iter()
+            .nth(index)
+            .map(|s| s.publisher.to_bytes().to_vec())
+    }
+
+    #[wasm_bindgen(js_name = publisherPrice)]
+    pub fn publisher_price(&self, index: usize) -> Option<f64> {
+        self.submissions.iter().nth(index).map(|s| s.price.to_f64())
+    }
+
+    #[wasm_bindgen(js_name = publisherUnixTimestamp)]
+    pub fn publisher_unix_timestamp(&self, index: usize) -> Option<f64> {
+        self.submissions
+            .iter()
+            .nth(index)
+            .map(|s| s.time.to_unix_timestamp() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> Time {
+        Time::from_unix_timestamp(secs)
+    }
+
+    fn feed(quorum: u8, submissions: &[(u64, u64)]) -> CommitteeFeed {
+        let mut feed = CommitteeFeed::zero();
+        feed.quorum = quorum;
+        for &(price, time) in submissions {
+            feed.submissions.push(Submission {
+                publisher: Pubkey::new_unique(),
+                price: Decimal::from(price),
+                time: at(time),
+            });
+        }
+        feed
+    }
+
+    #[test]
+    fn test_median_odd() {
+        let feed = feed(2, &[(10, 1000), (30, 1010), (20, 990)]);
+        assert_eq!(feed.median(at(1010)), Ok((Decimal::from(20), at(990))));
+    }
+
+    #[test]
+    fn test_median_even() {
+        let feed = feed(2, &[(10, 1000), (40, 1000), (20, 1000), (30, 1000)]);
+        assert_eq!(feed.median(at(1000)), Ok((Decimal::from(25), at(1000))));
+    }
+
+    #[test]
+    fn test_median_ignores_stale_submissions() {
+        let feed = feed(2, &[(10, 1000), (1000, 800), (20, 1000)]);
+        assert_eq!(feed.median(at(1000)), Ok((Decimal::from(15), at(1000))));
+    }
+
+    #[test]
+    fn test_median_requires_quorum() {
+        let feed = feed(3, &[(10, 1000), (1000, 800), (20, 1000)]);
+        assert!(feed.median(at(1000)).is_err());
+    }
+
+    #[test]
+    fn test_median_ignores_missing_submissions() {
+        let feed = feed(1, &[(10, 1000), (0, 0)]);
+        assert_eq!(feed.median(at(1000)), Ok((Decimal::from(10), at(1000))));
+    }
+}
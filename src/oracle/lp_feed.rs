@@ -0,0 +1,311 @@
+#[cfg(feature = "wasm")]
+use {crate::util::b2pk, wasm_bindgen::prelude::wasm_bindgen};
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, TokenAccount},
+        finance::Decimal,
+        oracle::Oracle,
+        state::SovereignAuth,
+        token::Mint,
+        traits::{Account, Pod, Store},
+        util::{require, Expect, Time},
+    },
+    solana_program::{clock::Clock, program_pack::Pack, pubkey::Pubkey},
+    spl_token::state::{Account as TokenAccountState, Mint as SplMint},
+};
+
+/// An oracle for constant-product AMM LP tokens.
+///
+/// The price is derived from the pool invariant `k` and the oracle prices of
+/// both underlying assets as `2 * sqrt(k * p0 * p1) / supply`, so it cannot be
+/// moved by trading against the pool. It is cached by `LpFeedUpdate`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct LpFeed {
+    initialized: bool,
+    nonce: u8,
+    lp_mint_decimals: u8,
+    reserve_decimals: [u8; 2],
+    lp_mint: Pubkey,
+    reserves: [Pubkey; 2],
+    oracles: [Oracle; 2],
+    price: Decimal,
+    time: Time,
+}
+
+pub struct LpFeedParams {
+    pub sovereign_auth: SovereignAuth,
+    pub lp_mint_account: MintAccount<Readonly>,
+    pub reserve_accounts: [TokenAccount<Readonly>; 2],
+    pub reserve_mint_accounts: [MintAccount<Readonly>; 2],
+    pub oracles: [Oracle; 2],
+}
+
+impl Store for LpFeed {
+    const SEED_PREFIX: &'static str = "lp_feed";
+    type Params = LpFeedParams;
+    type DeriveData<'a> = &'a Pubkey;
+    type CreateData<'a> = MintAccount<Readonly>;
+    type LoadData = ();
+    type LoadAuthData = SovereignAuth;
+
+    fn get_seeds_on_derive<'a>(derive_data: Self::DeriveData<'a>) -> [&'a [u8]; 2] {
+        [derive_data.as_bytes(), &[]]
+    }
+    fn get_seeds_on_create<'a>(lp_mint_account: MintAccount<Readonly>) -> [&'a [u8]; 2] {
+        [lp_mint_account.get_info().key.as_bytes(), &[]]
+    }
+    fn get_seeds_on_load<'a>(&'a self, _: ()) -> [&'a [u8]; 2] {
+        [self.lp_mint.as_bytes(), &[]]
+    }
+    fn get_seeds_on_load_auth<'a>(&'a self, sovereign_auth: SovereignAuth) -> [&'a [u8]; 2] {
+        _ = sovereign_auth;
+        [self.lp_mint.as_bytes(), &[]]
+    }
+
+    fn initialize(&mut self, nonce: u8, params: Self::Params) {
+        _ = params.sovereign_auth;
+        self.initialized = true;
+        self.nonce = nonce;
+        self.lp_mint = *Mint::from_account(
+            params.lp_mint_account,
+            Expect::Any,
+            Expect::Any,
+            Expect::Any,
+            &mut self.lp_mint_decimals,
+            &mut 0,
+        )
+        .get_key();
+        for (i, reserve_account) in params.reserve_accounts.into_iter().enumerate() {
+            let reserve_mint = Mint::from_account(
+                params.reserve_mint_accounts[i],
+                Expect::Any,
+                Expect::Any,
+                Expect::Any,
+                &mut self.reserve_decimals[i],
+                &mut 0,
+            );
+            let reserve_data = reserve_account.get_info().data.borrow();
+            let reserve = TokenAccountState::unpack(&reserve_data)
+                .map_err(|_| "couldn't unpack reserve account data")
+                .unwrap();
+            require(
+                &reserve.mint == reserve_mint.get_key(),
+                "Reserve account mint does not match reserve mint",
+            );
+            self.reserves[i] = *reserve_account.get_info().key;
+        }
+        for oracle in &params.oracles {
+            require(oracle.is_enabled(), "Oracle kind is not enabled");
+        }
+        self.oracles = params.oracles;
+        self.price = Decimal::zero();
+        self.time = Time::from_unix_timestamp(0);
+    }
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+    fn get_nonce(&self) -> u8 {
+        self.nonce
+    }
+}
+
+unsafe impl Pod for LpFeed {
+    const NAME: &'static str = "LpFeed";
+}
+
+// Unauthorized functions
+impl LpFeed {
+    /// Recomputes the fair LP token price from the pool and underlying oracles.
+    pub fn update(
+        &mut self,
+        lp_mint_account: MintAccount<Readonly>,
+        reserve_accounts: [TokenAccount<Readonly>; 2],
+        oracle_accounts: [Readonly; 2],
+        clock: &Clock,
+    ) {
+        require(
+            lp_mint_account.get_info().key == &self.lp_mint,
+            "LP mint account mismatch",
+        );
+        for (reserve_account, reserve) in reserve_accounts.iter().zip(&self.reserves) {
+            require(
+                reserve_account.get_info().key == reserve,
+                "Reserve account mismatch",
+            );
+        }
+        let lp_mint_data = lp_mint_account.get_info().data.borrow();
+        let reserve_data = reserve_accounts.map(|r| r.get_info().data.borrow());
+        let (reserves, supply) = self
+            .read_pool(&lp_mint_data, [&reserve_data[0][..], &reserve_data[1][..]])
+            .unwrap();
+        let prices = [0, 1].map(|i| self.oracles[i].query_usd(oracle_accounts[i], clock));
+        self.price = fair_price(reserves, prices, supply);
+        self.time = Time::now(clock);
+    }
+}
+
+// For internal use only
+impl LpFeed {
+    pub fn query(data: &[u8]) -> Result<(Decimal, Time), &'static str> {
+        // Provided oracle account key assumed valid
+        let lp_feed = Self::load_unchecked(data)?;
+        Ok((lp_feed.price, lp_feed.time))
+    }
+
+    /// Returns the pool reserves and LP token supply.
+    fn read_pool(
+        &self,
+        lp_mint_data: &[u8],
+        reserve_data: [&[u8]; 2],
+    ) -> Result<([Decimal; 2], Decimal), &'static str> {
+        let lp_mint = SplMint::unpack(lp_mint_data).map_err(|_| "couldn't unpack LP mint")?;
+        let supply = Decimal::from_token_amount(lp_mint.supply, self.lp_mint_decimals);
+        let reserve_amount = |data| {
+            TokenAccountState::unpack(data)
+                .map(|reserve| reserve.amount)
+                .map_err(|_| "couldn't unpack reserve account")
+        };
+        let reserves = [
+            Decimal::from_token_amount(reserve_amount(reserve_data[0])?, self.reserve_decimals[0]),
+            Decimal::from_token_amount(reserve_amount(reserve_data[1])?, self.reserve_decimals[1]),
+        ];
+        Ok((reserves, supply))
+    }
+}
+
+/// Returns the fair price of one LP token, `2 * sqrt(r0 * p0) * sqrt(r1 * p1) / supply`,
+/// which equals `2 * sqrt(k * p0 * p1) / supply` without overflowing on `k`.
+fn fair_price(reserves: [Decimal; 2], prices: [Decimal; 2], supply: Decimal) -> Decimal {
+    if supply.is_zero() {
+        return Decimal::zero();
+    }
+    let value_sqrts = [0, 1].map(|i| (reserves[i] * prices[i]).sqrt());
+    value_sqrts[0] * value_sqrts[1] * 2u64 / supply
+}
+
+#[cfg(feature = "wasm")]
+#[allow(non_snake_case)]
+#[wasm_bindgen]
+impl LpFeed {
+    #[wasm_bindgen(js_name = deriveKey)]
+    pub fn derive_key(programKey: &[u8], lpMintKey: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(Self::derive_address_raw(
+            &b2pk(programKey)?,
+            &b2pk(lpMintKey)?,
+        ))
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<LpFeed, String> {
+        Self::try_cast_from(bytes)
+            .map(|x| *x)
+            .map_err(|e| format!("Invalid LP feed: {}", e))
+    }
+
+    #[wasm_bindgen(getter, js_name = lpMint)]
+    pub fn lp_mint(&self) -> Vec<u8> {
+        self.lp_mint.to_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = reserveKey)]
+    pub fn reserve_key(&self, index: usize) -> Option<Vec<u8>> {
+        self.reserves.get(index).map(|r| r.to_bytes().to_vec())
+    }
+
+    #[wasm_bindgen(js_name = oracle)]
+    pub fn oracle(&self, index: usize) -> Option<Oracle> {
+        self.oracles.get(index).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_program::program_option::COption};
+
+    fn lp_mint_fixture(supply: u64) -> Vec<u8> {
+        let mint = SplMint {
+            mint_authority: COption::None,
+            supply,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0u8; SplMint::LEN];
+        SplMint::pack(mint, &mut data).unwrap();
+        data
+    }
+
+    fn reserve_fixture(amount: u64) -> Vec<u8> {
+        let account = TokenAccountState {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; TokenAccountState::LEN];
+        TokenAccountState::pack(account, &mut data).unwrap();
+        data
+    }
+
+    fn feed() -> LpFeed {
+        let mut feed = LpFeed::zero();
+        feed.lp_mint_decimals = 6;
+        feed.reserve_decimals = [9, 6];
+        feed
+    }
+
+    #[test]
+    fn test_read_pool() {
+        let lp_mint = lp_mint_fixture(2_000_000);
+        let reserve_a = reserve_fixture(50_000_000_000);
+        let reserve_b = reserve_fixture(5_000_000_000);
+        let (reserves, supply) = feed()
+            .read_pool(&lp_mint, [&reserve_a[..], &reserve_b[..]])
+            .unwrap();
+        assert_eq!(reserves, [Decimal::from(50), Decimal::from(5000)]);
+        assert_eq!(supply, Decimal::from(2));
+    }
+
+    #[test]
+    fn test_read_pool_rejects_invalid_accounts() {
+        let lp_mint = lp_mint_fixture(1);
+        let reserve = reserve_fixture(1);
+        assert!(feed()
+            .read_pool(&reserve, [&reserve[..], &reserve[..]])
+            .is_err());
+        assert!(feed()
+            .read_pool(&lp_mint, [&lp_mint[..], &reserve[..]])
+            .is_err());
+    }
+
+    #[test]
+    fn test_fair_price_balanced_pool() {
+        // 25 SOL at 100 USD and 2500 USDC at 1 USD, 2 LP tokens
+        let reserves = [Decimal::from(25), Decimal::from(2500)];
+        let prices = [Decimal::from(100), Decimal::one()];
+        let price = fair_price(reserves, prices, Decimal::from(2));
+        assert_eq!(price, Decimal::from(2500));
+    }
+
+    #[test]
+    fn test_fair_price_ignores_spot_reserves() {
+        // Same pool after a large swap: k is unchanged but reserves are skewed
+        let reserves = [Decimal::from(100), Decimal::from(625)];
+        let prices = [Decimal::from(100), Decimal::one()];
+        let price = fair_price(reserves, prices, Decimal::from(2));
+        assert_eq!(price, Decimal::from(2500));
+    }
+
+    #[test]
+    fn test_fair_price_empty_pool() {
+        let reserves = [Decimal::zero(); 2];
+        let prices = [Decimal::from(100), Decimal::one()];
+        assert_eq!(
+            fair_price(reserves, prices, Decimal::zero()),
+            Decimal::zero()
+        );
+    }
+}
@@ -1,12 +1,30 @@
+mod circuit_breaker;
+mod committee_feed;
+mod lp_feed;
 mod oracle;
 mod oracle_kind;
+mod price_mode;
 mod pyth;
 mod switchboard;
+mod twap;
+#[cfg(feature = "devnet")]
 mod user_feed;
 mod validity;
+#[cfg(feature = "devnet")]
 mod zero_feed;
 
+#[cfg(feature = "devnet")]
+pub use {user_feed::UserFeed, zero_feed::ZeroFeed};
+
 pub use {
-    oracle::Oracle, oracle_kind::OracleKind, pyth::Pyth, switchboard::Switchboard,
-    user_feed::UserFeed, validity::Validity, zero_feed::ZeroFeed,
+    circuit_breaker::CircuitBreaker,
+    committee_feed::{CommitteeFeed, CommitteeFeedParams},
+    lp_feed::{LpFeed, LpFeedParams},
+    oracle::Oracle,
+    oracle_kind::OracleKind,
+    price_mode::{PriceMode, PricePurpose},
+    pyth::Pyth,
+    switchboard::Switchboard,
+    twap::Twap,
+    validity::Validity,
 };
@@ -2,7 +2,7 @@ use {
     crate::{
         accounts::Readonly,
         finance::{Decimal, InterestRate},
-        oracle::{OracleKind, Pyth, Switchboard, UserFeed, Validity, ZeroFeed},
+        oracle::{CommitteeFeed, LpFeed, OracleKind, Pyth, Switchboard, Validity},
         state::DvdPrice,
         traits::Account,
         util::{require, Time},
@@ -10,6 +10,8 @@ use {
     solana_program::{clock::Clock, pubkey::Pubkey},
 };
 
+#[cfg(feature = "devnet")]
+use crate::oracle::{UserFeed, ZeroFeed};
 #[cfg(feature = "wasm")]
 use {crate::util::b2pk, wasm_bindgen::prelude::*};
 
@@ -41,6 +43,10 @@ impl Oracle {
         }
     }
 
+    pub const fn is_enabled(&self) -> bool {
+        self.kind.is_enabled()
+    }
+
     fn query_usd_raw(
         &self,
         key: &Pubkey,
@@ -52,10 +58,16 @@ impl Oracle {
             return Err("Oracle account mismatch");
         }
         let (price, price_time) = match self.kind {
+            #[cfg(feature = "devnet")]
             OracleKind::ZeroFeed => ZeroFeed::query(time),
             OracleKind::Pyth => Pyth::query(data, owner),
             OracleKind::Switchboard => Switchboard::query(data, owner),
+            #[cfg(feature = "devnet")]
             OracleKind::UserFeed => UserFeed::query(data, time),
+            OracleKind::LpFeed => LpFeed::query(data),
+            OracleKind::CommitteeFeed => CommitteeFeed::query(data, time),
+            #[cfg(not(feature = "devnet"))]
+            OracleKind::ZeroFeed | OracleKind::UserFeed => Err("Oracle kind is not enabled"),
         }?;
         let validity = match time.secs_since(price_time) {
             0..=STALE_AFTER_SECS_ELAPSED => Validity::Fresh,
@@ -64,6 +76,17 @@ impl Oracle {
         Ok((price, validity))
     }
 
+    /// Returns the price, in USD, of the oracle's asset.
+    pub fn query_usd(&self, oracle_account: Readonly, clock: &Clock) -> Decimal {
+        let key = oracle_account.get_info().key;
+        let data = oracle_account.get_info().data.borrow();
+        let owner = oracle_account.get_info().owner;
+        let time = Time::now(clock);
+        let (price, validity) = self.query_usd_raw(key, &data, owner, time).unwrap();
+        require(validity == Validity::Fresh, "Oracle price is stale");
+        price
+    }
+
     /// Returns the price, in DVD, of the oracle's asset.
     pub fn query_dvd(
         &self,
@@ -72,13 +95,7 @@ impl Oracle {
         dvd_interest_rate: &InterestRate,
         clock: &Clock,
     ) -> Decimal {
-        let key = oracle_account.get_info().key;
-        let data = oracle_account.get_info().data.borrow();
-        let owner = oracle_account.get_info().owner;
-        let time = Time::now(clock);
-        let (price, validity) = self.query_usd_raw(key, &data, owner, time).unwrap();
-        require(validity == Validity::Fresh, "Oracle price is stale");
-        price / dvd_price.get(dvd_interest_rate, clock)
+        self.query_usd(oracle_account, clock) / dvd_price.get(dvd_interest_rate, clock)
     }
 }
 
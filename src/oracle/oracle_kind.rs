@@ -10,4 +10,20 @@ pub enum OracleKind {
     Pyth = 1,
     Switchboard = 2,
     UserFeed = 3,
+    LpFeed = 4,
+    CommitteeFeed = 5,
+}
+
+impl OracleKind {
+    /// Returns whether oracles of this kind may be used in this build.
+    /// Feeds that report arbitrary prices are only available on devnet.
+    pub const fn is_enabled(self) -> bool {
+        match self {
+            OracleKind::ZeroFeed | OracleKind::UserFeed => cfg!(feature = "devnet"),
+            OracleKind::Pyth
+            | OracleKind::Switchboard
+            | OracleKind::LpFeed
+            | OracleKind::CommitteeFeed => true,
+        }
+    }
 }
@@ -0,0 +1,42 @@
+use crate::finance::Decimal;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// How a collateral is priced when valuing vaults.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum PriceMode {
+    /// The current oracle price.
+    Spot = 0,
+    /// The time-weighted average of recorded oracle prices.
+    Twap = 1,
+    /// Whichever of spot and TWAP is least favourable to the vault owner.
+    Conservative = 2,
+}
+
+/// What a collateral price is being used for.
+#[derive(Clone, Copy)]
+pub enum PricePurpose {
+    /// Borrowing or withdrawing against the collateral.
+    Borrow,
+    /// Checking whether a vault can be liquidated, and starting its auction.
+    Liquidate,
+    /// Paying out collateral for redeemed DVD.
+    Redeem,
+}
+
+impl PriceMode {
+    pub fn select(self, purpose: PricePurpose, spot: Decimal, twap: Decimal) -> Decimal {
+        match (self, purpose) {
+            (PriceMode::Spot, _) => spot,
+            (PriceMode::Twap, _) => twap,
+            (PriceMode::Conservative, PricePurpose::Borrow) => spot.min(twap),
+            (PriceMode::Conservative, PricePurpose::Liquidate | PricePurpose::Redeem) => {
+                spot.max(twap)
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{finance::Decimal, util::Time};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Number of observations kept by a `Twap`.
+const CAPACITY: usize = 16;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Observation {
+    price: Decimal,
+    time: Time,
+}
+
+/// A ring buffer of observed oracle prices, in USD.
+///
+/// Observations are spaced at least `window / CAPACITY` seconds apart,
+/// so that the buffer always spans roughly one averaging window.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Twap {
+    observations: [Observation; CAPACITY],
+    // Index of the most recent observation.
+    head: u8,
+    // Number of observations recorded, up to `CAPACITY`.
+    len: u8,
+}
+
+impl Twap {
+    pub const fn new() -> Self {
+        Self {
+            observations: [Observation {
+                price: Decimal::zero(),
+                time: Time::from_unix_timestamp(0),
+            }; CAPACITY],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn latest(&self) -> Option<&Observation> {
+        match self.len {
+            0 => None,
+            _ => Some(&self.observations[self.head as usize]),
+        }
+    }
+
+    /// Records a price observation, unless the previous one is too recent.
+    pub fn record(&mut self, price: Decimal, time: Time, window: u64) {
+        if let Some(latest) = self.latest() {
+            if time.secs_since(latest.time) < window / CAPACITY as u64 {
+                return;
+            }
+        }
+        if self.len > 0 {
+            self.head = ((self.head as usize + 1) % CAPACITY) as u8;
+        }
+        self.observations[self.head as usize] = Observation { price, time };
+        if (self.len as usize) < CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Returns the time-weighted average price over the last `window` seconds.
+    ///
+    /// Each observation is assumed to hold until the next one, and the most
+    /// recent one until `time`. If the history does not cover the whole window,
+    /// the average is taken over the covered part only.
+    pub fn average(&self, window: u64, time: Time) -> Option<Decimal> {
+        let latest = self.latest()?;
+        let mut weighted_sum = Decimal::zero();
+        let mut total_weight = 0u64;
+        let mut end = time;
+        for i in 0..self.len as usize {
+            let observation = &self.observations[(self.head as usize + CAPACITY - i) % CAPACITY];
+            let age_of_end = time.secs_since(end);
+            if age_of_end >= window {
+                break;
+            }
+            let age_of_start = time.secs_since(observation.time).min(window);
+            let weight = age_of_start.saturating_sub(age_of_end);
+            weighted_sum += observation.price * weight;
+            total_weight += weight;
+            end = observation.time;
+        }
+        if total_weight == 0 {
+            return Some(latest.price);
+        }
+        Some(weighted_sum / total_weight)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Twap {
+    #[wasm_bindgen(getter, js_name = observationCount)]
+    pub fn observation_count(&self) -> u8 {
+        self.len
+    }
+
+    #[wasm_bindgen(js_name = getAverage)]
+    #[allow(non_snake_case)]
+    pub fn get_average(&self, window: f64, unixTimestamp: f64) -> Option<f64> {
+        let time = Time::from_unix_timestamp(unixTimestamp as u64);
+        self.average(window as u64, time).map(Decimal::to_f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> Time {
+        Time::from_unix_timestamp(secs)
+    }
+
+    #[test]
+    fn test_average_empty() {
+        let twap = Twap::new();
+        assert!(twap.average(100, at(1000)).is_none());
+    }
+
+    #[test]
+    fn test_average_single_observation() {
+        let mut twap = Twap::new();
+        twap.record(Decimal::from(5), at(1000), 160);
+        assert_eq!(twap.average(160, at(1000)), Some(Decimal::from(5)));
+        assert_eq!(twap.average(160, at(1100)), Some(Decimal::from(5)));
+    }
+
+    #[test]
+    fn test_average_weights_by_time() {
+        let mut twap = Twap::new();
+        twap.record(Decimal::from(10), at(1000), 160);
+        twap.record(Decimal::from(20), at(1060), 160);
+        // 60 secs at 10, 20 secs at 20
+        let expected = (Decimal::from(10) * 60u64 + Decimal::from(20) * 20u64) / 80u64;
+        assert_eq!(twap.average(160, at(1080)), Some(expected));
+        // window only covers the last 40 secs: 20 secs at 10, 20 secs at 20
+        assert_eq!(twap.average(40, at(1080)), Some(Decimal::from(15)));
+    }
+
+    #[test]
+    fn test_record_respects_spacing() {
+        let mut twap = Twap::new();
+        twap.record(Decimal::from(10), at(1000), 160);
+        // 160 / 16 = 10 secs minimum spacing
+        twap.record(Decimal::from(1000), at(1005), 160);
+        assert_eq!(twap.average(160, at(1010)), Some(Decimal::from(10)));
+        twap.record(Decimal::from(20), at(1010), 160);
+        assert_eq!(twap.average(160, at(1010)), Some(Decimal::from(10)));
+        assert_eq!(twap.average(160, at(1020)), Some(Decimal::from(15)));
+    }
+
+    #[test]
+    fn test_record_wraps_around() {
+        let mut twap = Twap::new();
+        for i in 0..(CAPACITY as u64 * 2) {
+            twap.record(Decimal::from(i), at(1000 + i * 10), 160);
+        }
+        assert_eq!(twap.len as usize, CAPACITY);
+        let last = CAPACITY as u64 * 2 - 1;
+        assert_eq!(
+            twap.average(10, at(1000 + last * 10 + 10)),
+            Some(Decimal::from(last))
+        );
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
     finance::{AuctionConfig, BookConfig, Decimal, InterestRate},
     oracle::Oracle,
-    state::{OfferingConfig, SovereignAuth},
+    state::{
+        FixedRateConfig, MintLimitConfig, OfferingConfig, OriginationConfig, RecoveryConfig,
+        RedemptionConfig, SavingsExitConfig, SovereignAuth,
+    },
     store::VaultConfig,
     traits::Pod,
+    util::require,
 };
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -15,13 +19,22 @@ use super::flash_mint::FlashMintConfig;
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Config {
     max_ltv: Decimal,
+    // The share of liquidation penalties and flash mint fees set aside in the insurance fund.
+    insurance_rate: Decimal,
     dvd_interest_rate: InterestRate,
     dove_oracle: Oracle,
     auction_config: AuctionConfig,
     debt_config: BookConfig,
+    fixed_rate_config: FixedRateConfig,
     flash_mint_config: FlashMintConfig,
+    mint_limit_config: MintLimitConfig,
     offering_config: OfferingConfig,
+    origination_config: OriginationConfig,
+    recovery_config: RecoveryConfig,
+    redemption_config: RedemptionConfig,
     savings_config: BookConfig,
+    savings_exit_config: SavingsExitConfig,
+    term_savings_config: FixedRateConfig,
     vault_config: VaultConfig,
 }
 
@@ -30,6 +43,10 @@ impl Config {
         self.max_ltv
     }
 
+    pub const fn get_insurance_rate(&self) -> Decimal {
+        self.insurance_rate
+    }
+
     pub const fn get_dvd_interest_rate(&self) -> &InterestRate {
         &self.dvd_interest_rate
     }
@@ -46,23 +63,83 @@ impl Config {
         &self.debt_config
     }
 
+    pub const fn get_fixed_rate_config(&self) -> &FixedRateConfig {
+        &self.fixed_rate_config
+    }
+
     pub const fn get_flash_mint_config(&self) -> &FlashMintConfig {
         &self.flash_mint_config
     }
 
+    pub const fn get_mint_limit_config(&self) -> &MintLimitConfig {
+        &self.mint_limit_config
+    }
+
     pub const fn get_offering_config(&self) -> &OfferingConfig {
         &self.offering_config
     }
 
+    pub const fn get_origination_config(&self) -> &OriginationConfig {
+        &self.origination_config
+    }
+
+    pub const fn get_recovery_config(&self) -> &RecoveryConfig {
+        &self.recovery_config
+    }
+
+    pub const fn get_redemption_config(&self) -> &RedemptionConfig {
+        &self.redemption_config
+    }
+
     pub const fn get_savings_config(&self) -> &BookConfig {
         &self.savings_config
     }
 
+    pub const fn get_savings_exit_config(&self) -> &SavingsExitConfig {
+        &self.savings_exit_config
+    }
+
+    pub const fn get_term_savings_config(&self) -> &FixedRateConfig {
+        &self.term_savings_config
+    }
+
     pub const fn get_vault_config(&self) -> &VaultConfig {
         &self.vault_config
     }
 
     pub fn update(&mut self, _: SovereignAuth, new_config: Config) {
+        require(
+            new_config.insurance_rate <= Decimal::one(),
+            "Insurance rate must be at most 1",
+        );
+        require(
+            new_config.mint_limit_config.is_valid(),
+            "Mint rate limit window must be positive",
+        );
+        require(
+            new_config.origination_config.is_valid(),
+            "Origination fee must be below 1 and referral share at most 1",
+        );
+        require(
+            new_config.recovery_config.is_valid(),
+            "Recovery LTVs must satisfy withdraw max LTV <= max LTV < 1",
+        );
+        require(
+            new_config.redemption_config.is_valid(),
+            "Redemption fees must satisfy fee floor <= max fee <= 1",
+        );
+        require(
+            new_config.savings_exit_config.is_valid(),
+            "Savings withdrawal epoch length must be positive when capped",
+        );
+        require(
+            new_config.savings_config.is_valid(),
+            "Savings link share must be at most 1, and the savings rate zero when linked",
+        );
+        require(
+            new_config.dove_oracle.is_enabled(),
+            "DOVE oracle kind is not enabled",
+        );
         *self = new_config;
     }
 }
@@ -73,27 +150,46 @@ impl Config {
     #[allow(non_snake_case)]
     pub fn new(
         maxLtv: f64,
+        insuranceRate: f64,
         dvdInterestRate: InterestRate,
         doveOracle: Oracle,
         auctionConfig: AuctionConfig,
         debtConfig: BookConfig,
+        fixedRateConfig: FixedRateConfig,
         flashMintConfig: FlashMintConfig,
+        mintLimitConfig: MintLimitConfig,
         offeringConfig: OfferingConfig,
+        originationConfig: OriginationConfig,
+        recoveryConfig: RecoveryConfig,
+        redemptionConfig: RedemptionConfig,
         savingsConfig: BookConfig,
+        savingsExitConfig: SavingsExitConfig,
+        termSavingsConfig: FixedRateConfig,
         vaultConfig: VaultConfig,
     ) -> Result<Self, String> {
         if maxLtv <= 0.0 || maxLtv >= 1.0 {
             return Err("max_ltv must be between 0 and 1".to_string());
         }
+        if !(0.0..=1.0).contains(&insuranceRate) {
+            return Err("insurance_rate must be between 0 and 1".to_string());
+        }
         Ok(Self {
             max_ltv: Decimal::from(maxLtv),
+            insurance_rate: Decimal::from(insuranceRate),
             dvd_interest_rate: dvdInterestRate,
             dove_oracle: doveOracle,
             auction_config: auctionConfig,
             debt_config: debtConfig,
+            fixed_rate_config: fixedRateConfig,
             flash_mint_config: flashMintConfig,
+            mint_limit_config: mintLimitConfig,
             offering_config: offeringConfig,
+            origination_config: originationConfig,
+            recovery_config: recoveryConfig,
+            redemption_config: redemptionConfig,
             savings_config: savingsConfig,
+            savings_exit_config: savingsExitConfig,
+            term_savings_config: termSavingsConfig,
             vault_config: vaultConfig,
         })
     }
@@ -103,6 +199,11 @@ impl Config {
         self.max_ltv.to_f64()
     }
 
+    #[wasm_bindgen(getter, js_name = "insuranceRate")]
+    pub fn insurance_rate(&self) -> f64 {
+        self.insurance_rate.to_f64()
+    }
+
     #[wasm_bindgen(getter, js_name = "dvdInterestRate")]
     pub fn dvd_interest_rate(&self) -> InterestRate {
         self.dvd_interest_rate
@@ -123,21 +224,56 @@ impl Config {
         self.debt_config
     }
 
+    #[wasm_bindgen(getter, js_name = "fixedRateConfig")]
+    pub fn fixed_rate_config(&self) -> FixedRateConfig {
+        self.fixed_rate_config
+    }
+
     #[wasm_bindgen(getter, js_name = "flashMintConfig")]
     pub fn flash_mint_config(&self) -> FlashMintConfig {
         self.flash_mint_config
     }
 
+    #[wasm_bindgen(getter, js_name = "mintLimitConfig")]
+    pub fn mint_limit_config(&self) -> MintLimitConfig {
+        self.mint_limit_config
+    }
+
     #[wasm_bindgen(getter, js_name = "offeringConfig")]
     pub fn offering_config(&self) -> OfferingConfig {
         self.offering_config
     }
 
+    #[wasm_bindgen(getter, js_name = "originationConfig")]
+    pub fn origination_config(&self) -> OriginationConfig {
+        self.origination_config
+    }
+
+    #[wasm_bindgen(getter, js_name = "recoveryConfig")]
+    pub fn recovery_config(&self) -> RecoveryConfig {
+        self.recovery_config
+    }
+
+    #[wasm_bindgen(getter, js_name = "redemptionConfig")]
+    pub fn redemption_config(&self) -> RedemptionConfig {
+        self.redemption_config
+    }
+
     #[wasm_bindgen(getter, js_name = "savingsConfig")]
     pub fn savings_config(&self) -> BookConfig {
         self.savings_config
     }
 
+    #[wasm_bindgen(getter, js_name = "savingsExitConfig")]
+    pub fn savings_exit_config(&self) -> SavingsExitConfig {
+        self.savings_exit_config
+    }
+
+    #[wasm_bindgen(getter, js_name = "termSavingsConfig")]
+    pub fn term_savings_config(&self) -> FixedRateConfig {
+        self.term_savings_config
+    }
+
     #[wasm_bindgen(getter, js_name = "vaultConfig")]
     pub fn vault_config(&self) -> VaultConfig {
         self.vault_config
@@ -0,0 +1,149 @@
+use {
+    crate::{
+        finance::{Book, BookConfig, FixedTerm, InterestRate},
+        util::{require, Time},
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Configuration for fixed-rate terms, of borrowing or of term deposits.
+///
+/// Borrowers and depositors lock `rate` for a term of up to `max_term`
+/// seconds. Past maturity, debt accrues at the floating rate of the debt
+/// `Book`, and term deposits stop accruing.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct FixedRateConfig {
+    /// The fixed rate offered to new terms.
+    rate: InterestRate,
+    /// The longest term, in seconds. Zero disables the terms.
+    max_term: u64,
+}
+
+impl FixedRateConfig {
+    fn require_term(&self, term: u64) {
+        require(
+            term > 0 && term <= self.max_term,
+            "Term must be positive and at most the maximum term",
+        );
+    }
+
+    /// Starts a term of `term` seconds at the offered rate, reverting if it
+    /// exceeds `max_rate`.
+    pub fn start_term(
+        &self,
+        term: u64,
+        max_rate: InterestRate,
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        clock: &Clock,
+    ) -> FixedTerm {
+        require(self.max_term > 0, "Fixed-rate borrowing is disabled");
+        self.require_term(term);
+        require(self.rate <= max_rate, "Fixed rate exceeds maximum");
+        FixedTerm::new(self.rate, term, debt_book, debt_config, clock)
+    }
+
+    /// Returns the rate and maturity of a deposit locked for `term` seconds,
+    /// reverting if the offered rate is below `min_rate`.
+    pub fn get_deposit_terms(
+        &self,
+        term: u64,
+        min_rate: InterestRate,
+        clock: &Clock,
+    ) -> (InterestRate, Time) {
+        require(self.max_term > 0, "Term deposits are disabled");
+        self.require_term(term);
+        require(self.rate >= min_rate, "Term deposit rate is below minimum");
+        (self.rate, Time::now(clock).add_secs(term))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl FixedRateConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(rate: InterestRate, maxTerm: u64) -> Self {
+        Self {
+            rate,
+            max_term: maxTerm,
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rate(&self) -> InterestRate {
+        self.rate
+    }
+
+    #[wasm_bindgen(getter, js_name = maxTerm)]
+    pub fn max_term(&self) -> f64 {
+        self.max_term as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::finance::Decimal};
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn config() -> FixedRateConfig {
+        FixedRateConfig {
+            rate: InterestRate::from_rate_per_sec(Decimal::one() / 1_000_000u64),
+            max_term: 3600,
+        }
+    }
+
+    #[test]
+    fn test_deposit_terms() {
+        let (rate, maturity) = config().get_deposit_terms(600, InterestRate::zero(), &clock(100));
+        assert!(rate == config().rate);
+        assert!(maturity == Time::from_unix_timestamp(700));
+    }
+
+    #[test]
+    #[should_panic(expected = "Term must be positive and at most the maximum term")]
+    fn test_deposit_term_too_long() {
+        config().get_deposit_terms(3601, InterestRate::zero(), &clock(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Term deposits are disabled")]
+    fn test_deposits_disabled() {
+        let config = FixedRateConfig {
+            max_term: 0,
+            ..config()
+        };
+        config.get_deposit_terms(600, InterestRate::zero(), &clock(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Term deposit rate is below minimum")]
+    fn test_deposit_rate_below_minimum() {
+        let min_rate = InterestRate::from_rate_per_sec(Decimal::one() / 100_000u64);
+        config().get_deposit_terms(600, min_rate, &clock(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Fixed rate exceeds maximum")]
+    fn test_borrow_rate_above_maximum() {
+        let mut book = Book::new(&clock(0));
+        config().start_term(
+            600,
+            InterestRate::from_rate_per_sec(Decimal::one() / 10_000_000u64),
+            &mut book,
+            &BookConfig::default(),
+            &clock(0),
+        );
+    }
+}
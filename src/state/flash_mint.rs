@@ -1,4 +1,5 @@
 use solana_program::{
+    clock::Clock,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
@@ -9,6 +10,7 @@ use crate::{
         InstructionSysvarAccount, MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable,
     },
     finance::Decimal,
+    state::{InsuranceFund, Ledger, MintLimitConfig, MintLimiter},
     store::Authority,
     token::Token,
     traits::Account,
@@ -80,6 +82,10 @@ impl FlashMint {
         flash_mint_config: &FlashMintConfig,
 
         dvd: &mut Token,
+        dvd_deposits: Decimal,
+        mint_limiter: &mut MintLimiter,
+        mint_limit_config: &MintLimitConfig,
+        clock: &Clock,
     ) {
         require(
             self.borrow_amount.is_none(),
@@ -124,6 +130,13 @@ impl FlashMint {
         }
         require(found_repay, "can't find flash mint end instruction");
 
+        mint_limiter.record(
+            borrow_amount,
+            dvd.get_supply() + dvd_deposits,
+            mint_limit_config,
+            clock,
+        );
+
         dvd.mint(
             borrow_amount,
             dvd_mint_account,
@@ -141,18 +154,25 @@ impl FlashMint {
         token_program_account: TokenProgramAccount,
         flash_mint_config: &FlashMintConfig,
         dvd: &mut Token,
+        mint_limiter: &mut MintLimiter,
+        ledger: &mut Ledger,
+        insurance_fund: &mut InsuranceFund,
+        insurance_rate: Decimal,
     ) {
         let borrow_amount = match self.borrow_amount.take() {
             Some(v) => v,
             None => revert("active flash mint not found"),
         };
-        let repay_amount = borrow_amount * (Decimal::one() + flash_mint_config.fee);
+        let fee = borrow_amount * flash_mint_config.fee;
         dvd.burn(
-            repay_amount,
+            borrow_amount + fee,
             dvd_mint_account,
             dvd_account,
             token_program_account,
             user_account,
         );
+        mint_limiter.release(borrow_amount);
+        ledger.record_flash_mint_fee(fee);
+        insurance_fund.collect(fee, insurance_rate);
     }
 }
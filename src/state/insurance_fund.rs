@@ -0,0 +1,143 @@
+use crate::finance::Decimal;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The part of the protocol's equity, in DVD, reserved to cover bad debt.
+///
+/// It is funded by a share of liquidation penalties, once auctions realize
+/// them, and of flash mint fees. The reserve is held out of the surplus like
+/// a liability, so it is never offered in DVD offerings. Bad debt is burned
+/// against it when written off, and a deficit is covered from it before any
+/// DOVE is minted, so both reduce the reserve rather than the equity left
+/// after it.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct InsuranceFund {
+    balance: Decimal,
+}
+
+impl InsuranceFund {
+    pub const fn new() -> Self {
+        Self {
+            balance: Decimal::zero(),
+        }
+    }
+
+    /// Sets aside `rate` of `income`.
+    pub fn collect(&mut self, income: Decimal, rate: Decimal) {
+        self.balance += income * rate;
+    }
+
+    /// Burns bad debt against the fund, as far as its balance goes.
+    pub fn absorb(&mut self, bad_debt: Decimal) {
+        self.balance -= bad_debt.min(self.balance);
+    }
+
+    /// Returns the equity beyond the fund, given the protocol's `assets` and
+    /// `liabilities`.
+    pub fn get_surplus(&self, assets: Decimal, liabilities: Decimal) -> Decimal {
+        assets.saturating_sub(liabilities + self.balance)
+    }
+
+    /// Returns what the protocol's `assets` fall short of its `liabilities`
+    /// and the fund.
+    pub fn get_deficit(&self, assets: Decimal, liabilities: Decimal) -> Decimal {
+        (liabilities + self.balance).saturating_sub(assets)
+    }
+
+    /// Burns as much of `deficit` as the fund can against it, returning the
+    /// amount covered.
+    pub fn cover(&mut self, deficit: Decimal) -> Decimal {
+        let covered = deficit.min(self.balance);
+        self.balance -= covered;
+        covered
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl InsuranceFund {
+    #[wasm_bindgen(getter)]
+    pub fn balance(&self) -> f64 {
+        self.balance.to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn funded(balance: u64) -> InsuranceFund {
+        let mut fund = InsuranceFund::new();
+        fund.collect(Decimal::from(balance), Decimal::one());
+        fund
+    }
+
+    #[test]
+    fn test_collect_sets_aside_share() {
+        let mut fund = InsuranceFund::new();
+        fund.collect(Decimal::from(200u64), Decimal::from(0.25));
+        assert_eq!(fund.balance, Decimal::from(50u64));
+    }
+
+    #[test]
+    fn test_fund_is_held_out_of_surplus() {
+        let fund = funded(30);
+        let liabilities = Decimal::from(100u64);
+        assert_eq!(
+            fund.get_surplus(Decimal::from(150u64), liabilities),
+            Decimal::from(20u64)
+        );
+        assert!(fund
+            .get_surplus(Decimal::from(120u64), liabilities)
+            .is_zero());
+    }
+
+    #[test]
+    fn test_absorbed_bad_debt_leaves_no_deficit() {
+        let mut fund = funded(30);
+        let liabilities = Decimal::from(100u64);
+        // 20 of the 130 DVD of vault debt is written off
+        fund.absorb(Decimal::from(20u64));
+        assert_eq!(fund.balance, Decimal::from(10u64));
+        assert!(fund
+            .get_deficit(Decimal::from(110u64), liabilities)
+            .is_zero());
+    }
+
+    #[test]
+    fn test_absorb_beyond_balance() {
+        let mut fund = funded(30);
+        fund.absorb(Decimal::from(50u64));
+        assert!(fund.balance.is_zero());
+        assert_eq!(
+            fund.get_deficit(Decimal::from(80u64), Decimal::from(100u64)),
+            Decimal::from(20u64)
+        );
+    }
+
+    #[test]
+    fn test_cover_shrinks_deficit() {
+        let mut fund = funded(30);
+        let assets = Decimal::from(110u64);
+        let liabilities = Decimal::from(100u64);
+        let deficit = fund.get_deficit(assets, liabilities);
+        assert_eq!(deficit, Decimal::from(20u64));
+
+        assert_eq!(fund.cover(deficit), deficit);
+        assert!(fund.get_deficit(assets, liabilities).is_zero());
+        assert_eq!(fund.balance, Decimal::from(10u64));
+    }
+
+    #[test]
+    fn test_cover_beyond_balance() {
+        let mut fund = funded(30);
+        let assets = Decimal::from(80u64);
+        let liabilities = Decimal::from(100u64);
+        let deficit = fund.get_deficit(assets, liabilities);
+        assert_eq!(fund.cover(deficit), Decimal::from(30u64));
+        assert_eq!(fund.get_deficit(assets, liabilities), Decimal::from(20u64));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::finance::Decimal;
+#[cfg(feature = "wasm")]
+use crate::{
+    finance::{Book, BookConfig},
+    state::TermSavings,
+    util::Time,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Cumulative protocol income and losses, in DVD.
+///
+/// Interest is tracked by the debt and savings books themselves, this keeps
+/// everything else that changes the protocol's equity.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Ledger {
+    // Penalties added to the debt of liquidated vaults.
+    liquidation_penalties: Decimal,
+    // Fees burned on top of flash mint repayments.
+    flash_mint_fees: Decimal,
+    // Origination fees, net of referral payouts, and collateral stability fees, on top of interest.
+    stability_fees: Decimal,
+    // Collateral kept as redemption fees, valued in DVD at redemption.
+    redemption_fees: Decimal,
+    // Vault debt written off by failed auctions.
+    bad_debt: Decimal,
+    // Interest and fees repaid on vault debt, and origination fees paid upfront.
+    realized_revenue: Decimal,
+}
+
+impl Ledger {
+    pub const fn new() -> Self {
+        Self {
+            liquidation_penalties: Decimal::zero(),
+            flash_mint_fees: Decimal::zero(),
+            stability_fees: Decimal::zero(),
+            redemption_fees: Decimal::zero(),
+            bad_debt: Decimal::zero(),
+            realized_revenue: Decimal::zero(),
+        }
+    }
+
+    pub fn record_liquidation_penalty(&mut self, amount: Decimal) {
+        self.liquidation_penalties += amount;
+    }
+
+    pub fn record_flash_mint_fee(&mut self, amount: Decimal) {
+        self.flash_mint_fees += amount;
+    }
+
+    pub fn record_stability_fee(&mut self, amount: Decimal) {
+        self.stability_fees += amount;
+    }
+
+    pub fn record_redemption_fee(&mut self, amount: Decimal) {
+        self.redemption_fees += amount;
+    }
+
+    pub fn record_bad_debt(&mut self, amount: Decimal) {
+        self.bad_debt += amount;
+    }
+
+    pub fn record_realized_revenue(&mut self, amount: Decimal) {
+        self.realized_revenue += amount;
+    }
+
+    /// Returns the realized revenue net of bad debt, or zero if the bad debt
+    /// exceeds it.
+    #[cfg(test)]
+    pub fn get_net_realized_revenue(&self) -> Decimal {
+        self.realized_revenue.saturating_sub(self.bad_debt)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Ledger {
+    pub fn project_balance_sheet(
+        &self,
+        debt: &Book,
+        debt_config: &BookConfig,
+        savings: &Book,
+        savings_config: &BookConfig,
+        term_savings: &TermSavings,
+        stability_pool_deposits: Decimal,
+        dvd_supply: Decimal,
+        stable_dvd_circulating: Decimal,
+        time: Time,
+    ) -> BalanceSheet {
+        BalanceSheet {
+            assets: debt.project_total_at(debt_config, time) + stable_dvd_circulating,
+            liabilities: dvd_supply
+                + savings.project_total_at(savings_config, time)
+                + term_savings.project_liability_at(time)
+                + stability_pool_deposits,
+            interest_income: debt.project_interest_at(debt_config, time),
+            interest_expense: savings.project_interest_at(savings_config, time)
+                + term_savings.get_interest(),
+            ledger: *self,
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Ledger {
+    #[wasm_bindgen(getter, js_name = liquidationPenalties)]
+    pub fn liquidation_penalties(&self) -> f64 {
+        self.liquidation_penalties.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = flashMintFees)]
+    pub fn flash_mint_fees(&self) -> f64 {
+        self.flash_mint_fees.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = stabilityFees)]
+    pub fn stability_fees(&self) -> f64 {
+        self.stability_fees.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = redemptionFees)]
+    pub fn redemption_fees(&self) -> f64 {
+        self.redemption_fees.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = badDebt)]
+    pub fn bad_debt(&self) -> f64 {
+        self.bad_debt.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = realizedRevenue)]
+    pub fn realized_revenue(&self) -> f64 {
+        self.realized_revenue.to_f64()
+    }
+}
+
+/// The protocol's assets, liabilities and cumulative income at a point in time.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct BalanceSheet {
+    assets: Decimal,
+    liabilities: Decimal,
+    interest_income: Decimal,
+    interest_expense: Decimal,
+    ledger: Ledger,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl BalanceSheet {
+    /// Vault debt and the DVD backed by stability modules.
+    #[wasm_bindgen(getter)]
+    pub fn assets(&self) -> f64 {
+        self.assets.to_f64()
+    }
+
+    /// DVD supply, and savings, term and stability pool deposits.
+    #[wasm_bindgen(getter)]
+    pub fn liabilities(&self) -> f64 {
+        self.liabilities.to_f64()
+    }
+
+    /// Assets minus liabilities, negative when the protocol is in deficit.
+    #[wasm_bindgen(getter)]
+    pub fn equity(&self) -> f64 {
+        self.assets.to_f64() - self.liabilities.to_f64()
+    }
+
+    /// Interest accrued on vault debt.
+    #[wasm_bindgen(getter, js_name = interestIncome)]
+    pub fn interest_income(&self) -> f64 {
+        self.interest_income.to_f64()
+    }
+
+    /// Interest accrued on savings deposits, and paid on term deposits.
+    #[wasm_bindgen(getter, js_name = interestExpense)]
+    pub fn interest_expense(&self) -> f64 {
+        self.interest_expense.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ledger(&self) -> Ledger {
+        self.ledger
+    }
+
+    /// All income minus savings interest and bad debt.
+    #[wasm_bindgen(getter, js_name = netIncome)]
+    pub fn net_income(&self) -> f64 {
+        let income = self.interest_income + self.ledger.stability_fees + self.get_other_income();
+        income.to_f64() - self.get_losses().to_f64()
+    }
+
+    /// Like `netIncome`, but counting only the interest and fees repaid on
+    /// vault debt.
+    #[wasm_bindgen(getter, js_name = realizedNetIncome)]
+    pub fn realized_net_income(&self) -> f64 {
+        let income = self.ledger.realized_revenue + self.get_other_income();
+        income.to_f64() - self.get_losses().to_f64()
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl BalanceSheet {
+    // Income that isn't accrued on vault debt.
+    fn get_other_income(&self) -> Decimal {
+        self.ledger.liquidation_penalties
+            + self.ledger.flash_mint_fees
+            + self.ledger.redemption_fees
+    }
+
+    fn get_losses(&self) -> Decimal {
+        self.interest_expense + self.ledger.bad_debt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_accumulate() {
+        let mut ledger = Ledger::new();
+        for _ in 0..2 {
+            ledger.record_liquidation_penalty(Decimal::from(1u64));
+            ledger.record_flash_mint_fee(Decimal::from(2u64));
+            ledger.record_stability_fee(Decimal::from(3u64));
+            ledger.record_redemption_fee(Decimal::from(4u64));
+            ledger.record_bad_debt(Decimal::from(5u64));
+            ledger.record_realized_revenue(Decimal::from(6u64));
+        }
+        assert_eq!(ledger.liquidation_penalties, Decimal::from(2u64));
+        assert_eq!(ledger.flash_mint_fees, Decimal::from(4u64));
+        assert_eq!(ledger.stability_fees, Decimal::from(6u64));
+        assert_eq!(ledger.redemption_fees, Decimal::from(8u64));
+        assert_eq!(ledger.bad_debt, Decimal::from(10u64));
+        assert_eq!(ledger.realized_revenue, Decimal::from(12u64));
+    }
+
+    #[test]
+    fn test_net_realized_revenue_saturates() {
+        let mut ledger = Ledger::new();
+        ledger.record_realized_revenue(Decimal::from(10u64));
+        ledger.record_bad_debt(Decimal::from(4u64));
+        assert_eq!(ledger.get_net_realized_revenue(), Decimal::from(6u64));
+
+        ledger.record_bad_debt(Decimal::from(10u64));
+        assert!(ledger.get_net_realized_revenue().is_zero());
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_project_balance_sheet() {
+        use {crate::finance::Page, solana_program::clock::Clock};
+
+        let clock = Clock::default();
+        let config = BookConfig::default();
+        let mut debt = Book::new(&clock);
+        Page::new().add(Decimal::from(100u64), &mut debt, &config, &clock);
+        let mut savings = Book::new(&clock);
+        Page::new().add(Decimal::from(30u64), &mut savings, &config, &clock);
+        let mut term_savings = TermSavings::new();
+        term_savings.record_deposit(Decimal::from(10u64), Decimal::from(10u64), 60, &clock);
+        let mut ledger = Ledger::new();
+        ledger.record_liquidation_penalty(Decimal::from(1u64));
+        ledger.record_flash_mint_fee(Decimal::from(2u64));
+        ledger.record_stability_fee(Decimal::from(3u64));
+        ledger.record_redemption_fee(Decimal::from(4u64));
+        ledger.record_bad_debt(Decimal::from(5u64));
+        ledger.record_realized_revenue(Decimal::from(6u64));
+
+        let balance_sheet = ledger.project_balance_sheet(
+            &debt,
+            &config,
+            &savings,
+            &config,
+            &term_savings,
+            Decimal::from(20u64),
+            Decimal::from(50u64),
+            Decimal::from(15u64),
+            Time::now(&clock),
+        );
+        assert_eq!(balance_sheet.assets(), 115.0);
+        assert_eq!(balance_sheet.liabilities(), 110.0);
+        assert_eq!(balance_sheet.equity(), 5.0);
+        assert_eq!(balance_sheet.net_income(), 5.0);
+        assert_eq!(balance_sheet.realized_net_income(), 8.0);
+    }
+}
@@ -0,0 +1,207 @@
+use {
+    crate::{
+        finance::Decimal,
+        util::{require, Time},
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Configuration for the limits on DVD issuance.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct MintLimitConfig {
+    /// The maximum DVD supply that borrowing, flash mints, the stability
+    /// module and DVD offerings can issue up to. DVD deposited in savings,
+    /// term deposits and the stability pool counts toward it.
+    supply_ceiling: Decimal,
+    /// The maximum amount of DVD issued per `window`.
+    rate_limit: Decimal,
+    /// The window of the rate limit, in seconds. For example: 3600 (1 hour)
+    window: u64,
+}
+
+impl MintLimitConfig {
+    pub fn is_valid(&self) -> bool {
+        self.window > 0
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl MintLimitConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(supplyCeiling: f64, rateLimit: f64, window: f64) -> Result<MintLimitConfig, String> {
+        let config = Self {
+            supply_ceiling: Decimal::from(supplyCeiling),
+            rate_limit: Decimal::from(rateLimit),
+            window: window as u64,
+        };
+        if !config.is_valid() {
+            return Err("Window must be positive".to_string());
+        }
+        Ok(config)
+    }
+
+    #[wasm_bindgen(getter, js_name = supplyCeiling)]
+    pub fn supply_ceiling(&self) -> f64 {
+        self.supply_ceiling.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = rateLimit)]
+    pub fn rate_limit(&self) -> f64 {
+        self.rate_limit.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn window(&self) -> f64 {
+        self.window as f64
+    }
+}
+
+/// Caps the DVD supply, and the rate at which DVD is issued.
+///
+/// Deposits burn DVD, so the supply ceiling is checked against the supply
+/// along with the DVD deposited, which is owed all the same.
+///
+/// The rate limit is a bucket shared by all issuing paths, which fills up
+/// with every mint and drains at `rate_limit` per `window`. Savings and
+/// stability pool withdrawals, interest and rewards are not limited, so that
+/// users can always get their DVD back.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct MintLimiter {
+    // The DVD issued within the window, as of `time`.
+    used: Decimal,
+    time: Time,
+}
+
+impl MintLimiter {
+    pub const fn new(clock: &Clock) -> Self {
+        Self {
+            used: Decimal::zero(),
+            time: Time::now(clock),
+        }
+    }
+
+    /// Records the issuance of `amount` DVD on top of `dvd_owed`, the supply
+    /// and deposits, reverting if it exceeds the supply ceiling or the rate
+    /// limit.
+    pub fn record(
+        &mut self,
+        amount: Decimal,
+        dvd_owed: Decimal,
+        config: &MintLimitConfig,
+        clock: &Clock,
+    ) {
+        require(
+            dvd_owed + amount <= config.supply_ceiling,
+            "DVD supply ceiling reached",
+        );
+        let time = Time::now(clock);
+        let used = self.get_used_at(config, time) + amount;
+        require(used <= config.rate_limit, "DVD mint rate limit reached");
+        self.used = used;
+        self.time = time;
+    }
+
+    /// Gives back the rate budget of `amount` DVD burned again right away,
+    /// like a repaid flash mint.
+    pub fn release(&mut self, amount: Decimal) {
+        self.used = self.used.saturating_sub(amount);
+    }
+
+    fn get_used_at(&self, config: &MintLimitConfig, time: Time) -> Decimal {
+        let elapsed = time.secs_since(self.time);
+        if elapsed >= config.window {
+            return Decimal::zero();
+        }
+        self.used
+            .saturating_sub(config.rate_limit * elapsed / config.window)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl MintLimiter {
+    /// The amount of DVD that can still be issued at `unixTimestamp`, ignoring
+    /// the supply ceiling.
+    #[wasm_bindgen(js_name = available)]
+    #[allow(non_snake_case)]
+    pub fn available(&self, config: &MintLimitConfig, unixTimestamp: f64) -> f64 {
+        let used = self.get_used_at(config, Time::from_unix_timestamp(unixTimestamp as u64));
+        config.rate_limit.saturating_sub(used).to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn config() -> MintLimitConfig {
+        MintLimitConfig {
+            supply_ceiling: Decimal::from(1_000u64),
+            rate_limit: Decimal::from(100u64),
+            window: 3600,
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_drains_over_window() {
+        let mut limiter = MintLimiter::new(&clock(0));
+        limiter.record(Decimal::from(100u64), Decimal::zero(), &config(), &clock(0));
+        limiter.record(
+            Decimal::from(50u64),
+            Decimal::zero(),
+            &config(),
+            &clock(1800),
+        );
+        assert_eq!(limiter.used, Decimal::from(100u64));
+        limiter.record(
+            Decimal::from(100u64),
+            Decimal::zero(),
+            &config(),
+            &clock(5400),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DVD mint rate limit reached")]
+    fn test_rate_limit() {
+        let mut limiter = MintLimiter::new(&clock(0));
+        limiter.record(Decimal::from(60u64), Decimal::zero(), &config(), &clock(0));
+        limiter.record(Decimal::from(60u64), Decimal::zero(), &config(), &clock(60));
+    }
+
+    #[test]
+    #[should_panic(expected = "DVD supply ceiling reached")]
+    fn test_supply_ceiling() {
+        let mut limiter = MintLimiter::new(&clock(0));
+        limiter.record(
+            Decimal::from(10u64),
+            Decimal::from(995u64),
+            &config(),
+            &clock(0),
+        );
+    }
+
+    #[test]
+    fn test_release() {
+        let mut limiter = MintLimiter::new(&clock(0));
+        limiter.record(Decimal::from(100u64), Decimal::zero(), &config(), &clock(0));
+        limiter.release(Decimal::from(100u64));
+        limiter.record(Decimal::from(100u64), Decimal::zero(), &config(), &clock(0));
+    }
+}
@@ -1,14 +1,40 @@
 mod config;
 mod dvd_price;
+mod fixed_rate;
 mod flash_mint;
+mod insurance_fund;
+mod ledger;
+mod mint_limiter;
 mod offering;
+mod origination;
+mod recovery;
+mod redemption;
+mod referrers;
+mod savings_exit;
+mod savings_share;
+mod settlement;
 mod sovereign;
 mod stable_dvd;
+mod term_savings;
 mod vesting;
 pub use config::Config;
 pub use dvd_price::DvdPrice;
+pub use fixed_rate::FixedRateConfig;
 pub use flash_mint::FlashMint;
+pub use insurance_fund::InsuranceFund;
+#[cfg(feature = "wasm")]
+pub use ledger::BalanceSheet;
+pub use ledger::Ledger;
+pub use mint_limiter::{MintLimitConfig, MintLimiter};
 pub use offering::{Offering, OfferingConfig};
+pub use origination::OriginationConfig;
+pub use recovery::{Recovery, RecoveryConfig};
+pub use redemption::{Redemption, RedemptionConfig};
+pub use referrers::Referrers;
+pub use savings_exit::{SavingsExit, SavingsExitConfig};
+pub use savings_share::SavingsShare;
+pub use settlement::Settlement;
 pub use sovereign::{Sovereign, SovereignAuth};
 pub use stable_dvd::StableDvd;
+pub use term_savings::TermSavings;
 pub use vesting::Vesting;
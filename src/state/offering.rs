@@ -1,12 +1,12 @@
 use crate::{
     accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
-    finance::{Auction, AuctionConfig, Book, BookConfig, Decimal, InterestRate},
+    finance::{Auction, AuctionConfig, Book, BookConfig, Decimal, InterestRate, Pool},
     oracle::Oracle,
-    state::{DvdPrice, StableDvd},
+    state::{DvdPrice, InsuranceFund, MintLimitConfig, MintLimiter, StableDvd, TermSavings},
     store::Authority,
     token::Token,
     traits::Pod,
-    util::{revert, Time},
+    util::{require, revert, Time},
 };
 use solana_program::clock::Clock;
 
@@ -174,10 +174,13 @@ impl Offering {
         oracle_account: Readonly,
         debt: &mut Book,
         savings: &mut Book,
+        term_savings: &TermSavings,
+        stability_pool: &Pool,
         dvd: &mut Token,
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         stable_dvd: &mut StableDvd,
+        insurance_fund: &mut InsuranceFund,
         dove_oracle: &Oracle,
         offering_config: &OfferingConfig,
         debt_config: &BookConfig,
@@ -190,9 +193,14 @@ impl Offering {
         let dove_price = dove_oracle.query_dvd(oracle_account, dvd_price, dvd_interest_rate, clock);
 
         let assets = debt.get_total(debt_config, clock) + stable_dvd.get_circulating();
-        let liabilities = dvd.get_supply() + savings.get_total(savings_config, clock);
-        if assets > liabilities {
-            let surplus = assets - liabilities;
+        let liabilities = dvd.get_supply()
+            + savings.get_total(savings_config, clock)
+            + term_savings.get_liability(clock)
+            + stability_pool.get_total();
+        let deficit = insurance_fund.get_deficit(assets, liabilities);
+        if deficit.is_zero() {
+            // The insurance fund is held out of DVD offerings
+            let surplus = insurance_fund.get_surplus(assets, liabilities);
             if surplus <= offering_config.surplus_limit {
                 revert("surplus is too low to merit auction");
             }
@@ -202,9 +210,11 @@ impl Offering {
                 auction: Auction::new([debt_price], Time::now(clock)),
             }
         } else {
-            let deficit = liabilities - assets;
-            if deficit <= offering_config.deficit_limit {
-                revert("deficit is too low to merit auction");
+            // The insurance fund is burned against the deficit before any DOVE is minted
+            let covered = insurance_fund.cover(deficit);
+            if deficit - covered <= offering_config.deficit_limit {
+                require(!covered.is_zero(), "deficit is too low to merit auction");
+                return;
             }
             self.state = OfferingState::DoveOffering {
                 qty_remaining: offering_config.dove_offering_size,
@@ -237,7 +247,10 @@ impl Offering {
         requested_base_amount: Decimal,
         clock: &Clock,
         dvd: &mut Token,
+        dvd_deposits: Decimal,
         dove: &mut Token,
+        mint_limiter: &mut MintLimiter,
+        mint_limit_config: &MintLimitConfig,
         authority: Authority,
         auction_config: &AuctionConfig,
         user_account: Signer,
@@ -259,6 +272,12 @@ impl Offering {
                 }
                 let dove_amount = (dvd_amount * dvd_price).min(requested_base_amount);
 
+                mint_limiter.record(
+                    dvd_amount,
+                    dvd.get_supply() + dvd_deposits,
+                    mint_limit_config,
+                    clock,
+                );
                 dvd.mint(
                     dvd_amount,
                     dvd_mint_account,
@@ -0,0 +1,94 @@
+use crate::finance::Decimal;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Configuration for the one-time fee on newly issued DVD.
+///
+/// Borrowers pay the fee on top of their debt, and stability module buyers
+/// receive that much less DVD. When the instruction names a registered
+/// referrer, the referrer is minted `referral_share` of the fee, and the
+/// protocol keeps the rest as surplus.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct OriginationConfig {
+    /// The fee on newly issued DVD. For example: 0.005 (0.5%)
+    fee: Decimal,
+    /// The share of the fee paid to the referrer. For example: 0.25 (25%)
+    referral_share: Decimal,
+}
+
+impl OriginationConfig {
+    pub fn is_valid(&self) -> bool {
+        self.fee < Decimal::one() && self.referral_share <= Decimal::one()
+    }
+
+    pub const fn get_fee_rate(&self) -> Decimal {
+        self.fee
+    }
+
+    /// Returns the fee on `amount`, and the part of it owed to the referrer.
+    pub fn get_fee(&self, amount: Decimal, referred: bool) -> (Decimal, Decimal) {
+        let fee = amount * self.fee;
+        let referral = if referred {
+            fee * self.referral_share
+        } else {
+            Decimal::zero()
+        };
+        (fee, referral)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl OriginationConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(fee: f64, referralShare: f64) -> Result<OriginationConfig, String> {
+        let config = Self {
+            fee: Decimal::from(fee),
+            referral_share: Decimal::from(referralShare),
+        };
+        if !config.is_valid() {
+            return Err("Fee must be below 1 and referral share at most 1".to_string());
+        }
+        Ok(config)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fee(&self) -> f64 {
+        self.fee.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = referralShare)]
+    pub fn referral_share(&self) -> f64 {
+        self.referral_share.to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OriginationConfig {
+        OriginationConfig {
+            fee: Decimal::one() / 100u64,
+            referral_share: Decimal::one() / 4u64,
+        }
+    }
+
+    #[test]
+    fn test_fee_without_referrer() {
+        let (fee, referral) = config().get_fee(Decimal::from(1_000u64), false);
+        assert_eq!(fee, Decimal::from(10u64));
+        assert_eq!(referral, Decimal::zero());
+    }
+
+    #[test]
+    fn test_referral_share() {
+        let (fee, referral) = config().get_fee(Decimal::from(1_000u64), true);
+        assert_eq!(fee, Decimal::from(10u64));
+        assert_eq!(referral, Decimal::from(10u64) / 4u64);
+    }
+}
@@ -0,0 +1,393 @@
+use {
+    crate::{
+        accounts::Readonly,
+        finance::{Book, BookConfig, Decimal, InterestRate},
+        oracle::PricePurpose,
+        state::DvdPrice,
+        store::Collateral,
+        util::{require, List, Time},
+    },
+    solana_program::{clock::Clock, pubkey::Pubkey},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The maximum number of collateral types whose value is tracked, which is
+/// also the most collateral types that can be created.
+const MAX_TRACKED: usize = 16;
+
+/// Configuration for recovery mode.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct RecoveryConfig {
+    /// The aggregate collateral ratio below which the protocol is in recovery
+    /// mode. For example: 1.5 (150%). Zero disables recovery mode.
+    min_collateral_ratio: Decimal,
+    /// The `max_ltv` of vaults in recovery mode.
+    max_ltv: Decimal,
+    /// The `max_ltv` a vault must stay within after a withdrawal in recovery mode.
+    withdraw_max_ltv: Decimal,
+    /// How long, in seconds, the recorded value of a collateral type counts
+    /// toward the aggregate collateral ratio.
+    max_age: u64,
+}
+
+impl RecoveryConfig {
+    pub fn is_valid(&self) -> bool {
+        self.withdraw_max_ltv <= self.max_ltv && self.max_ltv < Decimal::one()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl RecoveryConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        minCollateralRatio: f64,
+        maxLtv: f64,
+        withdrawMaxLtv: f64,
+        maxAge: f64,
+    ) -> Result<RecoveryConfig, String> {
+        let config = Self {
+            min_collateral_ratio: Decimal::from(minCollateralRatio),
+            max_ltv: Decimal::from(maxLtv),
+            withdraw_max_ltv: Decimal::from(withdrawMaxLtv),
+            max_age: maxAge as u64,
+        };
+        if !config.is_valid() {
+            return Err("LTVs must satisfy 0 <= withdraw max LTV <= max LTV < 1".to_string());
+        }
+        Ok(config)
+    }
+
+    #[wasm_bindgen(getter, js_name = minCollateralRatio)]
+    pub fn min_collateral_ratio(&self) -> f64 {
+        self.min_collateral_ratio.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = maxLtv)]
+    pub fn max_ltv(&self) -> f64 {
+        self.max_ltv.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = withdrawMaxLtv)]
+    pub fn withdraw_max_ltv(&self) -> f64 {
+        self.withdraw_max_ltv.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = maxAge)]
+    pub fn max_age(&self) -> f64 {
+        self.max_age as f64
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct TrackedValue {
+    mint: Pubkey,
+    // The value, in DVD, of the collateral held for vaults when last priced.
+    value: Decimal,
+    // The vault debt backed by the collateral when last priced.
+    debt: Decimal,
+    // When the collateral was last priced.
+    time: Time,
+}
+
+/// The aggregate collateral ratio of the protocol, and whether it is in
+/// recovery mode.
+///
+/// Every collateral type is tracked from its creation, up to `MAX_TRACKED`
+/// of them. Its value at the liquidation price and the vault debt it backs
+/// are recorded whenever a command prices it. The ratio is only trusted while
+/// every collateral type was priced within the configured max age, so
+/// commands only price the collateral they touch, and collateral types no
+/// command priced lately must be recorded again. In recovery mode, vaults
+/// get a tighter `max_ltv`, withdrawals a tighter one still, and borrowing
+/// is blocked, as any new debt lowers the ratio further.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Recovery {
+    values: List<TrackedValue, MAX_TRACKED>,
+}
+
+impl Recovery {
+    pub const fn new() -> Self {
+        Self {
+            values: List::new(),
+        }
+    }
+
+    /// Starts tracking a new collateral type, which holds nothing yet.
+    pub fn register(&mut self, mint: &Pubkey, clock: &Clock) {
+        require(self.values.len() < MAX_TRACKED, "Too many collateral types");
+        self.values.push(TrackedValue {
+            mint: *mint,
+            value: Decimal::zero(),
+            debt: Decimal::zero(),
+            time: Time::now(clock),
+        });
+    }
+
+    /// Prices each of `collateral` for liquidations, and records the value of
+    /// the collateral it holds for vaults and the debt it backs.
+    pub fn track(
+        &mut self,
+        collateral: &mut [&mut Collateral],
+        oracle_accounts: &[Readonly],
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        dvd_price: &mut DvdPrice,
+        dvd_interest_rate: &InterestRate,
+        clock: &Clock,
+    ) {
+        let debt_multiplier = debt_book.get_multiplier(debt_config, clock);
+        for (c, &o) in collateral.iter_mut().zip(oracle_accounts) {
+            let price = c.get_price(
+                PricePurpose::Liquidate,
+                o,
+                dvd_price,
+                dvd_interest_rate,
+                clock,
+            );
+            self.set_value(
+                c.get_mint().get_key(),
+                c.get_vault_balance() * price,
+                c.get_backed_debt(debt_multiplier),
+                Time::now(clock),
+            );
+        }
+    }
+
+    fn set_value(&mut self, mint: &Pubkey, value: Decimal, debt: Decimal, time: Time) {
+        let tracked = self
+            .values
+            .iter_mut()
+            .find(|v| &v.mint == mint)
+            .ok_or("Collateral is not tracked by recovery mode")
+            .unwrap();
+        tracked.value = value;
+        tracked.debt = debt;
+        tracked.time = time;
+    }
+
+    pub fn get_collateral_value(&self) -> Decimal {
+        self.values
+            .iter()
+            .fold(Decimal::zero(), |sum, v| sum + v.value)
+    }
+
+    pub fn get_debt(&self) -> Decimal {
+        self.values
+            .iter()
+            .fold(Decimal::zero(), |sum, v| sum + v.debt)
+    }
+
+    fn is_below(&self, config: &RecoveryConfig) -> bool {
+        self.get_collateral_value() < self.get_debt() * config.min_collateral_ratio
+    }
+
+    /// Returns whether the aggregate collateral ratio is below the minimum,
+    /// reverting unless every collateral type was priced within the max age.
+    pub fn is_active(&self, config: &RecoveryConfig, clock: &Clock) -> bool {
+        if config.min_collateral_ratio.is_zero() {
+            return false;
+        }
+        require(
+            self.values
+                .iter()
+                .all(|v| v.time.secs_elapsed(clock) <= config.max_age),
+            "Collateral value is too old for recovery mode",
+        );
+        self.is_below(config)
+    }
+
+    /// Returns the `max_ltv` vaults are held to.
+    pub fn get_max_ltv(&self, max_ltv: Decimal, config: &RecoveryConfig, clock: &Clock) -> Decimal {
+        if self.is_active(config, clock) {
+            max_ltv.min(config.max_ltv)
+        } else {
+            max_ltv
+        }
+    }
+
+    /// Returns the `max_ltv` a vault must stay within after a withdrawal.
+    pub fn get_withdraw_max_ltv(
+        &self,
+        max_ltv: Decimal,
+        config: &RecoveryConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        if self.is_active(config, clock) {
+            max_ltv.min(config.withdraw_max_ltv)
+        } else {
+            max_ltv
+        }
+    }
+
+    /// Reverts if the protocol is in recovery mode after a borrow, which must
+    /// be tracked first.
+    pub fn require_borrow(&self, config: &RecoveryConfig, clock: &Clock) {
+        require(
+            !self.is_active(config, clock),
+            "Borrowing is blocked in recovery mode",
+        );
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Recovery {
+    #[wasm_bindgen(getter, js_name = collateralValue)]
+    pub fn collateral_value(&self) -> f64 {
+        self.get_collateral_value().to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn debt(&self) -> f64 {
+        self.get_debt().to_f64()
+    }
+
+    /// The aggregate collateral ratio as last tracked.
+    #[wasm_bindgen(getter, js_name = collateralRatio)]
+    pub fn collateral_ratio(&self) -> f64 {
+        self.get_collateral_value().to_f64() / self.get_debt().to_f64()
+    }
+
+    /// Whether the aggregate collateral ratio as last tracked is below the
+    /// minimum.
+    #[wasm_bindgen(js_name = isActive)]
+    pub fn is_active_wasm(&self, config: &RecoveryConfig) -> bool {
+        !config.min_collateral_ratio.is_zero() && self.is_below(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn config() -> RecoveryConfig {
+        RecoveryConfig {
+            min_collateral_ratio: Decimal::from(3u64) / 2u64,
+            max_ltv: Decimal::from(6u64) / 10u64,
+            withdraw_max_ltv: Decimal::one() / 2u64,
+            max_age: 60,
+        }
+    }
+
+    fn mint(i: usize) -> Pubkey {
+        Pubkey::new_from_array([i as u8; 32])
+    }
+
+    fn time(unix_timestamp: u64) -> Time {
+        Time::from_unix_timestamp(unix_timestamp)
+    }
+
+    /// Tracks collateral types of the given value and debt at time 0.
+    fn tracked(values: &[(u64, u64)]) -> Recovery {
+        let mut recovery = Recovery::new();
+        for (i, &(value, debt)) in values.iter().enumerate() {
+            recovery.register(&mint(i), &clock(0));
+            recovery.set_value(&mint(i), Decimal::from(value), Decimal::from(debt), time(0));
+        }
+        recovery
+    }
+
+    #[test]
+    fn test_value_is_replaced_per_mint() {
+        let mut recovery = tracked(&[(100, 40), (50, 10)]);
+        recovery.set_value(
+            &mint(0),
+            Decimal::from(20u64),
+            Decimal::from(30u64),
+            time(0),
+        );
+        assert_eq!(recovery.get_collateral_value(), Decimal::from(70u64));
+        assert_eq!(recovery.get_debt(), Decimal::from(40u64));
+    }
+
+    #[test]
+    fn test_active_below_min_collateral_ratio() {
+        assert!(!tracked(&[(100, 50), (50, 50)]).is_active(&config(), &clock(0)));
+        assert!(tracked(&[(100, 50), (50, 51)]).is_active(&config(), &clock(0)));
+    }
+
+    #[test]
+    fn test_ratio_uses_debt_of_tracked_collateral() {
+        // Each collateral type is compared to the debt it backs, so a well
+        // collateralized type offsets an undercollateralized one
+        let recovery = tracked(&[(300, 100), (100, 100)]);
+        assert!(!recovery.is_active(&config(), &clock(0)));
+    }
+
+    #[test]
+    fn test_value_within_max_age() {
+        // Only the collateral type touched is priced again
+        let mut recovery = tracked(&[(100, 10), (100, 10)]);
+        recovery.set_value(
+            &mint(0),
+            Decimal::from(100u64),
+            Decimal::from(10u64),
+            time(50),
+        );
+        assert!(!recovery.is_active(&config(), &clock(60)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Collateral value is too old for recovery mode")]
+    fn test_stale_value() {
+        let mut recovery = tracked(&[(100, 10), (100, 10)]);
+        recovery.set_value(
+            &mint(0),
+            Decimal::from(100u64),
+            Decimal::from(10u64),
+            time(50),
+        );
+        recovery.is_active(&config(), &clock(61));
+    }
+
+    #[test]
+    fn test_disabled_without_min_collateral_ratio() {
+        let mut recovery = tracked(&[(10, 100)]);
+        let config = RecoveryConfig {
+            min_collateral_ratio: Decimal::zero(),
+            ..config()
+        };
+        assert!(!recovery.is_active(&config, &clock(1_000)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many collateral types")]
+    fn test_register_when_full() {
+        let mut recovery = Recovery::new();
+        for i in 0..=MAX_TRACKED {
+            recovery.register(&mint(i), &clock(0));
+        }
+    }
+
+    #[test]
+    fn test_max_ltv_is_tightened() {
+        let max_ltv = Decimal::from(8u64) / 10u64;
+        let healthy = tracked(&[(100, 50)]);
+        let unhealthy = tracked(&[(100, 90)]);
+        assert_eq!(healthy.get_max_ltv(max_ltv, &config(), &clock(0)), max_ltv);
+        assert_eq!(
+            unhealthy.get_max_ltv(max_ltv, &config(), &clock(0)),
+            config().max_ltv
+        );
+        assert_eq!(
+            unhealthy.get_withdraw_max_ltv(max_ltv, &config(), &clock(0)),
+            config().withdraw_max_ltv
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::{Book, BookConfig, Decimal, InterestRate},
+        oracle::PricePurpose,
+        state::{DvdPrice, Ledger},
+        store::{Authority, Collateral, Vault, VaultConfig},
+        token::Token,
+        util::{require, List, Time},
+    },
+    solana_program::{clock::Clock, pubkey::Pubkey},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Configuration for DVD redemptions.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct RedemptionConfig {
+    /// The fee charged once the base rate has decayed. For example: 0.005 (0.5%)
+    fee_floor: Decimal,
+    /// The highest fee a redemption can be charged.
+    max_fee: Decimal,
+    /// The factor the base rate is multiplied by every second.
+    decay_per_sec: Decimal,
+}
+
+impl RedemptionConfig {
+    pub fn is_valid(&self) -> bool {
+        self.fee_floor <= self.max_fee
+            && self.max_fee <= Decimal::one()
+            && self.decay_per_sec <= Decimal::one()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl RedemptionConfig {
+    /// `halfLife` is the number of seconds it takes the base rate to halve.
+    #[wasm_bindgen(constructor)]
+    pub fn new(feeFloor: f64, maxFee: f64, halfLife: f64) -> Result<RedemptionConfig, String> {
+        if !(halfLife > 0.0) || halfLife.is_infinite() {
+            return Err("Half-life must be a positive number".to_string());
+        }
+        let config = Self {
+            fee_floor: Decimal::from(feeFloor),
+            max_fee: Decimal::from(maxFee),
+            decay_per_sec: Decimal::from(0.5f64.powf(1.0 / halfLife)),
+        };
+        if !config.is_valid() {
+            return Err("Fees must satisfy 0 <= fee floor <= max fee <= 1".to_string());
+        }
+        Ok(config)
+    }
+
+    #[wasm_bindgen(getter, js_name = feeFloor)]
+    pub fn fee_floor(&self) -> f64 {
+        self.fee_floor.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = maxFee)]
+    pub fn max_fee(&self) -> f64 {
+        self.max_fee.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = halfLife)]
+    pub fn half_life(&self) -> f64 {
+        0.5f64.ln() / self.decay_per_sec.to_f64().ln()
+    }
+}
+
+/// The maximum number of vaults in the redemption queue.
+const MAX_QUEUED: usize = 32;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct QueuedVault {
+    owner: Pubkey,
+    // The collateral ratio when the vault was last measured.
+    ratio: Decimal,
+}
+
+/// The state of the redemption fee, and the queue of vaults to redeem
+/// against.
+///
+/// Every redemption raises the base rate by the fraction of the DVD supply it
+/// redeems, and the base rate then decays exponentially. The fee is the fee
+/// floor plus the base rate, so that bursts of redemptions get expensive.
+///
+/// The queue holds the vaults with the lowest collateral ratios, sorted by
+/// increasing ratio. Anyone can measure vaults into it, and redemptions must
+/// start from its head after measuring the vaults they redeem, so the owner
+/// of a queued vault can always push a riskier vault ahead of theirs.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Redemption {
+    base_rate: Decimal,
+    time: Time,
+    queue: List<QueuedVault, MAX_QUEUED>,
+}
+
+impl Redemption {
+    pub const fn new(clock: &Clock) -> Self {
+        Self {
+            base_rate: Decimal::zero(),
+            time: Time::now(clock),
+            queue: List::new(),
+        }
+    }
+
+    /// Measures the collateral ratio of `vaults` and moves them to their
+    /// place in the queue, or out of it when they can't be redeemed against.
+    /// `collateral` must price every reserve of the vaults.
+    pub fn refresh(
+        &mut self,
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        dvd_price: &mut DvdPrice,
+        dvd_interest_rate: &InterestRate,
+        max_ltv: Decimal,
+        ledger: &mut Ledger,
+        collateral: &mut [&mut Collateral],
+        oracle_accounts: &[Readonly],
+        vaults: &mut [&mut Vault],
+        clock: &Clock,
+    ) {
+        let prices = get_prices(
+            collateral,
+            oracle_accounts,
+            dvd_price,
+            dvd_interest_rate,
+            clock,
+        );
+        for vault in vaults.iter_mut() {
+            vault.accrue_fixed_term(debt_book, debt_config, clock);
+            vault.accrue_stability_fees(collateral, debt_book, debt_config, ledger, clock);
+            let ratio = vault.get_redemption_ratio(
+                max_ltv,
+                debt_book,
+                debt_config,
+                collateral,
+                &prices,
+                clock,
+            );
+            self.requeue(vault.get_owner(), ratio);
+        }
+    }
+
+    /// Burns up to `requested_amount` DVD and pays out `collateral[0]` for it
+    /// at oracle price, minus the redemption fee, which is seized.
+    ///
+    /// `vaults` are measured into the queue first, and must then be the head
+    /// of the queue, in order. The other entries of `collateral` price the
+    /// rest of their reserves. Vaults without a reserve of `collateral[0]`
+    /// are skipped.
+    /// A vault is either redeemed in full or left with at least the
+    /// minimum debt.
+    pub fn redeem(
+        &mut self,
+        requested_amount: Decimal,
+        max_fee_rate: Decimal,
+
+        program_id: &Pubkey,
+
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        dvd: &mut Token,
+        dvd_price: &mut DvdPrice,
+        dvd_interest_rate: &InterestRate,
+        max_ltv: Decimal,
+        vault_config: &VaultConfig,
+        redemption_config: &RedemptionConfig,
+        ledger: &mut Ledger,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+
+        collateral: &mut [&mut Collateral],
+        oracle_accounts: &[Readonly],
+        vaults: &mut [&mut Vault],
+
+        user_account: Signer,
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_account: TokenAccount<Writable>,
+        dvd_token_program_account: TokenProgramAccount,
+
+        safe_account: TokenAccount<Writable>,
+        collateral_token_account: TokenAccount<Writable>,
+        collateral_mint_account: MintAccount<Readonly>,
+        collateral_token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+        clock: &Clock,
+    ) {
+        let prices = get_prices(
+            collateral,
+            oracle_accounts,
+            dvd_price,
+            dvd_interest_rate,
+            clock,
+        );
+        for (i, vault) in vaults.iter_mut().enumerate() {
+            vault.accrue_fixed_term(debt_book, debt_config, clock);
+            vault.accrue_stability_fees(collateral, debt_book, debt_config, ledger, clock);
+            let ratio = vault.get_redemption_ratio(
+                max_ltv,
+                debt_book,
+                debt_config,
+                collateral,
+                &prices,
+                clock,
+            );
+            require(ratio.is_some(), "Vault can't be redeemed against");
+            self.requeue(vault.get_owner(), ratio);
+            require(
+                self.queue
+                    .iter()
+                    .position(|q| &q.owner == vault.get_owner())
+                    == Some(i),
+                "Vaults are not at the head of the redemption queue",
+            );
+        }
+
+        let mut remaining = requested_amount;
+        let mut collateral_amount = Decimal::zero();
+        for vault in vaults.iter_mut() {
+            require(
+                !remaining.is_zero(),
+                "More vaults than needed for redemption",
+            );
+            let (amount, vault_collateral_amount) = vault.redeem(
+                remaining,
+                collateral[0],
+                prices[0],
+                debt_book,
+                debt_config,
+                vault_config,
+                ledger,
+                savings_book,
+                savings_config,
+                clock,
+            );
+            remaining -= amount;
+            collateral_amount += vault_collateral_amount;
+            let ratio = vault.get_redemption_ratio(
+                max_ltv,
+                debt_book,
+                debt_config,
+                collateral,
+                &prices,
+                clock,
+            );
+            self.requeue(vault.get_owner(), ratio);
+        }
+        let amount = requested_amount - remaining;
+        require(!amount.is_zero(), "Nothing to redeem");
+
+        let fee_rate = self.record(amount, dvd.get_supply(), redemption_config, clock);
+        require(fee_rate <= max_fee_rate, "Redemption fee exceeds maximum");
+        dvd.burn(
+            amount,
+            dvd_mint_account,
+            dvd_account,
+            dvd_token_program_account,
+            user_account,
+        );
+        let fee = collateral_amount * fee_rate;
+        collateral[0].seize(fee);
+        collateral[0].send(
+            collateral_amount - fee,
+            program_id,
+            safe_account,
+            collateral_token_account,
+            collateral_mint_account,
+            collateral_token_program_account,
+            authority,
+        );
+        ledger.record_redemption_fee(amount * fee_rate);
+    }
+
+    /// Moves the vault of `owner` to its place in the queue, or out of it when
+    /// `ratio` is `None`. When the queue is full, the vault with the highest
+    /// ratio is left out.
+    fn requeue(&mut self, owner: &Pubkey, ratio: Option<Decimal>) {
+        if let Some(index) = self.queue.iter().position(|q| &q.owner == owner) {
+            self.queue.remove(index);
+        }
+        let ratio = match ratio {
+            Some(ratio) => ratio,
+            None => return,
+        };
+        let index = self
+            .queue
+            .iter()
+            .position(|q| q.ratio > ratio)
+            .unwrap_or(self.queue.len());
+        if index == MAX_QUEUED {
+            return;
+        }
+        if self.queue.len() == MAX_QUEUED {
+            self.queue.remove(MAX_QUEUED - 1);
+        }
+        self.queue.insert(
+            index,
+            QueuedVault {
+                owner: *owner,
+                ratio,
+            },
+        );
+    }
+
+    /// Records the redemption of `amount` out of a supply of `dvd_supply`,
+    /// returning the fee rate to charge for it.
+    fn record(
+        &mut self,
+        amount: Decimal,
+        dvd_supply: Decimal,
+        config: &RedemptionConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        let time = Time::now(clock);
+        self.base_rate = self.get_base_rate_after(amount, dvd_supply, config, time);
+        self.time = time;
+        (config.fee_floor + self.base_rate).min(config.max_fee)
+    }
+
+    /// Returns the base rate after a redemption of `amount` at `time`.
+    fn get_base_rate_after(
+        &self,
+        amount: Decimal,
+        dvd_supply: Decimal,
+        config: &RedemptionConfig,
+        time: Time,
+    ) -> Decimal {
+        let decayed = self.base_rate * config.decay_per_sec.pow(time.secs_since(self.time));
+        (decayed + amount / dvd_supply).min(Decimal::one())
+    }
+}
+
+fn get_prices(
+    collateral: &mut [&mut Collateral],
+    oracle_accounts: &[Readonly],
+    dvd_price: &mut DvdPrice,
+    dvd_interest_rate: &InterestRate,
+    clock: &Clock,
+) -> Vec<Decimal> {
+    require(
+        !collateral.is_empty() && collateral.len() == oracle_accounts.len(),
+        "Expected one oracle account per collateral account",
+    );
+    collateral
+        .iter_mut()
+        .zip(oracle_accounts)
+        .map(|(c, &o)| c.get_price(PricePurpose::Redeem, o, dvd_price, dvd_interest_rate, clock))
+        .collect()
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Redemption {
+    /// The fee rate a redemption of `amount` would be charged at `unixTimestamp`.
+    #[wasm_bindgen(js_name = projectFeeRate)]
+    #[allow(non_snake_case)]
+    pub fn project_fee_rate(
+        &self,
+        config: &RedemptionConfig,
+        amount: f64,
+        dvdSupply: f64,
+        unixTimestamp: f64,
+    ) -> f64 {
+        let base_rate = self.get_base_rate_after(
+            Decimal::from(amount),
+            Decimal::from(dvdSupply),
+            config,
+            Time::from_unix_timestamp(unixTimestamp as u64),
+        );
+        (config.fee_floor + base_rate).min(config.max_fee).to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = baseRate)]
+    pub fn base_rate(&self) -> f64 {
+        self.base_rate.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = queueLength)]
+    pub fn queue_length(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// The owner of the vault at `index` in the redemption queue.
+    #[wasm_bindgen(js_name = queuedOwnerKey)]
+    pub fn queued_owner_key(&self, index: usize) -> Option<Vec<u8>> {
+        self.queue
+            .iter()
+            .nth(index)
+            .map(|q| q.owner.to_bytes().to_vec())
+    }
+
+    /// The collateral ratio of the vault at `index` when it was last measured.
+    #[wasm_bindgen(js_name = queuedRatio)]
+    pub fn queued_ratio(&self, index: usize) -> Option<f64> {
+        self.queue.iter().nth(index).map(|q| q.ratio.to_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn percent(value: u64) -> Decimal {
+        Decimal::from(value) / 100u64
+    }
+
+    fn config() -> RedemptionConfig {
+        RedemptionConfig {
+            fee_floor: Decimal::one() / 200u64,
+            max_fee: Decimal::one() / 20u64,
+            // Halves every second
+            decay_per_sec: Decimal::one() / 2u64,
+        }
+    }
+
+    #[test]
+    fn test_fee_is_floor_plus_redeemed_fraction() {
+        let mut redemption = Redemption::new(&clock(0));
+        let fee_rate = redemption.record(percent(1), Decimal::one(), &config(), &clock(0));
+        assert_eq!(fee_rate, percent(1) + Decimal::one() / 200u64);
+    }
+
+    #[test]
+    fn test_base_rate_decays() {
+        let mut redemption = Redemption::new(&clock(0));
+        redemption.record(percent(2), Decimal::one(), &config(), &clock(0));
+        let fee_rate = redemption.record(Decimal::zero(), Decimal::one(), &config(), &clock(1));
+        assert_eq!(fee_rate, percent(1) + Decimal::one() / 200u64);
+    }
+
+    fn owners(redemption: &Redemption) -> Vec<Pubkey> {
+        redemption.queue.iter().map(|q| q.owner).collect()
+    }
+
+    #[test]
+    fn test_queue_is_sorted_by_ratio() {
+        let mut redemption = Redemption::new(&clock(0));
+        let [a, b, c] = [(); 3].map(|_| Pubkey::new_unique());
+        redemption.requeue(&a, Some(percent(150)));
+        redemption.requeue(&b, Some(percent(120)));
+        redemption.requeue(&c, Some(percent(200)));
+        assert_eq!(owners(&redemption), vec![b, a, c]);
+
+        // A vault is moved when measured again, and removed without a ratio
+        redemption.requeue(&c, Some(percent(110)));
+        redemption.requeue(&b, None);
+        assert_eq!(owners(&redemption), vec![c, a]);
+    }
+
+    #[test]
+    fn test_full_queue_leaves_out_highest_ratio() {
+        let mut redemption = Redemption::new(&clock(0));
+        for i in 0..MAX_QUEUED as u64 {
+            redemption.requeue(&Pubkey::new_unique(), Some(percent(200 + i)));
+        }
+        let highest = redemption.queue[MAX_QUEUED - 1].owner;
+
+        let riskier = Pubkey::new_unique();
+        redemption.requeue(&riskier, Some(percent(110)));
+        assert_eq!(redemption.queue[0].owner, riskier);
+        assert!(!owners(&redemption).contains(&highest));
+
+        let safer = Pubkey::new_unique();
+        redemption.requeue(&safer, Some(percent(1_000)));
+        assert_eq!(redemption.queue.len(), MAX_QUEUED);
+        assert!(!owners(&redemption).contains(&safer));
+    }
+
+    #[test]
+    fn test_fee_is_capped() {
+        let mut redemption = Redemption::new(&clock(0));
+        let fee_rate = redemption.record(percent(50), Decimal::one(), &config(), &clock(0));
+        assert_eq!(fee_rate, percent(5));
+    }
+}
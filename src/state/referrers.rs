@@ -0,0 +1,107 @@
+use {
+    crate::{
+        accounts::Readonly,
+        state::SovereignAuth,
+        traits::Account,
+        util::{require, List},
+    },
+    solana_program::pubkey::Pubkey,
+};
+
+#[cfg(feature = "wasm")]
+use {crate::util::b2pk, wasm_bindgen::prelude::*};
+
+/// The maximum number of registered referrers.
+const MAX_REFERRERS: usize = 32;
+
+/// The wallets that may receive the referral share of origination fees.
+///
+/// A referral is paid to a token account owned by a registered wallet, and
+/// never to the wallet of the borrower or buyer itself, so users can't refer
+/// themselves to get part of their own fee back.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Referrers {
+    keys: List<Pubkey, MAX_REFERRERS>,
+}
+
+impl Referrers {
+    pub const fn new() -> Self {
+        Self { keys: List::new() }
+    }
+
+    pub fn is_registered(&self, referrer: &Pubkey) -> bool {
+        self.keys.iter().any(|key| key == referrer)
+    }
+
+    /// Requires `referrer` to be registered, and not to be `user`.
+    pub fn require_valid(&self, referrer: &Pubkey, user: &Pubkey) {
+        require(self.is_registered(referrer), "Referrer is not registered");
+        require(referrer != user, "Referrer can't be the user");
+    }
+}
+
+// Authorized functions
+impl Referrers {
+    /// Replaces the set of registered referrers.
+    pub fn set(&mut self, _: SovereignAuth, referrer_accounts: &[Readonly]) {
+        require(
+            referrer_accounts.len() <= MAX_REFERRERS,
+            "Too many referrers",
+        );
+        let mut keys = List::new();
+        for referrer_account in referrer_accounts {
+            let referrer = referrer_account.get_info().key;
+            require(
+                keys.iter().all(|key: &Pubkey| key != referrer),
+                "Duplicate referrer",
+            );
+            keys.push(*referrer);
+        }
+        self.keys = keys;
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Referrers {
+    #[wasm_bindgen(js_name = isRegistered)]
+    pub fn is_registered_wasm(&self, referrer: &[u8]) -> Result<bool, String> {
+        Ok(self.is_registered(&b2pk(referrer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn referrers(keys: &[Pubkey]) -> Referrers {
+        let mut referrers = Referrers::new();
+        for key in keys {
+            referrers.keys.push(*key);
+        }
+        referrers
+    }
+
+    #[test]
+    fn test_registered_referrer() {
+        let referrer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        referrers(&[referrer]).require_valid(&referrer, &user);
+    }
+
+    #[test]
+    #[should_panic(expected = "Referrer is not registered")]
+    fn test_unregistered_referrer() {
+        let user = Pubkey::new_unique();
+        referrers(&[Pubkey::new_unique()]).require_valid(&Pubkey::new_unique(), &user);
+    }
+
+    #[test]
+    #[should_panic(expected = "Referrer can't be the user")]
+    fn test_self_referral() {
+        let user = Pubkey::new_unique();
+        referrers(&[user]).require_valid(&user, &user);
+    }
+}
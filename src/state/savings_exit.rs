@@ -0,0 +1,230 @@
+use {
+    crate::{
+        finance::Decimal,
+        util::{require, Time},
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Configuration for the friction on savings withdrawals.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsExitConfig {
+    /// The maximum amount of DVD withdrawn from savings per epoch, across all
+    /// users. Zero disables the cap.
+    epoch_cap: Decimal,
+    /// The length of an epoch, in seconds. For example: 86400 (1 day)
+    epoch_length: u64,
+    /// The time between requesting a withdrawal and claiming it, in seconds.
+    /// Zero lets users withdraw without a request.
+    cooldown: u64,
+    /// The time after the cooldown during which a request can be claimed, in
+    /// seconds. Past it, the request lapses and must be made again.
+    claim_window: u64,
+}
+
+impl SavingsExitConfig {
+    /// Returns a config without a cap or cooldown.
+    pub const fn none() -> Self {
+        Self {
+            epoch_cap: Decimal::zero(),
+            epoch_length: 0,
+            cooldown: 0,
+            claim_window: 0,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (self.epoch_cap.is_zero() || self.epoch_length > 0)
+            && (self.cooldown == 0 || self.claim_window > 0)
+    }
+
+    pub const fn get_cooldown(&self) -> u64 {
+        self.cooldown
+    }
+
+    pub const fn get_claim_window(&self) -> u64 {
+        self.claim_window
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl SavingsExitConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        epochCap: f64,
+        epochLength: f64,
+        cooldown: f64,
+        claimWindow: f64,
+    ) -> Result<SavingsExitConfig, String> {
+        let config = Self {
+            epoch_cap: Decimal::from(epochCap),
+            epoch_length: epochLength as u64,
+            cooldown: cooldown as u64,
+            claim_window: claimWindow as u64,
+        };
+        if !config.is_valid() {
+            return Err(
+                "Epoch length must be positive when the cap is set, and the claim window when \
+                 the cooldown is set"
+                    .to_string(),
+            );
+        }
+        Ok(config)
+    }
+
+    #[wasm_bindgen(getter, js_name = epochCap)]
+    pub fn epoch_cap(&self) -> f64 {
+        self.epoch_cap.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = epochLength)]
+    pub fn epoch_length(&self) -> f64 {
+        self.epoch_length as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cooldown(&self) -> f64 {
+        self.cooldown as f64
+    }
+
+    #[wasm_bindgen(getter, js_name = claimWindow)]
+    pub fn claim_window(&self) -> f64 {
+        self.claim_window as f64
+    }
+}
+
+/// Caps the DVD withdrawn from savings per epoch.
+///
+/// Epochs are fixed windows of `epoch_length` seconds, so the whole cap
+/// becomes available again at the start of each epoch.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsExit {
+    // The DVD withdrawn within `epoch`.
+    withdrawn: Decimal,
+    epoch: u64,
+}
+
+impl SavingsExit {
+    pub const fn new() -> Self {
+        Self {
+            withdrawn: Decimal::zero(),
+            epoch: 0,
+        }
+    }
+
+    /// Records the withdrawal of `amount` DVD, reverting if it exceeds the
+    /// cap of the current epoch.
+    pub fn record(&mut self, amount: Decimal, config: &SavingsExitConfig, clock: &Clock) {
+        if config.epoch_cap.is_zero() {
+            return;
+        }
+        let epoch = Time::now(clock).get_epoch(config.epoch_length);
+        let withdrawn = self.get_withdrawn_in(epoch) + amount;
+        require(
+            withdrawn <= config.epoch_cap,
+            "Savings withdrawal cap reached for this epoch",
+        );
+        self.withdrawn = withdrawn;
+        self.epoch = epoch;
+    }
+
+    fn get_withdrawn_in(&self, epoch: u64) -> Decimal {
+        if epoch == self.epoch {
+            self.withdrawn
+        } else {
+            Decimal::zero()
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SavingsExit {
+    /// The amount of DVD that can still be withdrawn from savings in the
+    /// epoch of `unixTimestamp`, or infinity if uncapped.
+    #[wasm_bindgen(js_name = available)]
+    #[allow(non_snake_case)]
+    pub fn available(&self, config: &SavingsExitConfig, unixTimestamp: f64) -> f64 {
+        if config.epoch_cap.is_zero() {
+            return f64::INFINITY;
+        }
+        let epoch = Time::from_unix_timestamp(unixTimestamp as u64).get_epoch(config.epoch_length);
+        config
+            .epoch_cap
+            .saturating_sub(self.get_withdrawn_in(epoch))
+            .to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn config() -> SavingsExitConfig {
+        SavingsExitConfig {
+            epoch_cap: Decimal::from(100u64),
+            epoch_length: 3600,
+            cooldown: 0,
+            claim_window: 0,
+        }
+    }
+
+    #[test]
+    fn test_cap_resets_each_epoch() {
+        let mut exit = SavingsExit::new();
+        exit.record(Decimal::from(100u64), &config(), &clock(0));
+        exit.record(Decimal::from(100u64), &config(), &clock(3600));
+        assert_eq!(exit.withdrawn, Decimal::from(100u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Savings withdrawal cap reached for this epoch")]
+    fn test_cap() {
+        let mut exit = SavingsExit::new();
+        exit.record(Decimal::from(60u64), &config(), &clock(0));
+        exit.record(Decimal::from(60u64), &config(), &clock(3599));
+    }
+
+    #[test]
+    fn test_zero_cap_is_uncapped() {
+        let config = SavingsExitConfig {
+            epoch_cap: Decimal::zero(),
+            epoch_length: 0,
+            cooldown: 0,
+            claim_window: 0,
+        };
+        let mut exit = SavingsExit::new();
+        exit.record(Decimal::from(1_000u64), &config, &clock(0));
+        assert!(exit.withdrawn.is_zero());
+    }
+
+    #[test]
+    fn test_cooldown_needs_claim_window() {
+        let config = SavingsExitConfig {
+            cooldown: 3600,
+            ..config()
+        };
+        assert!(!config.is_valid());
+        assert!(SavingsExitConfig {
+            claim_window: 600,
+            ..config
+        }
+        .is_valid());
+    }
+}
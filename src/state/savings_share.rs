@@ -0,0 +1,262 @@
+use {
+    crate::{
+        accounts::{MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::{Book, BookConfig, Decimal},
+        state::{SavingsExit, SavingsExitConfig, SovereignAuth},
+        store::Authority,
+        token::Token,
+        util::require,
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use {crate::util::Time, wasm_bindgen::prelude::*};
+
+/// sDVD, a transferable share of a pooled savings position.
+///
+/// Deposits are pooled in the savings `Book` as a single principal, so each
+/// sDVD is worth a growing amount of DVD as the Book multiplier grows. As sDVD
+/// holders can't be tracked to claim them, the pooled principal is excluded
+/// from DOVE rewards, which go to the other savers instead.
+///
+/// Withdrawals count towards the savings withdrawal cap. While the savings
+/// cooldown is set, sDVD can't be withdrawn directly: the holder requests
+/// the exit instead, which burns the shares and moves the DVD they are worth
+/// into the holder's savings account as a withdrawal request, claimable like
+/// any other.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SavingsShare {
+    enabled: bool,
+    token: Token,
+    // The pooled savings, in terms of the Book multiplier.
+    principal: Decimal,
+}
+
+impl SavingsShare {
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            token: Token::zero(),
+            principal: Decimal::zero(),
+        }
+    }
+
+    pub fn enable(&mut self, _: SovereignAuth, token: Token) {
+        require(!self.enabled, "Savings shares are already enabled");
+        self.enabled = true;
+        self.token = token;
+    }
+
+    pub fn get_token(&self) -> Option<&Token> {
+        self.enabled.then_some(&self.token)
+    }
+
+    fn get_total(
+        &self,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        self.principal * savings_book.get_multiplier(savings_config, clock)
+    }
+
+    /// Returns the DVD redeemable for one sDVD.
+    pub fn get_exchange_rate(
+        &mut self,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        clock: &Clock,
+    ) -> Decimal {
+        require(self.enabled, "Savings shares are not enabled");
+        let total = self.get_total(savings_book, savings_config, clock);
+        exchange_rate(total, self.token.get_supply())
+    }
+
+    /// Burns `amount` DVD into the pooled savings, minting sDVD at the
+    /// current exchange rate.
+    pub fn deposit(
+        &mut self,
+        amount: Decimal,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        dvd: &mut Token,
+
+        user_account: Signer,
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_token_account: TokenAccount<Writable>,
+        dvd_token_program_account: TokenProgramAccount,
+        sdvd_mint_account: MintAccount<Writable>,
+        sdvd_token_account: TokenAccount<Writable>,
+        sdvd_token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+        clock: &Clock,
+    ) {
+        require(!amount.is_zero(), "Amount must be greater than zero");
+        let shares = amount / self.get_exchange_rate(savings_book, savings_config, clock);
+        dvd.burn(
+            amount,
+            dvd_mint_account,
+            dvd_token_account,
+            dvd_token_program_account,
+            user_account,
+        );
+        self.principal += savings_book.add_unrewarded(amount, savings_config, clock);
+        self.token.mint(
+            shares,
+            sdvd_mint_account,
+            sdvd_token_account,
+            authority,
+            sdvd_token_program_account,
+        );
+    }
+
+    /// Burns `shares` sDVD, minting the DVD they are worth. Only while the
+    /// savings cooldown is unset, otherwise see `redeem`.
+    pub fn withdraw(
+        &mut self,
+        shares: Decimal,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        savings_exit: &mut SavingsExit,
+        savings_exit_config: &SavingsExitConfig,
+        dvd: &mut Token,
+
+        user_account: Signer,
+        sdvd_mint_account: MintAccount<Writable>,
+        sdvd_token_account: TokenAccount<Writable>,
+        sdvd_token_program_account: TokenProgramAccount,
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_token_account: TokenAccount<Writable>,
+        dvd_token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+        clock: &Clock,
+    ) {
+        require(
+            savings_exit_config.get_cooldown() == 0,
+            "sDVD withdrawals must be requested while the savings cooldown is set",
+        );
+        let amount = self.redeem(
+            shares,
+            savings_book,
+            savings_config,
+            user_account,
+            sdvd_mint_account,
+            sdvd_token_account,
+            sdvd_token_program_account,
+            clock,
+        );
+        savings_exit.record(amount, savings_exit_config, clock);
+        dvd.mint(
+            amount,
+            dvd_mint_account,
+            dvd_token_account,
+            authority,
+            dvd_token_program_account,
+        );
+    }
+
+    /// Burns `shares` sDVD and takes the DVD they are worth out of the pooled
+    /// savings, returning it. While the savings cooldown is set, the caller
+    /// moves it into the holder's savings as a withdrawal request.
+    pub fn redeem(
+        &mut self,
+        shares: Decimal,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+
+        user_account: Signer,
+        sdvd_mint_account: MintAccount<Writable>,
+        sdvd_token_account: TokenAccount<Writable>,
+        sdvd_token_program_account: TokenProgramAccount,
+
+        clock: &Clock,
+    ) -> Decimal {
+        require(!shares.is_zero(), "Amount must be greater than zero");
+        let rate = self.get_exchange_rate(savings_book, savings_config, clock);
+        // The last shares out take whatever rounding left in the pool
+        let amount = (shares * rate).min(self.get_total(savings_book, savings_config, clock));
+        self.token.burn(
+            shares,
+            sdvd_mint_account,
+            sdvd_token_account,
+            sdvd_token_program_account,
+            user_account,
+        );
+        let principal = savings_book.subtract_unrewarded(amount, savings_config, clock);
+        self.principal = self.principal.saturating_sub(principal);
+        amount
+    }
+}
+
+fn exchange_rate(total: Decimal, supply: Decimal) -> Decimal {
+    if supply.is_zero() {
+        Decimal::one()
+    } else {
+        total / supply
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl SavingsShare {
+    #[wasm_bindgen(getter)]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn token(&self) -> Token {
+        self.token
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn principal(&self) -> f64 {
+        self.principal.to_f64()
+    }
+
+    /// Returns the DVD redeemable for one sDVD at `unixTimestamp`.
+    #[wasm_bindgen(js_name = "projectExchangeRate")]
+    pub fn project_exchange_rate(
+        &self,
+        savingsBook: &Book,
+        savingsConfig: &BookConfig,
+        unixTimestamp: f64,
+    ) -> f64 {
+        let time = Time::from_unix_timestamp(unixTimestamp as u64);
+        let total = self.principal * savingsBook.project_multiplier(savingsConfig, time);
+        exchange_rate(total, self.token.get_supply()).to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_rate_starts_at_one() {
+        assert_eq!(
+            exchange_rate(Decimal::zero(), Decimal::zero()),
+            Decimal::one()
+        );
+        assert_eq!(
+            exchange_rate(Decimal::from(5u64), Decimal::zero()),
+            Decimal::one()
+        );
+    }
+
+    #[test]
+    fn test_exchange_rate_grows_with_savings() {
+        let supply = Decimal::from(100u64);
+        assert_eq!(exchange_rate(Decimal::from(100u64), supply), Decimal::one());
+        assert_eq!(
+            exchange_rate(Decimal::from(150u64), supply),
+            Decimal::from(3u64) / 2u64
+        );
+    }
+}
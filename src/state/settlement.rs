@@ -0,0 +1,222 @@
+use {
+    crate::{
+        finance::Decimal,
+        state::{SavingsExitConfig, SovereignAuth},
+        util::{require, Time},
+    },
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The state of global settlement, the protocol's emergency shutdown.
+///
+/// Once settled, collateral prices are frozen one `Collateral` at a time,
+/// vault debt is covered with collateral at those prices, and DVD holders
+/// redeem DVD for a pro-rata share of the seized collateral and of the
+/// stablecoins held by `Stability` modules.
+///
+/// Vaults have `wait` seconds to be settled. After that the outstanding DVD
+/// claims are fixed, and the assets are split between them. Savings stop
+/// accruing at settlement, so what savers withdraw matches their claims.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Settlement {
+    settled: bool,
+    time: Time,
+    wait: u64,
+    // The DVD claims on the assets, fixed once the wait is over.
+    claims: Decimal,
+    // The part of `claims` burned by DVD holders so far.
+    packed: Decimal,
+}
+
+impl Settlement {
+    pub const fn new() -> Self {
+        Self {
+            settled: false,
+            time: Time::from_unix_timestamp(0),
+            wait: 0,
+            claims: Decimal::zero(),
+            packed: Decimal::zero(),
+        }
+    }
+
+    pub fn settle(&mut self, _: SovereignAuth, wait: u64, clock: &Clock) {
+        self.require_live();
+        self.settled = true;
+        self.time = Time::now(clock);
+        self.wait = wait;
+    }
+
+    pub fn require_live(&self) {
+        require(!self.settled, "Protocol is settled");
+    }
+
+    pub fn require_settled(&self) {
+        require(self.settled, "Protocol is not settled");
+    }
+
+    /// Requires the protocol to be settled with the wait not over, while the
+    /// assets backing the claims can still change.
+    pub fn require_waiting(&self, clock: &Clock) {
+        self.require_settled();
+        require(
+            self.time.secs_elapsed(clock) < self.wait,
+            "Settlement wait is over",
+        );
+    }
+
+    /// Returns the clock savings accrue at, which stops at settlement so
+    /// that savings withdrawn later match their share of the claims.
+    pub fn get_savings_clock(&self, clock: &Clock) -> Clock {
+        if !self.settled {
+            return clock.clone();
+        }
+        Clock {
+            unix_timestamp: self.time.to_unix_timestamp() as i64,
+            ..clock.clone()
+        }
+    }
+
+    /// Returns the friction on savings withdrawals, lifted once settled so
+    /// savers can withdraw their DVD to pack it.
+    pub fn get_savings_exit_config(&self, config: &SavingsExitConfig) -> SavingsExitConfig {
+        if self.settled {
+            SavingsExitConfig::none()
+        } else {
+            *config
+        }
+    }
+
+    /// Returns the DVD claims on the assets, fixing them the first time this
+    /// is called after the wait. DVD held in savings, term deposits and the
+    /// stability pool has a claim too, and can be withdrawn to be packed later.
+    pub fn get_claims(
+        &mut self,
+        dvd_supply: Decimal,
+        savings_total: Decimal,
+        term_savings_total: Decimal,
+        stability_pool_total: Decimal,
+        clock: &Clock,
+    ) -> Decimal {
+        self.require_settled();
+        require(
+            self.time.secs_elapsed(clock) >= self.wait,
+            "Settlement wait is not over",
+        );
+        if self.claims.is_zero() {
+            self.claims = dvd_supply + savings_total + term_savings_total + stability_pool_total;
+        }
+        self.claims
+    }
+
+    pub fn get_fixed_claims(&self) -> Decimal {
+        require(!self.claims.is_zero(), "Claims are not fixed yet");
+        self.claims
+    }
+
+    /// Records `amount` DVD burned against the claims.
+    pub fn pack(&mut self, amount: Decimal) {
+        require(
+            self.packed + amount <= self.claims,
+            "Amount exceeds the remaining claims",
+        );
+        self.packed += amount;
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Settlement {
+    #[wasm_bindgen(getter)]
+    pub fn settled(&self) -> bool {
+        self.settled
+    }
+
+    #[wasm_bindgen(getter, js_name = unixTimestamp)]
+    pub fn unix_timestamp(&self) -> f64 {
+        self.time.to_unix_timestamp() as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn wait(&self) -> f64 {
+        self.wait as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn claims(&self) -> f64 {
+        self.claims.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn packed(&self) -> f64 {
+        self.packed.to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn settled() -> Settlement {
+        Settlement {
+            settled: true,
+            time: Time::from_unix_timestamp(100),
+            wait: 50,
+            ..Settlement::new()
+        }
+    }
+
+    #[test]
+    fn test_waiting() {
+        settled().require_waiting(&clock(149));
+    }
+
+    #[test]
+    #[should_panic(expected = "Settlement wait is over")]
+    fn test_waiting_after_wait() {
+        settled().require_waiting(&clock(150));
+    }
+
+    #[test]
+    fn test_savings_clock_stops_at_settlement() {
+        assert_eq!(
+            Settlement::new()
+                .get_savings_clock(&clock(200))
+                .unix_timestamp,
+            200
+        );
+        assert_eq!(settled().get_savings_clock(&clock(200)).unix_timestamp, 100);
+    }
+
+    #[test]
+    fn test_claims_are_fixed_once() {
+        let mut settlement = settled();
+        let claims = settlement.get_claims(
+            Decimal::from(1u64),
+            Decimal::from(2u64),
+            Decimal::from(3u64),
+            Decimal::from(4u64),
+            &clock(150),
+        );
+        assert!(claims == Decimal::from(10u64));
+        let claims = settlement.get_claims(
+            Decimal::from(5u64),
+            Decimal::zero(),
+            Decimal::zero(),
+            Decimal::zero(),
+            &clock(200),
+        );
+        assert!(claims == Decimal::from(10u64));
+    }
+}
@@ -0,0 +1,204 @@
+use {
+    crate::{finance::Decimal, util::Time},
+    solana_program::clock::Clock,
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The totals of all term deposits.
+///
+/// Each deposit's fixed interest is accrued here in a straight line from its
+/// start to its payout at maturity, which is never below the compounded
+/// interest. Deposits past maturity keep accruing until withdrawn, but the
+/// interest is capped to the sum of the payouts, so the liability stays
+/// conservative.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct TermSavings {
+    // The DVD locked in term deposits.
+    total: Decimal,
+    // The DVD the deposits pay out at maturity.
+    payout: Decimal,
+    // The interest accrued per second on all deposits.
+    interest_per_sec: Decimal,
+    // The interest accrued on deposits not yet withdrawn, as of `last_update`.
+    accrued: Decimal,
+    // The interest paid out on matured deposits.
+    interest: Decimal,
+    last_update: Time,
+}
+
+impl TermSavings {
+    pub const fn new() -> Self {
+        Self {
+            total: Decimal::zero(),
+            payout: Decimal::zero(),
+            interest_per_sec: Decimal::zero(),
+            accrued: Decimal::zero(),
+            interest: Decimal::zero(),
+            last_update: Time::from_unix_timestamp(0),
+        }
+    }
+
+    fn get_accrued_at(&self, time: Time) -> Decimal {
+        let secs = time.secs_since(self.last_update);
+        (self.accrued + self.interest_per_sec * secs).min(self.payout - self.total)
+    }
+
+    fn accrue(&mut self, clock: &Clock) {
+        let now = Time::now(clock);
+        self.accrued = self.get_accrued_at(now);
+        self.last_update = now;
+    }
+
+    /// Records a deposit of `amount`, paying out `payout` after `term` seconds.
+    pub fn record_deposit(&mut self, amount: Decimal, payout: Decimal, term: u64, clock: &Clock) {
+        self.accrue(clock);
+        self.total += amount;
+        self.payout += payout;
+        self.interest_per_sec += (payout - amount) / term;
+    }
+
+    /// Records the withdrawal of a deposit started at `start`, along with the
+    /// `interest` paid on it.
+    pub fn record_withdrawal(
+        &mut self,
+        amount: Decimal,
+        payout: Decimal,
+        term: u64,
+        start: Time,
+        interest: Decimal,
+        clock: &Clock,
+    ) {
+        self.accrue(clock);
+        let interest_per_sec = (payout - amount) / term;
+        let accrued = interest_per_sec * Time::now(clock).secs_since(start);
+        self.interest_per_sec = self.interest_per_sec.saturating_sub(interest_per_sec);
+        self.total -= amount;
+        self.payout -= payout;
+        self.accrued = self
+            .accrued
+            .saturating_sub(accrued)
+            .min(self.payout - self.total);
+        self.interest += interest;
+    }
+
+    /// Returns the deposits along with the interest accrued on them.
+    pub fn get_liability(&self, clock: &Clock) -> Decimal {
+        self.total + self.get_accrued_at(Time::now(clock))
+    }
+
+    #[cfg(feature = "wasm")]
+    pub fn project_liability_at(&self, time: Time) -> Decimal {
+        self.total + self.get_accrued_at(time)
+    }
+
+    #[cfg(feature = "wasm")]
+    pub const fn get_interest(&self) -> Decimal {
+        self.interest
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl TermSavings {
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> f64 {
+        self.total.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn payout(&self) -> f64 {
+        self.payout.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn interest(&self) -> f64 {
+        self.interest.to_f64()
+    }
+
+    #[wasm_bindgen(js_name = projectLiability)]
+    #[allow(non_snake_case)]
+    pub fn project_liability(&self, unixTimestamp: f64) -> f64 {
+        self.project_liability_at(Time::from_unix_timestamp(unixTimestamp as u64))
+            .to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_interest_accrues_until_payout() {
+        let mut term_savings = TermSavings::new();
+        let amount = Decimal::from(100u64);
+        let payout = Decimal::from(110u64);
+        term_savings.record_deposit(amount, payout, 100, &clock(0));
+
+        assert_eq!(term_savings.get_liability(&clock(0)), amount);
+        assert_eq!(
+            term_savings.get_liability(&clock(50)),
+            Decimal::from(105u64)
+        );
+        assert_eq!(term_savings.get_liability(&clock(100)), payout);
+        assert_eq!(term_savings.get_liability(&clock(1_000)), payout);
+    }
+
+    #[test]
+    fn test_early_withdrawal_forfeits_accrued_interest() {
+        let mut term_savings = TermSavings::new();
+        let amount = Decimal::from(100u64);
+        let payout = Decimal::from(110u64);
+        term_savings.record_deposit(amount, payout, 100, &clock(0));
+        term_savings.record_deposit(amount, payout, 100, &clock(50));
+
+        term_savings.record_withdrawal(
+            amount,
+            payout,
+            100,
+            Time::from_unix_timestamp(0),
+            Decimal::zero(),
+            &clock(60),
+        );
+        assert_eq!(term_savings.total, amount);
+        assert_eq!(
+            term_savings.get_liability(&clock(60)),
+            Decimal::from(101u64)
+        );
+        assert_eq!(term_savings.get_liability(&clock(150)), payout);
+    }
+
+    #[test]
+    fn test_withdrawal_past_maturity() {
+        let mut term_savings = TermSavings::new();
+        let amount = Decimal::from(100u64);
+        let payout = Decimal::from(110u64);
+        term_savings.record_deposit(amount, payout, 100, &clock(0));
+        term_savings.record_deposit(amount, payout, 100, &clock(100));
+
+        // The first deposit accrued past its payout until it was withdrawn
+        term_savings.record_withdrawal(
+            amount,
+            payout,
+            100,
+            Time::from_unix_timestamp(0),
+            payout - amount,
+            &clock(150),
+        );
+        assert_eq!(
+            term_savings.get_liability(&clock(150)),
+            Decimal::from(105u64)
+        );
+        assert_eq!(term_savings.get_liability(&clock(200)), payout);
+    }
+}
@@ -3,13 +3,13 @@ use {crate::util::b2pk, wasm_bindgen::prelude::wasm_bindgen};
 use {
     crate::{
         accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
-        finance::{Decimal, InterestRate},
-        oracle::Oracle,
+        finance::{Decimal, InterestRate, StabilityFee},
+        oracle::{CircuitBreaker, Oracle, PriceMode, PricePurpose, Twap},
         state::{DvdPrice, SovereignAuth},
         store::Authority,
-        token::{Mint, Safe},
+        token::{Mint, Safe, SafeAudit},
         traits::{Account, Pod, Store, StoreAuth},
-        util::{revert, Expect},
+        util::{require, revert, Expect, Time},
     },
     solana_program::{clock::Clock, pubkey::Pubkey},
 };
@@ -24,8 +24,25 @@ pub struct Collateral {
     mint_decimals: u8,
     mint: Mint,
     deposited: Decimal,
+    // The part of `deposited` seized from vaults whose auction failed.
+    seized: Decimal,
+    // The price frozen by global settlement, in DVD. Zero until then.
+    settlement_price: Decimal,
+    // Seized collateral paid out per DVD claim after global settlement.
+    settlement_rate: Decimal,
     max_deposit: Decimal,
+    // Charged on vault debt backed by this collateral, on top of the debt interest rate.
+    stability_fee: StabilityFee,
+    // The principal of the vault debt backed by this collateral, in terms of
+    // the debt `Book` multiplier, as of each vault's last update.
+    backed_debt: Decimal,
     oracle: Oracle,
+    price_mode: PriceMode,
+    // The averaging window of the TWAP, in seconds.
+    twap_window: u64,
+    twap: Twap,
+    circuit_breaker: CircuitBreaker,
+    safe_audit: SafeAudit,
 }
 
 pub struct CollateralParams {
@@ -70,8 +87,18 @@ impl Store for Collateral {
             &mut 0,
         );
         self.deposited = Decimal::zero();
+        self.seized = Decimal::zero();
+        self.settlement_price = Decimal::zero();
+        self.settlement_rate = Decimal::zero();
         self.max_deposit = Decimal::zero();
+        self.stability_fee = StabilityFee::new();
+        self.backed_debt = Decimal::zero();
         self.oracle = Oracle::zero();
+        self.price_mode = PriceMode::Spot;
+        self.twap_window = 0;
+        self.twap = Twap::new();
+        self.circuit_breaker = CircuitBreaker::new();
+        self.safe_audit = SafeAudit::new();
     }
     fn is_initialized(&self) -> bool {
         self.initialized
@@ -87,9 +114,113 @@ impl Collateral {
         _ = auth;
         self.max_deposit = new_max_deposit;
     }
+    pub fn set_stability_fee(&mut self, auth: StoreAuth<Self>, rate: InterestRate, clock: &Clock) {
+        _ = auth;
+        self.stability_fee.set_rate(rate, clock);
+    }
     pub fn set_oracle(&mut self, auth: StoreAuth<Self>, oracle: Oracle) {
         _ = auth;
+        require(oracle.is_enabled(), "Oracle kind is not enabled");
         self.oracle = oracle;
+        self.twap = Twap::new();
+        self.circuit_breaker.reset();
+    }
+    pub fn set_price_mode(
+        &mut self,
+        auth: StoreAuth<Self>,
+        price_mode: PriceMode,
+        twap_window: u64,
+    ) {
+        _ = auth;
+        require(
+            price_mode == PriceMode::Spot || twap_window > 0,
+            "TWAP window must be positive",
+        );
+        self.price_mode = price_mode;
+        self.twap_window = twap_window;
+    }
+    pub fn set_circuit_breaker(
+        &mut self,
+        auth: StoreAuth<Self>,
+        max_price_move: Decimal,
+        window: u64,
+        blocks_liquidation: bool,
+    ) {
+        _ = auth;
+        require(
+            max_price_move.is_zero() || window > 0,
+            "Circuit breaker window must be positive",
+        );
+        self.circuit_breaker
+            .configure(max_price_move, window, blocks_liquidation);
+    }
+    /// Accepts the oracle price that tripped the circuit breaker.
+    pub fn clear_circuit_breaker(&mut self, auth: StoreAuth<Self>) {
+        _ = auth;
+        require(
+            self.circuit_breaker.is_tripped(),
+            "Circuit breaker is not tripped",
+        );
+        self.circuit_breaker.clear();
+    }
+    /// Sends collateral seized from failed auctions to a treasury account.
+    pub fn withdraw_seized(
+        &mut self,
+        auth: StoreAuth<Self>,
+        amount: Decimal,
+
+        program_id: &Pubkey,
+
+        safe_account: TokenAccount<Writable>,
+        treasury_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        _ = auth;
+        require(!amount.is_zero(), "Amount must be greater than zero");
+        require(amount <= self.seized, "Not enough seized collateral");
+        self.send(
+            amount,
+            program_id,
+            safe_account,
+            treasury_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+        self.seized -= amount;
+    }
+    /// Sends the tokens in the safe that are not part of `deposited`, such as
+    /// tokens transferred to it by mistake.
+    pub fn sweep(
+        &mut self,
+        auth: StoreAuth<Self>,
+
+        program_id: &Pubkey,
+
+        safe_account: TokenAccount<Writable>,
+        destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        _ = auth;
+        require(
+            mint_account.get_info().key == self.mint.get_key(),
+            "Invalid mint account",
+        );
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.mint);
+        safe.sweep(
+            self.deposited,
+            self.mint_decimals,
+            destination_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
     }
 }
 
@@ -98,10 +229,78 @@ impl Collateral {
     pub const fn get_mint(&self) -> &Mint {
         &self.mint
     }
+    /// Returns the stability fee multiplier, which vaults compare to their
+    /// snapshot to charge the fee accrued since.
+    pub fn get_stability_fee_multiplier(&mut self, clock: &Clock) -> Decimal {
+        self.stability_fee.get_multiplier(clock)
+    }
+    /// Returns the amount held for vaults, which excludes seized collateral.
+    pub fn get_vault_balance(&self) -> Decimal {
+        self.deposited - self.seized
+    }
+    /// Replaces `old` with `new` in the principal of the vault debt backed by
+    /// this collateral.
+    pub fn record_backed_debt(&mut self, old: Decimal, new: Decimal) {
+        self.backed_debt = (self.backed_debt + new).saturating_sub(old);
+    }
+    /// Returns the vault debt backed by this collateral, given the multiplier
+    /// of the debt `Book`.
+    pub fn get_backed_debt(&self, debt_multiplier: Decimal) -> Decimal {
+        self.backed_debt * debt_multiplier
+    }
+    /// Records the current oracle price in the TWAP and circuit breaker.
+    ///
+    /// A tripped circuit breaker only persists through this, as commands
+    /// that it blocks are reverted.
+    pub fn record_price(&mut self, oracle_account: Readonly, clock: &Clock) {
+        self.observe_price(oracle_account, clock);
+    }
+    /// Records the token balance of the safe next to the deposited amount.
+    pub fn reconcile(
+        &mut self,
+        program_id: &Pubkey,
+        safe_account: TokenAccount<Writable>,
+        clock: &Clock,
+    ) {
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.mint);
+        self.safe_audit.record(
+            Decimal::from_token_amount(safe.get_balance(), self.mint_decimals),
+            self.deposited,
+            Time::now(clock),
+        );
+    }
+    /// Freezes the price of the collateral for global settlement.
+    pub fn settle(
+        &mut self,
+        oracle_account: Readonly,
+        dvd_price: &mut DvdPrice,
+        dvd_interest_rate: &InterestRate,
+        clock: &Clock,
+    ) {
+        require(
+            self.settlement_price.is_zero(),
+            "Collateral is already settled",
+        );
+        self.settlement_price = self.get_price(
+            PricePurpose::Liquidate,
+            oracle_account,
+            dvd_price,
+            dvd_interest_rate,
+            clock,
+        );
+    }
+    pub fn get_settlement_price(&self) -> Decimal {
+        require(
+            !self.settlement_price.is_zero(),
+            "Collateral price is not frozen",
+        );
+        self.settlement_price
+    }
 }
 
 // For internal use only
 impl Collateral {
+    /// Moves tokens into the safe and returns the amount actually received.
     pub fn receive(
         &mut self,
         amount: Decimal,
@@ -111,24 +310,69 @@ impl Collateral {
         user_account: Signer,
         source_token_account: TokenAccount<Writable>,
         program_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
-    ) {
-        let token_account = Safe::get(
+    ) -> Decimal {
+        require(
+            mint_account.get_info().key == self.mint.get_key(),
+            "Invalid mint account",
+        );
+        let token_account = Safe::get::<Self>(
             program_id,
             program_token_account,
             self.safe_nonce,
             &self.mint,
         );
-        let new_deposited = self.deposited + amount;
+        let received = token_account.receive(
+            amount.to_token_amount(self.mint_decimals),
+            self.mint_decimals,
+            user_account,
+            source_token_account,
+            mint_account,
+            token_program_account,
+        );
+        let received = Decimal::from_token_amount(received, self.mint_decimals);
+        let new_deposited = self.deposited + received;
         if new_deposited > self.max_deposit {
             revert("Deposit limit for collateral type reached");
         }
         self.deposited = new_deposited;
-        token_account.receive(
-            amount.to_token_amount(self.mint_decimals),
-            user_account,
-            source_token_account,
+        received
+    }
+    /// Takes over collateral left in a vault. The tokens stay in the safe.
+    pub fn seize(&mut self, amount: Decimal) {
+        self.seized += amount;
+    }
+    /// Pays out the share of the seized collateral owed to `claim` out of
+    /// all `claims` on it after global settlement.
+    pub fn pay_settlement(
+        &mut self,
+        claim: Decimal,
+        claims: Decimal,
+
+        program_id: &Pubkey,
+
+        safe_account: TokenAccount<Writable>,
+        destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        if self.settlement_rate.is_zero() {
+            self.settlement_rate = self.seized / claims;
+        }
+        let amount = (claim * self.settlement_rate).min(self.seized);
+        require(!amount.is_zero(), "No collateral to pay out");
+        self.seized -= amount;
+        self.send(
+            amount,
+            program_id,
+            safe_account,
+            destination_token_account,
+            mint_account,
             token_program_account,
+            authority,
         );
     }
     pub fn send(
@@ -139,28 +383,59 @@ impl Collateral {
 
         safe_account: TokenAccount<Writable>,
         destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
 
         authority: Authority,
     ) {
-        let token_account = Safe::get(program_id, safe_account, self.safe_nonce, &self.mint);
+        require(
+            mint_account.get_info().key == self.mint.get_key(),
+            "Invalid mint account",
+        );
+        let token_account =
+            Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.mint);
         token_account.send(
             amount.to_token_amount(self.mint_decimals),
+            self.mint_decimals,
             destination_token_account,
+            mint_account,
             token_program_account,
             authority,
         );
         self.deposited -= amount;
     }
+    /// Returns the price, in DVD, of the collateral according to its price mode.
+    /// The current oracle price is recorded in the TWAP along the way.
+    /// Reverts if the circuit breaker blocks prices for `purpose`.
     pub fn get_price(
-        &self,
+        &mut self,
+        purpose: PricePurpose,
         oracle_account: Readonly,
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         clock: &Clock,
     ) -> Decimal {
-        self.oracle
-            .query_dvd(oracle_account, dvd_price, dvd_interest_rate, clock)
+        let spot = self.observe_price(oracle_account, clock);
+        require(
+            !self.circuit_breaker.blocks(purpose),
+            "Collateral price circuit breaker is tripped",
+        );
+        let twap = self
+            .twap
+            .average(self.twap_window, Time::now(clock))
+            .unwrap_or(spot);
+        self.price_mode.select(purpose, spot, twap) / dvd_price.get(dvd_interest_rate, clock)
+    }
+    /// Returns the current oracle price, in USD, after passing it through the
+    /// circuit breaker. Only accepted prices are recorded in the TWAP.
+    fn observe_price(&mut self, oracle_account: Readonly, clock: &Clock) -> Decimal {
+        let time = Time::now(clock);
+        let price = self.oracle.query_usd(oracle_account, clock);
+        self.circuit_breaker.update(price, time);
+        if !self.circuit_breaker.is_tripped() {
+            self.twap.record(price, time, self.twap_window);
+        }
+        price
     }
 }
 
@@ -200,10 +475,62 @@ impl Collateral {
         self.deposited.to_f64()
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn seized(&self) -> f64 {
+        self.seized.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = settlementPrice)]
+    pub fn settlement_price(&self) -> f64 {
+        self.settlement_price.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = settlementRate)]
+    pub fn settlement_rate(&self) -> f64 {
+        self.settlement_rate.to_f64()
+    }
+
     #[wasm_bindgen(getter, js_name = maxDeposit)]
     pub fn max_deposit(&self) -> f64 {
         self.max_deposit.to_f64()
     }
+
+    #[wasm_bindgen(getter, js_name = stabilityFee)]
+    pub fn stability_fee(&self) -> StabilityFee {
+        self.stability_fee
+    }
+
+    /// The principal of the vault debt backed by this collateral, to be
+    /// multiplied by the multiplier of the debt `Book`.
+    #[wasm_bindgen(getter, js_name = backedDebtPrincipal)]
+    pub fn backed_debt_principal(&self) -> f64 {
+        self.backed_debt.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = priceMode)]
+    pub fn price_mode(&self) -> PriceMode {
+        self.price_mode
+    }
+
+    #[wasm_bindgen(getter, js_name = twapWindow)]
+    pub fn twap_window(&self) -> f64 {
+        self.twap_window as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn twap(&self) -> Twap {
+        self.twap
+    }
+
+    #[wasm_bindgen(getter, js_name = circuitBreaker)]
+    pub fn circuit_breaker(&self) -> CircuitBreaker {
+        self.circuit_breaker
+    }
+
+    #[wasm_bindgen(getter, js_name = safeAudit)]
+    pub fn safe_audit(&self) -> SafeAudit {
+        self.safe_audit
+    }
 }
 
 unsafe impl Pod for Collateral {
@@ -1,7 +1,10 @@
 mod authority;
 mod collateral;
 mod savings;
+mod settlement_claim;
 mod stability;
+mod stability_pool;
+mod term_deposit;
 mod vault;
 mod world;
 
@@ -9,7 +12,10 @@ pub use {
     authority::Authority,
     collateral::{Collateral, CollateralParams},
     savings::Savings,
+    settlement_claim::SettlementClaim,
     stability::{Stability, StabilityParams},
+    stability_pool::StabilityPool,
+    term_deposit::TermDeposit,
     vault::{Vault, VaultConfig},
     world::{World, WorldParams},
 };
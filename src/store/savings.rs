@@ -1,6 +1,7 @@
 use crate::{
     finance::{Book, BookConfig},
-    util::revert,
+    state::{SavingsExit, SavingsExitConfig},
+    util::{require, revert, Time},
 };
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -16,6 +17,11 @@ use {
     solana_program::{clock::Clock, pubkey::Pubkey},
 };
 
+/// A user's savings.
+///
+/// When the savings withdrawal cooldown is set, withdrawals must be requested
+/// first, and claimed within the claim window after the cooldown. The savings
+/// keep accruing interest until the request is claimed.
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -23,6 +29,10 @@ pub struct Savings {
     initialized: bool,
     nonce: u8,
     page: Page,
+    // The DVD requested for withdrawal and not yet withdrawn.
+    pending: Decimal,
+    claimable_at: Time,
+    lapses_at: Time,
 }
 
 impl Store for Savings {
@@ -51,6 +61,9 @@ impl Store for Savings {
         self.initialized = true;
         self.nonce = nonce;
         self.page = Page::new();
+        self.pending = Decimal::zero();
+        self.claimable_at = Time::from_unix_timestamp(0);
+        self.lapses_at = Time::from_unix_timestamp(0);
     }
 
     fn is_initialized(&self) -> bool {
@@ -89,12 +102,50 @@ impl Savings {
         self.page.add(amount, savings_book, savings_config, clock);
     }
 
+    /// Requests the withdrawal of `amount` DVD, claimable once the cooldown
+    /// is over and until the claim window closes. A new request replaces the
+    /// previous one.
+    pub fn request_withdrawal(
+        &mut self,
+        auth: StoreAuth<Self>,
+        amount: Decimal,
+        savings_exit_config: &SavingsExitConfig,
+        clock: &Clock,
+    ) {
+        _ = auth;
+        require(!amount.is_zero(), "Amount must be greater than zero");
+        self.pending = amount;
+        self.claimable_at = Time::now(clock).add_secs(savings_exit_config.get_cooldown());
+        self.lapses_at = self
+            .claimable_at
+            .add_secs(savings_exit_config.get_claim_window());
+    }
+
+    /// Adds `amount` DVD, taken out of the pooled sDVD savings, to the savings
+    /// and to the requested withdrawal, restarting the cooldown. A lapsed
+    /// request is replaced.
+    pub fn deposit_requested(
+        &mut self,
+        auth: StoreAuth<Self>,
+        amount: Decimal,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        savings_exit_config: &SavingsExitConfig,
+        clock: &Clock,
+    ) {
+        self.page.add(amount, savings_book, savings_config, clock);
+        let pending = self.get_live_pending(clock) + amount;
+        self.request_withdrawal(auth, pending, savings_exit_config, clock);
+    }
+
     pub fn withdraw(
         &mut self,
         auth: StoreAuth<Self>,
         requested_amount: Decimal,
         savings_book: &mut Book,
         savings_config: &BookConfig,
+        savings_exit: &mut SavingsExit,
+        savings_exit_config: &SavingsExitConfig,
         dvd: &mut Token,
 
         dvd_mint_account: MintAccount<Writable>,
@@ -105,10 +156,15 @@ impl Savings {
         clock: &Clock,
     ) {
         _ = auth;
-        let amount = requested_amount.min(self.page.get_total(savings_book, savings_config, clock));
+        let mut amount =
+            requested_amount.min(self.page.get_total(savings_book, savings_config, clock));
+        if savings_exit_config.get_cooldown() > 0 {
+            amount = self.take_pending(amount, clock);
+        }
         if amount.is_zero() {
             revert("Insufficient savings");
         }
+        savings_exit.record(amount, savings_exit_config, clock);
         self.page
             .subtract(amount, savings_book, savings_config, clock);
         dvd.mint(
@@ -148,6 +204,29 @@ impl Savings {
     }
 }
 
+impl Savings {
+    /// Takes up to `amount` off the requested withdrawal, reverting unless
+    /// it is claimable.
+    fn take_pending(&mut self, amount: Decimal, clock: &Clock) -> Decimal {
+        require(!self.pending.is_zero(), "No withdrawal requested");
+        let now = Time::now(clock);
+        require(now >= self.claimable_at, "Withdrawal is still cooling down");
+        require(now < self.lapses_at, "Withdrawal request has lapsed");
+        let amount = amount.min(self.pending);
+        self.pending -= amount;
+        amount
+    }
+
+    /// Returns the requested withdrawal, or zero once it has lapsed.
+    fn get_live_pending(&self, clock: &Clock) -> Decimal {
+        if Time::now(clock) < self.lapses_at {
+            self.pending
+        } else {
+            Decimal::zero()
+        }
+    }
+}
+
 // External functions
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
@@ -175,8 +254,85 @@ impl Savings {
     pub fn page(&self) -> Page {
         self.page
     }
+
+    /// The DVD requested for withdrawal and not yet withdrawn.
+    #[wasm_bindgen(getter)]
+    pub fn pending(&self) -> f64 {
+        self.pending.to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = claimableAt)]
+    pub fn claimable_at(&self) -> f64 {
+        self.claimable_at.to_unix_timestamp() as f64
+    }
+
+    #[wasm_bindgen(getter, js_name = lapsesAt)]
+    pub fn lapses_at(&self) -> f64 {
+        self.lapses_at.to_unix_timestamp() as f64
+    }
 }
 
 unsafe impl Pod for Savings {
     const NAME: &'static str = "Savings";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn savings(pending: u64) -> Savings {
+        Savings {
+            pending: Decimal::from(pending),
+            claimable_at: Time::from_unix_timestamp(100),
+            lapses_at: Time::from_unix_timestamp(200),
+            ..Savings::zero()
+        }
+    }
+
+    #[test]
+    fn test_take_pending() {
+        let mut savings = savings(100);
+        assert_eq!(
+            savings.take_pending(Decimal::from(60u64), &clock(100)),
+            Decimal::from(60u64)
+        );
+        assert_eq!(
+            savings.take_pending(Decimal::from(60u64), &clock(199)),
+            Decimal::from(40u64)
+        );
+        assert!(savings.pending.is_zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "Withdrawal is still cooling down")]
+    fn test_take_pending_during_cooldown() {
+        savings(100).take_pending(Decimal::from(60u64), &clock(99));
+    }
+
+    #[test]
+    #[should_panic(expected = "Withdrawal request has lapsed")]
+    fn test_take_pending_after_claim_window() {
+        savings(100).take_pending(Decimal::from(60u64), &clock(200));
+    }
+
+    #[test]
+    #[should_panic(expected = "No withdrawal requested")]
+    fn test_take_pending_without_request() {
+        savings(0).take_pending(Decimal::from(60u64), &clock(150));
+    }
+
+    #[test]
+    fn test_live_pending() {
+        let savings = savings(100);
+        assert_eq!(savings.get_live_pending(&clock(50)), Decimal::from(100u64));
+        assert_eq!(savings.get_live_pending(&clock(199)), Decimal::from(100u64));
+        assert!(savings.get_live_pending(&clock(200)).is_zero());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{state::Settlement, util::List};
+#[cfg(feature = "wasm")]
+use {crate::util::b2pk, wasm_bindgen::prelude::wasm_bindgen};
+
+use {
+    crate::{
+        accounts::{MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        token::Token,
+        traits::{Account, Pod, Store, StoreAuth},
+    },
+    solana_program::pubkey::Pubkey,
+};
+
+/// The maximum number of assets a claim can be cashed in for.
+const MAX_CASHED: usize = 16;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Cashed {
+    mint: Pubkey,
+    // The part of `packed` already cashed in for this asset.
+    amount: Decimal,
+}
+
+/// A DVD holder's claim on the assets left after global settlement.
+///
+/// DVD is packed into the claim first, which burns it. The claim is then
+/// cashed in for each collateral and stablecoin separately.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SettlementClaim {
+    initialized: bool,
+    nonce: u8,
+    packed: Decimal,
+    cashed: List<Cashed, MAX_CASHED>,
+}
+
+impl Store for SettlementClaim {
+    const SEED_PREFIX: &'static str = "settlement_claim";
+
+    type Params = ();
+    type DeriveData<'a> = &'a Pubkey;
+    type CreateData<'a> = Signer;
+    type LoadData = ();
+    type LoadAuthData = Signer;
+
+    fn get_seeds_on_derive<'a>(derive_data: Self::DeriveData<'a>) -> [&'a [u8]; 2] {
+        [derive_data.as_bytes(), &[]]
+    }
+    fn get_seeds_on_create<'a>(user_account: Signer) -> [&'a [u8]; 2] {
+        [user_account.get_info().key.as_bytes(), &[]]
+    }
+    fn get_seeds_on_load(&self, _: ()) -> [&'static [u8]; 2] {
+        unimplemented!("SettlementClaim does not have an unprivileged mode")
+    }
+    fn get_seeds_on_load_auth(&self, user_account: Signer) -> [&'static [u8]; 2] {
+        [user_account.get_info().key.as_bytes(), &[]]
+    }
+
+    fn initialize(&mut self, nonce: u8, _: Self::Params) {
+        self.initialized = true;
+        self.nonce = nonce;
+        self.packed = Decimal::zero();
+        self.cashed = List::new();
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    fn get_nonce(&self) -> u8 {
+        self.nonce
+    }
+}
+
+// Authorized functions
+impl SettlementClaim {
+    /// Burns `amount` DVD into the claim.
+    pub fn pack(
+        &mut self,
+        auth: StoreAuth<Self>,
+        amount: Decimal,
+        dvd: &mut Token,
+        settlement: &mut Settlement,
+
+        user_account: Signer,
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_token_account: TokenAccount<Writable>,
+        token_program_account: TokenProgramAccount,
+    ) {
+        _ = auth;
+        settlement.pack(amount);
+        dvd.burn(
+            amount,
+            dvd_mint_account,
+            dvd_token_account,
+            token_program_account,
+            user_account,
+        );
+        self.packed += amount;
+    }
+
+    /// Marks the claim as cashed in for `mint`, and returns the amount of DVD
+    /// packed since it last was.
+    pub fn cash(&mut self, auth: StoreAuth<Self>, mint: &Pubkey) -> Decimal {
+        _ = auth;
+        let index = match self.cashed.iter().position(|c| &c.mint == mint) {
+            Some(index) => index,
+            None => {
+                self.cashed.push(Cashed {
+                    mint: *mint,
+                    amount: Decimal::zero(),
+                });
+                self.cashed.len() - 1
+            }
+        };
+        let cashed = &mut self.cashed[index];
+        let amount = self.packed - cashed.amount;
+        cashed.amount = self.packed;
+        amount
+    }
+}
+
+// External functions
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SettlementClaim {
+    #[wasm_bindgen(js_name = deriveKey)]
+    #[allow(non_snake_case)]
+    pub fn derive_key(programKey: &[u8], userKey: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(Self::derive_address_raw(
+            &b2pk(programKey)?,
+            &b2pk(userKey)?,
+        ))
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<SettlementClaim, String> {
+        Self::try_cast_from(bytes)
+            .map(|x| *x)
+            .map_err(|e| format!("Invalid settlement claim: {}", e))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn packed(&self) -> f64 {
+        self.packed.to_f64()
+    }
+
+    /// The amount of packed DVD not yet cashed in for `mintKey`.
+    #[wasm_bindgen(js_name = uncashed)]
+    #[allow(non_snake_case)]
+    pub fn uncashed(&self, mintKey: &[u8]) -> Result<f64, String> {
+        let mint = b2pk(mintKey)?;
+        let cashed = self
+            .cashed
+            .iter()
+            .find(|c| c.mint == mint)
+            .map_or(Decimal::zero(), |c| c.amount);
+        Ok((self.packed - cashed).to_f64())
+    }
+}
+
+unsafe impl Pod for SettlementClaim {
+    const NAME: &'static str = "SettlementClaim";
+}
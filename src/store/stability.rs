@@ -4,11 +4,14 @@ use {
     crate::{
         accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::{Decimal, InterestRate},
-        state::{DvdPrice, SovereignAuth, StableDvd},
+        state::{
+            DvdPrice, Ledger, MintLimitConfig, MintLimiter, OriginationConfig, SovereignAuth,
+            StableDvd,
+        },
         store::Authority,
-        token::{Mint, Safe, Token},
+        token::{Mint, Safe, SafeAudit, Token},
         traits::{Account, Pod, Store, StoreAuth},
-        util::{revert, Expect},
+        util::{require, revert, Expect, Time},
     },
     solana_program::{clock::Clock, pubkey::Pubkey},
 };
@@ -29,6 +32,9 @@ pub struct Stability {
     stable_mint: Mint,
     max_deposit: Decimal,
     deposited: Decimal,
+    safe_audit: SafeAudit,
+    // Stablecoins paid out per DVD claim after global settlement.
+    settlement_rate: Decimal,
 }
 
 pub struct StabilityParams {
@@ -74,6 +80,8 @@ impl Store for Stability {
         );
         self.max_deposit = Decimal::zero();
         self.deposited = Decimal::zero();
+        self.safe_audit = SafeAudit::new();
+        self.settlement_rate = Decimal::zero();
     }
     fn is_initialized(&self) -> bool {
         self.initialized
@@ -88,29 +96,135 @@ impl Stability {
     pub fn update_max_deposit(&mut self, _: StoreAuth<Self>, mint_limit: Decimal) {
         self.max_deposit = mint_limit;
     }
+    /// Sends the tokens in the safe that are not part of `deposited`, such as
+    /// tokens transferred to it by mistake.
+    pub fn sweep(
+        &mut self,
+        auth: StoreAuth<Self>,
+
+        program_id: &Pubkey,
+
+        safe_account: TokenAccount<Writable>,
+        destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        _ = auth;
+        require(
+            mint_account.get_info().key == self.stable_mint.get_key(),
+            "Invalid mint account",
+        );
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.stable_mint);
+        safe.sweep(
+            self.deposited,
+            self.mint_decimals,
+            destination_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+    }
 }
 
 impl Stability {
+    /// Records the token balance of the safe next to the deposited amount.
+    pub fn reconcile(
+        &mut self,
+        program_id: &Pubkey,
+        safe_account: TokenAccount<Writable>,
+        clock: &Clock,
+    ) {
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.stable_mint);
+        self.safe_audit.record(
+            Decimal::from_token_amount(safe.get_balance(), self.mint_decimals),
+            self.deposited,
+            Time::now(clock),
+        );
+    }
+
+    /// Pays out the share of the deposited stablecoins owed to `claim` out of
+    /// all `claims` on them after global settlement.
+    pub fn pay_settlement(
+        &mut self,
+        claim: Decimal,
+        claims: Decimal,
+
+        program_id: &Pubkey,
+
+        safe_account: TokenAccount<Writable>,
+        destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        require(
+            mint_account.get_info().key == self.stable_mint.get_key(),
+            "Invalid stable mint account",
+        );
+        if self.settlement_rate.is_zero() {
+            self.settlement_rate = self.deposited / claims;
+        }
+        let amount = (claim * self.settlement_rate).min(self.deposited);
+        require(!amount.is_zero(), "No stablecoins to pay out");
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.stable_mint);
+        safe.send(
+            amount.to_token_amount(self.mint_decimals),
+            self.mint_decimals,
+            destination_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+        self.deposited -= amount;
+    }
+
     pub fn buy_dvd(
         &mut self,
         deposit_amount: Decimal,
         dvd: &mut Token,
+        dvd_deposits: Decimal,
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         stable_dvd: &mut StableDvd,
+        origination_config: &OriginationConfig,
+        ledger: &mut Ledger,
+        mint_limiter: &mut MintLimiter,
+        mint_limit_config: &MintLimitConfig,
         program_id: &Pubkey,
         authority: Authority,
         user_account: Signer,
         safe_account: TokenAccount<Writable>,
         stable_token_account: TokenAccount<Writable>,
         dvd_token_account: TokenAccount<Writable>,
+        referrer_account: Option<TokenAccount<Writable>>,
         dvd_mint_account: MintAccount<Writable>,
+        stable_mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
+        stable_token_program_account: TokenProgramAccount,
         clock: &Clock,
     ) {
         if deposit_amount.is_zero() {
             return;
         }
+        require(
+            stable_mint_account.get_info().key == self.stable_mint.get_key(),
+            "Invalid stable mint account",
+        );
+
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.stable_mint);
+        let received = (safe).receive(
+            deposit_amount.to_token_amount(self.mint_decimals),
+            self.mint_decimals,
+            user_account,
+            stable_token_account,
+            stable_mint_account,
+            stable_token_program_account,
+        );
+        // Transfer fees are withheld from the deposit, only pay for what arrived
+        let deposit_amount = Decimal::from_token_amount(received, self.mint_decimals);
         let new_deposited = self.deposited + deposit_amount;
         if new_deposited > self.max_deposit {
             revert("mint limit exceeded");
@@ -118,20 +232,32 @@ impl Stability {
 
         let dvd_amount = deposit_amount / dvd_price.get(dvd_interest_rate, clock);
 
-        let safe = Safe::get(program_id, safe_account, self.safe_nonce, &self.stable_mint);
-        (safe).receive(
-            deposit_amount.to_token_amount(self.mint_decimals),
-            user_account,
-            stable_token_account,
-            token_program_account,
+        // The fee is kept out of the bought DVD, the stablecoins back all of it
+        let (fee, referral) = origination_config.get_fee(dvd_amount, referrer_account.is_some());
+        mint_limiter.record(
+            dvd_amount - fee + referral,
+            dvd.get_supply() + dvd_deposits,
+            mint_limit_config,
+            clock,
         );
         dvd.mint(
-            dvd_amount,
+            dvd_amount - fee,
             dvd_mint_account,
             dvd_token_account,
             authority,
             token_program_account,
         );
+        if let Some(referrer_account) = referrer_account {
+            dvd.mint(
+                referral,
+                dvd_mint_account,
+                referrer_account,
+                authority,
+                token_program_account,
+            );
+        }
+        ledger.record_stability_fee(fee - referral);
+        ledger.record_realized_revenue(fee - referral);
         stable_dvd.increase(dvd_amount);
 
         self.deposited = new_deposited;
@@ -151,24 +277,32 @@ impl Stability {
         stable_token_account: TokenAccount<Writable>,
         dvd_token_account: TokenAccount<Writable>,
         dvd_mint_account: MintAccount<Writable>,
+        stable_mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
+        stable_token_program_account: TokenProgramAccount,
         clock: &Clock,
     ) {
         if dvd_amount.is_zero() {
             return;
         }
+        require(
+            stable_mint_account.get_info().key == self.stable_mint.get_key(),
+            "Invalid stable mint account",
+        );
         let deposit_amount = dvd_amount / dvd_price.get(dvd_interest_rate, clock);
         if deposit_amount > self.deposited {
             revert("not enough stablecoin available to swap to");
         }
         let new_deposited = self.deposited - deposit_amount;
 
-        let safe = Safe::get(program_id, safe_account, self.safe_nonce, &self.stable_mint);
+        let safe = Safe::get::<Self>(program_id, safe_account, self.safe_nonce, &self.stable_mint);
 
         (safe).send(
             deposit_amount.to_token_amount(self.mint_decimals),
+            self.mint_decimals,
             stable_token_account,
-            token_program_account,
+            stable_mint_account,
+            stable_token_program_account,
             authority,
         );
         dvd.burn(
@@ -219,6 +353,16 @@ impl Stability {
     pub fn mint_key(&self) -> Vec<u8> {
         self.stable_mint.get_key().to_bytes().to_vec()
     }
+
+    #[wasm_bindgen(getter, js_name = safeAudit)]
+    pub fn safe_audit(&self) -> SafeAudit {
+        self.safe_audit
+    }
+
+    #[wasm_bindgen(getter, js_name = settlementRate)]
+    pub fn settlement_rate(&self) -> f64 {
+        self.settlement_rate.to_f64()
+    }
 }
 
 unsafe impl Pod for Stability {
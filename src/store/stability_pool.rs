@@ -0,0 +1,173 @@
+use crate::finance::{Pool, Share};
+#[cfg(feature = "wasm")]
+use {crate::util::b2pk, wasm_bindgen::prelude::wasm_bindgen};
+
+use {
+    crate::{
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::Decimal,
+        store::{Authority, Collateral},
+        token::Token,
+        traits::{Account, Pod, Store, StoreAuth},
+    },
+    solana_program::pubkey::Pubkey,
+};
+
+/// A user's deposit in the stability pool.
+///
+/// Deposited DVD is burned, and cancels the debt of liquidated vaults in
+/// exchange for their collateral, which the user can then claim.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct StabilityPool {
+    initialized: bool,
+    nonce: u8,
+    share: Share,
+}
+
+impl Store for StabilityPool {
+    const SEED_PREFIX: &'static str = "stability_pool";
+
+    type Params = ();
+    type DeriveData<'a> = &'a Pubkey;
+    type CreateData<'a> = Signer;
+    type LoadData = ();
+    type LoadAuthData = Signer;
+
+    fn get_seeds_on_derive<'a>(derive_data: Self::DeriveData<'a>) -> [&'a [u8]; 2] {
+        [derive_data.as_bytes(), &[]]
+    }
+    fn get_seeds_on_create<'a>(user_account: Signer) -> [&'a [u8]; 2] {
+        [user_account.get_info().key.as_bytes(), &[]]
+    }
+    fn get_seeds_on_load(&self, _: ()) -> [&'static [u8]; 2] {
+        unimplemented!("StabilityPool does not have an unprivileged mode")
+    }
+    fn get_seeds_on_load_auth(&self, user_account: Signer) -> [&'static [u8]; 2] {
+        [user_account.get_info().key.as_bytes(), &[]]
+    }
+
+    fn initialize(&mut self, nonce: u8, _: Self::Params) {
+        self.initialized = true;
+        self.nonce = nonce;
+        self.share = Share::new();
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    fn get_nonce(&self) -> u8 {
+        self.nonce
+    }
+}
+
+// Authorized functions
+impl StabilityPool {
+    pub fn deposit(
+        &mut self,
+        auth: StoreAuth<Self>,
+        amount: Decimal,
+        dvd: &mut Token,
+        pool: &mut Pool,
+
+        user_account: Signer,
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_token_account: TokenAccount<Writable>,
+        token_program_account: TokenProgramAccount,
+    ) {
+        _ = auth;
+        dvd.burn(
+            amount,
+            dvd_mint_account,
+            dvd_token_account,
+            token_program_account,
+            user_account,
+        );
+        self.share.add(amount, pool);
+    }
+
+    pub fn withdraw(
+        &mut self,
+        auth: StoreAuth<Self>,
+        requested_amount: Decimal,
+        dvd: &mut Token,
+        pool: &mut Pool,
+
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_account: TokenAccount<Writable>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        _ = auth;
+        let amount = self.share.subtract(requested_amount, pool);
+        dvd.mint(
+            amount,
+            dvd_mint_account,
+            dvd_account,
+            authority,
+            token_program_account,
+        );
+    }
+
+    /// Sends the collateral gained from liquidations in one collateral type.
+    pub fn claim_collateral(
+        &mut self,
+        auth: StoreAuth<Self>,
+        pool: &mut Pool,
+
+        program_id: &Pubkey,
+        collateral: &mut Collateral,
+
+        safe_account: TokenAccount<Writable>,
+        destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+    ) {
+        _ = auth;
+        let amount = self.share.claim(collateral.get_mint().get_key(), pool);
+        collateral.send(
+            amount,
+            program_id,
+            safe_account,
+            destination_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+    }
+}
+
+// External functions
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl StabilityPool {
+    #[wasm_bindgen(js_name = deriveKey)]
+    #[allow(non_snake_case)]
+    pub fn derive_key(programKey: &[u8], userKey: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(Self::derive_address_raw(
+            &b2pk(programKey)?,
+            &b2pk(userKey)?,
+        ))
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<StabilityPool, String> {
+        Self::try_cast_from(bytes)
+            .map(|x| *x)
+            .map_err(|e| format!("Invalid stability pool: {}", e))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn share(&self) -> Share {
+        self.share
+    }
+}
+
+unsafe impl Pod for StabilityPool {
+    const NAME: &'static str = "StabilityPool";
+}
@@ -0,0 +1,211 @@
+#[cfg(feature = "wasm")]
+use {crate::util::b2pk, wasm_bindgen::prelude::wasm_bindgen};
+use {
+    crate::{
+        accounts::{MintAccount, Signer, TokenAccount, TokenProgramAccount, Writable},
+        finance::{Decimal, InterestRate},
+        state::{FixedRateConfig, TermSavings},
+        store::Authority,
+        token::Token,
+        traits::{Account, Pod, Store, StoreAuth},
+        util::{require, Time},
+    },
+    solana_program::{clock::Clock, pubkey::Pubkey},
+};
+
+/// DVD locked until maturity at the term deposit rate offered when it was
+/// deposited.
+///
+/// Each deposit is its own account, indexed per owner. Withdrawing before
+/// maturity returns the deposit without interest. Once withdrawn, the
+/// account can be deposited into again.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct TermDeposit {
+    initialized: bool,
+    nonce: u8,
+    index: [u8; 8],
+    amount: Decimal,
+    rate: InterestRate,
+    start: Time,
+    maturity: Time,
+}
+
+impl Store for TermDeposit {
+    const SEED_PREFIX: &'static str = "term_deposit";
+
+    type Params = u64;
+    type DeriveData<'a> = (&'a Pubkey, &'a [u8; 8]);
+    type CreateData<'a> = (Signer, &'a [u8; 8]);
+    type LoadData = ();
+    type LoadAuthData = Signer;
+
+    fn get_seeds_on_derive<'a>((user_key, index): Self::DeriveData<'a>) -> [&'a [u8]; 2] {
+        [user_key.as_bytes(), index]
+    }
+    fn get_seeds_on_create<'a>((user_account, index): Self::CreateData<'a>) -> [&'a [u8]; 2] {
+        [user_account.get_info().key.as_bytes(), index]
+    }
+    fn get_seeds_on_load(&self, _: ()) -> [&'static [u8]; 2] {
+        unimplemented!("TermDeposit does not have an unprivileged mode")
+    }
+    fn get_seeds_on_load_auth(&self, user_account: Signer) -> [&[u8]; 2] {
+        [user_account.get_info().key.as_bytes(), &self.index]
+    }
+
+    fn initialize(&mut self, nonce: u8, index: u64) {
+        self.initialized = true;
+        self.nonce = nonce;
+        self.index = index.to_le_bytes();
+        self.amount = Decimal::zero();
+        self.rate = InterestRate::zero();
+        self.start = Time::from_unix_timestamp(0);
+        self.maturity = Time::from_unix_timestamp(0);
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    fn get_nonce(&self) -> u8 {
+        self.nonce
+    }
+}
+
+// Authorized functions
+impl TermDeposit {
+    /// Locks `amount` DVD for `term` seconds, at a rate of at least `min_rate`.
+    pub fn deposit(
+        &mut self,
+        auth: StoreAuth<Self>,
+        amount: Decimal,
+        term: u64,
+        min_rate: InterestRate,
+        dvd: &mut Token,
+        term_savings: &mut TermSavings,
+        term_savings_config: &FixedRateConfig,
+
+        user_account: Signer,
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_token_account: TokenAccount<Writable>,
+        token_program_account: TokenProgramAccount,
+        clock: &Clock,
+    ) {
+        _ = auth;
+        require(self.amount.is_zero(), "Term deposit is not empty");
+        require(!amount.is_zero(), "Amount must be greater than zero");
+        let (rate, maturity) = term_savings_config.get_deposit_terms(term, min_rate, clock);
+        dvd.burn(
+            amount,
+            dvd_mint_account,
+            dvd_token_account,
+            token_program_account,
+            user_account,
+        );
+        self.amount = amount;
+        self.rate = rate;
+        self.start = Time::now(clock);
+        self.maturity = maturity;
+        term_savings.record_deposit(amount, self.get_payout(), term, clock);
+    }
+
+    /// Pays out the deposit, with interest once it has matured.
+    pub fn withdraw(
+        &mut self,
+        auth: StoreAuth<Self>,
+        dvd: &mut Token,
+        term_savings: &mut TermSavings,
+
+        dvd_mint_account: MintAccount<Writable>,
+        dvd_account: TokenAccount<Writable>,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+        clock: &Clock,
+    ) {
+        _ = auth;
+        require(!self.amount.is_zero(), "Term deposit is empty");
+        let payout = self.get_payout();
+        let amount = self.amount.take();
+        let interest = if Time::now(clock) >= self.maturity {
+            payout - amount
+        } else {
+            Decimal::zero()
+        };
+        term_savings.record_withdrawal(
+            amount,
+            payout,
+            self.maturity.secs_since(self.start),
+            self.start,
+            interest,
+            clock,
+        );
+        dvd.mint(
+            amount + interest,
+            dvd_mint_account,
+            dvd_account,
+            authority,
+            token_program_account,
+        );
+    }
+}
+
+impl TermDeposit {
+    /// Returns the DVD paid out at maturity.
+    fn get_payout(&self) -> Decimal {
+        let term = self.maturity.secs_since(self.start);
+        self.amount * self.rate.get_accumulation_factor(term)
+    }
+}
+
+// External functions
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl TermDeposit {
+    #[wasm_bindgen(js_name = deriveKey)]
+    #[allow(non_snake_case)]
+    pub fn derive_key(programKey: &[u8], userKey: &[u8], index: u64) -> Result<Vec<u8>, String> {
+        Ok(Self::derive_address_raw(
+            &b2pk(programKey)?,
+            (&b2pk(userKey)?, &index.to_le_bytes()),
+        ))
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<TermDeposit, String> {
+        Self::try_cast_from(bytes)
+            .map(|x| *x)
+            .map_err(|e| format!("Invalid term deposit: {}", e))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> f64 {
+        u64::from_le_bytes(self.index) as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn amount(&self) -> f64 {
+        self.amount.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rate(&self) -> InterestRate {
+        self.rate
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn maturity(&self) -> f64 {
+        self.maturity.to_unix_timestamp() as f64
+    }
+
+    /// The DVD paid out at maturity.
+    #[wasm_bindgen(getter)]
+    pub fn payout(&self) -> f64 {
+        self.get_payout().to_f64()
+    }
+}
+
+unsafe impl Pod for TermDeposit {
+    const NAME: &'static str = "TermDeposit";
+}
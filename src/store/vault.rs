@@ -1,6 +1,7 @@
 use crate::{
-    finance::{AuctionConfig, Book, BookConfig, InterestRate},
-    state::DvdPrice,
+    finance::{AuctionConfig, Book, BookConfig, FixedTerm, InterestRate, Pool},
+    oracle::PricePurpose,
+    state::{DvdPrice, InsuranceFund, Ledger, MintLimitConfig, MintLimiter, OriginationConfig},
     token::Token,
     util::{revert, Time},
 };
@@ -38,12 +39,34 @@ pub struct VaultConfig {
     // The percentage of the still outstanding loan amount that is rewarded for marking an auction as failed.
     // The actual reward is the minimum of this percentage and the auction_failure_reward_cap.
     auction_failure_reward_rate: Decimal,
+    // The minimum debt of a vault that has debt, so that liquidating it is worth the cost.
+    min_debt: Decimal,
 }
 
 unsafe impl Pod for VaultConfig {
     const NAME: &'static str = "VaultConfig";
 }
 
+impl VaultConfig {
+    /// Reverts if a vault would be left with debt below the minimum.
+    fn require_min_debt(&self, debt: Decimal) {
+        require(
+            debt.is_zero() || debt >= self.min_debt,
+            "Vault debt is below the minimum",
+        );
+    }
+
+    /// Caps a partial repayment of `amount` out of `debt` so that it leaves
+    /// at least the minimum debt.
+    fn cap_partial_repayment(&self, debt: Decimal, amount: Decimal) -> Decimal {
+        if amount < debt {
+            amount.min(debt.saturating_sub(self.min_debt))
+        } else {
+            amount
+        }
+    }
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 impl VaultConfig {
@@ -55,6 +78,7 @@ impl VaultConfig {
         liquidationRewardRate: f64,
         auctionFailureRewardCap: f64,
         auctionFailureRewardRate: f64,
+        minDebt: f64,
     ) -> Self {
         Self {
             liquidation_penalty_rate: Decimal::from(liquidationPenaltyRate),
@@ -62,6 +86,7 @@ impl VaultConfig {
             liquidation_reward_rate: Decimal::from(liquidationRewardRate),
             auction_failure_reward_cap: Decimal::from(auctionFailureRewardCap),
             auction_failure_reward_rate: Decimal::from(auctionFailureRewardRate),
+            min_debt: Decimal::from(minDebt),
         }
     }
 
@@ -89,6 +114,11 @@ impl VaultConfig {
     pub fn auction_failure_reward_rate(&self) -> f64 {
         self.auction_failure_reward_rate.to_f64()
     }
+
+    #[wasm_bindgen(getter, js_name = "minDebt")]
+    pub fn min_debt(&self) -> f64 {
+        self.min_debt.to_f64()
+    }
 }
 
 #[repr(C)]
@@ -99,8 +129,16 @@ pub struct Vault {
     nonce: u8,
     owner: Pubkey,
     debt: Page,
+    // The part of the debt borrowed at a fixed rate, until maturity.
+    fixed_term: Option<FixedTerm>,
     reserves: List<Reserve, MAX_RESERVES>,
     auction: Option<Auction<MAX_RESERVES>>,
+    // The liquidation penalty in the debt of a vault under auction, until
+    // auction proceeds realize it.
+    penalty: Decimal,
+    // The DVD minted for the debt and not yet repaid. The rest of the debt is
+    // interest and fees, realized as they are repaid.
+    borrowed: Decimal,
 }
 
 impl Store for Vault {
@@ -129,8 +167,11 @@ impl Store for Vault {
         self.nonce = nonce;
         self.owner = *user_account.get_info().key;
         self.debt = Page::new();
+        self.fixed_term = None;
         self.reserves = List::new();
         self.auction = None;
+        self.penalty = Decimal::zero();
+        self.borrowed = Decimal::zero();
     }
     fn is_initialized(&self) -> bool {
         self.initialized
@@ -187,6 +228,7 @@ impl Vault {
         user_account: Signer,
         source_token_account: TokenAccount<Writable>,
         token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
     ) {
         _ = auth;
@@ -204,27 +246,73 @@ impl Vault {
             user_account,
             source_token_account,
             token_account,
+            mint_account,
             token_program_account,
         );
+        self.update_fee_shares();
+    }
+
+    /// Returns the value of each collateral asset, in DVD.
+    fn get_reserve_values(
+        &mut self,
+        purpose: PricePurpose,
+        collateral: &mut [&mut Collateral],
+        oracle_accounts: &[Readonly],
+        dvd_price: &mut DvdPrice,
+        dvd_interest_rate: &InterestRate,
+        clock: &Clock,
+    ) -> [Decimal; MAX_RESERVES] {
+        let mut values = [Decimal::zero(); MAX_RESERVES];
+        for (((v, r), c), &o) in values
+            .iter_mut()
+            .zip(self.reserves.iter_mut())
+            .zip(collateral)
+            .zip(oracle_accounts)
+        {
+            *v = r.get_value(c, purpose, o, dvd_price, dvd_interest_rate, clock);
+        }
+        values
     }
 
     /// Returns the total value of all collateral assets, in DVD.
-    fn get_collateral_value<'a, T>(
-        &self,
-        collateral: &[T],
+    fn get_collateral_value(
+        &mut self,
+        purpose: PricePurpose,
+        collateral: &mut [&mut Collateral],
         oracle_accounts: &[Readonly],
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         clock: &Clock,
-    ) -> Decimal
-    where
-        T: std::ops::Deref<Target = Collateral>,
-    {
-        let mut sum = Decimal::zero();
-        for ((&r, c), &o) in self.reserves.iter().zip(collateral).zip(oracle_accounts) {
-            sum += r.get_value(c, o, dvd_price, dvd_interest_rate, clock);
+    ) -> Decimal {
+        self.get_reserve_values(
+            purpose,
+            collateral,
+            oracle_accounts,
+            dvd_price,
+            dvd_interest_rate,
+            clock,
+        )
+        .iter()
+        .fold(Decimal::zero(), |sum, &v| sum + v)
+    }
+
+    /// Weighs the stability fee of each collateral type by the value of its
+    /// reserve, at the price it was last valued at. Called whenever a reserve
+    /// changes, so that the shares follow deposits, withdrawals, redemptions
+    /// and auctions. Shares are kept while the vault holds no valued
+    /// collateral.
+    fn update_fee_shares(&mut self) {
+        let total = self
+            .reserves
+            .iter()
+            .fold(Decimal::zero(), |sum, r| sum + r.get_last_value());
+        if total.is_zero() {
+            return;
+        }
+        for r in self.reserves.iter_mut() {
+            let share = r.get_last_value() / total;
+            r.set_fee_share(share);
         }
-        sum
     }
 
     pub fn withdraw(
@@ -237,11 +325,13 @@ impl Vault {
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         max_ltv: Decimal,
+        ledger: &mut Ledger,
 
         program_id: &Pubkey,
 
         program_token_account: TokenAccount<Writable>,
         destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
 
         authority: Authority,
@@ -256,80 +346,129 @@ impl Vault {
         require(self.auction.is_none(), "Vault is liquidated");
         require(reserve_index < self.reserves.len(), "Invalid reserve index");
 
-        let collateral_value = self.get_collateral_value(
-            &collateral,
-            oracle_accounts,
-            dvd_price,
-            dvd_interest_rate,
-            clock,
-        );
+        self.accrue_fixed_term(debt_book, debt_config, clock);
+        self.accrue_stability_fees(&mut collateral, debt_book, debt_config, ledger, clock);
         let debt = self.debt.get_total(debt_book, debt_config, clock);
-        let max_withdraw_value = collateral_value.saturating_sub(debt / max_ltv);
-
-        let reserve_oracle = oracle_accounts[reserve_index];
-        let reserve_collateral_price = collateral[reserve_index].get_price(
-            reserve_oracle,
-            dvd_price,
-            dvd_interest_rate,
-            clock,
-        );
-        let max_withdraw_amount = max_withdraw_value / reserve_collateral_price;
-
-        let reserve = &mut self.reserves[reserve_index];
-        let amount = requested_amount
-            .min(max_withdraw_amount)
-            .min(reserve.get_balance());
+        // Without debt there is nothing to secure, and no price is needed,
+        // which lets owners withdraw after global settlement.
+        let amount = if debt.is_zero() {
+            requested_amount.min(self.reserves[reserve_index].get_balance())
+        } else {
+            let values = self.get_reserve_values(
+                PricePurpose::Borrow,
+                &mut collateral,
+                oracle_accounts,
+                dvd_price,
+                dvd_interest_rate,
+                clock,
+            );
+            let collateral_value = values.iter().fold(Decimal::zero(), |sum, &v| sum + v);
+            let max_withdraw_value = collateral_value.saturating_sub(debt / max_ltv);
+
+            let reserve_oracle = oracle_accounts[reserve_index];
+            let reserve_collateral_price = collateral[reserve_index].get_price(
+                PricePurpose::Borrow,
+                reserve_oracle,
+                dvd_price,
+                dvd_interest_rate,
+                clock,
+            );
+            requested_amount
+                .min(max_withdraw_value / reserve_collateral_price)
+                .min(self.reserves[reserve_index].get_balance())
+        };
         require(amount > Decimal::zero(), "Amount must be greater than zero");
 
-        reserve.withdraw(
+        self.reserves[reserve_index].withdraw(
             amount,
             program_id,
             collateral[reserve_index],
             program_token_account,
             destination_token_account,
+            mint_account,
             token_program_account,
             authority,
-        )
+        );
+        self.update_fee_shares();
+        self.record_backed_debt(&mut collateral, debt_book, debt_config, clock);
     }
 
+    /// Borrows up to `requested_amount` DVD. The origination fee is added to
+    /// the debt on top, and its referral share minted to `referrer_account`.
+    /// With a `fixed_term`, the new debt accrues at its fixed rate until
+    /// maturity.
     pub fn borrow(
         &mut self,
         auth: StoreAuth<Self>,
         requested_amount: Decimal,
+        fixed_term: Option<FixedTerm>,
 
         debt_book: &mut Book,
         debt_config: &BookConfig,
         dvd: &mut Token,
+        dvd_deposits: Decimal,
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         max_ltv: Decimal,
+        vault_config: &VaultConfig,
+        origination_config: &OriginationConfig,
+        ledger: &mut Ledger,
+        mint_limiter: &mut MintLimiter,
+        mint_limit_config: &MintLimitConfig,
         authority: Authority,
 
-        collateral: &[&Collateral],
+        collateral: &mut [&mut Collateral],
         oracle_accounts: &[Readonly],
         mint_account: MintAccount<Writable>,
         dvd_account: TokenAccount<Writable>,
+        referrer_account: Option<TokenAccount<Writable>>,
         token_program_account: TokenProgramAccount,
         clock: &Clock,
     ) {
         _ = auth;
         require(self.auction.is_none(), "Vault is liquidated");
 
-        let collateral_value = self.get_collateral_value(
+        self.accrue_fixed_term(debt_book, debt_config, clock);
+        self.accrue_stability_fees(collateral, debt_book, debt_config, ledger, clock);
+        if fixed_term.is_some() {
+            require(
+                self.fixed_term.is_none(),
+                "Vault already has a fixed-rate term",
+            );
+            self.fixed_term = fixed_term;
+        }
+        let values = self.get_reserve_values(
+            PricePurpose::Borrow,
             collateral,
             oracle_accounts,
             dvd_price,
             dvd_interest_rate,
             clock,
         );
+        self.update_fee_shares();
+        let collateral_value = values.iter().fold(Decimal::zero(), |sum, &v| sum + v);
 
         let borrow_limit = collateral_value * max_ltv;
         let debt = self.debt.get_total(debt_book, debt_config, clock);
         let available_borrow = borrow_limit.saturating_sub(debt);
 
-        let amount = available_borrow.min(requested_amount);
+        let amount = (available_borrow / (Decimal::one() + origination_config.get_fee_rate()))
+            .min(requested_amount);
+        let (fee, referral) = origination_config.get_fee(amount, referrer_account.is_some());
+        vault_config.require_min_debt(debt + amount + fee);
 
-        self.debt.add(amount, debt_book, debt_config, clock);
+        mint_limiter.record(
+            amount + referral,
+            dvd.get_supply() + dvd_deposits,
+            mint_limit_config,
+            clock,
+        );
+        self.debt.add(amount + fee, debt_book, debt_config, clock);
+        self.borrowed += amount + referral;
+        if let (Some(_), Some(term)) = (fixed_term, &mut self.fixed_term) {
+            term.add(amount + fee);
+        }
+        self.record_backed_debt(collateral, debt_book, debt_config, clock);
         dvd.mint(
             amount,
             mint_account,
@@ -337,6 +476,16 @@ impl Vault {
             authority,
             token_program_account,
         );
+        if let Some(referrer_account) = referrer_account {
+            dvd.mint(
+                referral,
+                mint_account,
+                referrer_account,
+                authority,
+                token_program_account,
+            );
+        }
+        ledger.record_stability_fee(fee - referral);
     }
 
     // Can be called during liquidation to repay debt and reduce collateral loss.
@@ -347,8 +496,13 @@ impl Vault {
 
         debt_book: &mut Book,
         debt_config: &BookConfig,
+        vault_config: &VaultConfig,
         dvd: &mut Token,
+        ledger: &mut Ledger,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
 
+        collateral: &mut [&mut Collateral],
         user_account: Signer,
         mint_account: MintAccount<Writable>,
         dvd_account: TokenAccount<Writable>,
@@ -356,9 +510,18 @@ impl Vault {
         clock: &Clock,
     ) {
         _ = auth;
+        self.accrue_fixed_term(debt_book, debt_config, clock);
+        self.accrue_stability_fees(collateral, debt_book, debt_config, ledger, clock);
         let debt = self.debt.get_total(debt_book, debt_config, clock);
         let amount = requested_amount.min(debt);
+        if self.auction.is_none() {
+            vault_config.require_min_debt(debt - amount);
+        }
         self.debt.subtract(amount, debt_book, debt_config, clock);
+        let revenue = self.record_repayment(amount, debt, ledger);
+        savings_book.share_revenue(revenue, savings_config);
+        self.cap_fixed_term(debt - amount);
+        self.record_backed_debt(collateral, debt_book, debt_config, clock);
         dvd.burn(
             amount,
             mint_account,
@@ -397,18 +560,117 @@ impl Vault {
 
 // Unauthorized functions
 impl Vault {
+    /// Charges the fixed rate on the fixed-rate debt since it was last
+    /// accrued, and ends the term past maturity.
+    pub fn accrue_fixed_term(
+        &mut self,
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        clock: &Clock,
+    ) {
+        if let Some(term) = &mut self.fixed_term {
+            if term.accrue(&mut self.debt, debt_book, debt_config, clock) {
+                self.fixed_term = None;
+            }
+        }
+    }
+
+    fn cap_fixed_term(&mut self, debt: Decimal) {
+        if let Some(term) = &mut self.fixed_term {
+            if term.cap(debt) {
+                self.fixed_term = None;
+            }
+        }
+    }
+
+    /// Records `amount` of `debt` as repaid, in proportion out of the
+    /// borrowed DVD and out of interest and fees, which are realized.
+    /// Returns the interest and fees realized, without the liquidation
+    /// penalty, which is repaid last and goes to the insurance fund.
+    fn record_repayment(
+        &mut self,
+        amount: Decimal,
+        debt: Decimal,
+        ledger: &mut Ledger,
+    ) -> Decimal {
+        let borrowed = if amount >= debt {
+            self.borrowed
+        } else {
+            (self.borrowed * (amount / debt)).min(self.borrowed)
+        };
+        self.borrowed -= borrowed;
+        let revenue = amount.saturating_sub(borrowed);
+        ledger.record_realized_revenue(revenue);
+        let penalty = amount
+            .saturating_sub(debt.saturating_sub(self.penalty))
+            .min(self.penalty);
+        revenue.saturating_sub(penalty)
+    }
+
+    /// Adds the stability fees of the collateral types in `collateral`,
+    /// accrued since they were last charged, to the debt. Fees are charged
+    /// lazily, on the debt and fee share of the vault at the time: a reserve
+    /// without its entry in `collateral` keeps accruing until the vault is
+    /// next updated with it.
+    pub fn accrue_stability_fees(
+        &mut self,
+        collateral: &mut [&mut Collateral],
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        ledger: &mut Ledger,
+        clock: &Clock,
+    ) {
+        let debt = self.debt.get_total(debt_book, debt_config, clock);
+        let mut fee = Decimal::zero();
+        for r in self.reserves.iter_mut() {
+            if let Some(c) = collateral.iter_mut().find(|c| c.get_mint() == r.get_mint()) {
+                fee += r.accrue_stability_fee(c, debt, clock);
+            }
+        }
+        if !fee.is_zero() {
+            self.debt.add(fee, debt_book, debt_config, clock);
+            ledger.record_stability_fee(fee);
+        }
+    }
+
+    /// Records the part of the debt backed by each reserve in its collateral
+    /// type, for the aggregate collateral ratio of recovery mode. Debt repaid
+    /// without every reserve's entry in `collateral`, by redemptions and
+    /// auctions, stays recorded until the vault is next updated with them.
+    fn record_backed_debt(
+        &mut self,
+        collateral: &mut [&mut Collateral],
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        clock: &Clock,
+    ) {
+        let principal = self.debt.get_principal(debt_book, debt_config, clock);
+        for r in self.reserves.iter_mut() {
+            if let Some(c) = collateral.iter_mut().find(|c| c.get_mint() == r.get_mint()) {
+                r.record_backed_debt(c, principal);
+            }
+        }
+    }
+
+    /// Liquidates an unhealthy vault. The stability pool cancels the debt in
+    /// exchange for the collateral when it is deep enough, otherwise the
+    /// collateral goes up for auction.
     pub fn liquidate(
         &mut self,
 
         max_ltv: Decimal,
         debt_book: &mut Book,
         debt_config: &BookConfig,
+        ledger: &mut Ledger,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        stability_pool: &mut Pool,
         vault_config: &VaultConfig,
         dvd: &mut Token,
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
 
-        collateral: &[&Collateral],
+        collateral: &mut [&mut Collateral],
         oracle_accounts: &[Readonly],
 
         dvd_mint_account: MintAccount<Writable>,
@@ -419,26 +681,57 @@ impl Vault {
         clock: &Clock,
     ) {
         require(self.auction.is_none(), "Vault is already liquidated");
+        self.accrue_fixed_term(debt_book, debt_config, clock);
+        self.accrue_stability_fees(collateral, debt_book, debt_config, ledger, clock);
         let collateral_value = self.get_collateral_value(
+            PricePurpose::Liquidate,
             collateral,
             oracle_accounts,
             dvd_price,
             dvd_interest_rate,
             clock,
         );
+        self.update_fee_shares();
         let max_debt = collateral_value * max_ltv;
         let debt = self.debt.get_total(debt_book, debt_config, clock);
         if debt <= max_debt {
             revert("Vault is not unhealthy");
         }
-        let mut auction_market_prices = [Decimal::zero(); MAX_RESERVES];
-        for (i, (&c, &o)) in collateral.iter().zip(oracle_accounts).enumerate() {
-            auction_market_prices[i] = c.get_price(o, dvd_price, dvd_interest_rate, clock);
+        // Liquidated debt floats, whatever happens to the vault next
+        self.fixed_term = None;
+        let mut gains = [(Pubkey::default(), Decimal::zero()); MAX_RESERVES];
+        for (g, r) in gains.iter_mut().zip(self.reserves.iter()) {
+            *g = (*r.get_mint().get_key(), r.get_balance());
         }
-        self.auction = Some(Auction::new(auction_market_prices, Time::now(clock)));
-        let liquidation_penalty = debt * vault_config.liquidation_penalty_rate;
-        self.debt
-            .add(liquidation_penalty, debt_book, debt_config, clock);
+        let gains = &gains[..self.reserves.len()];
+        if stability_pool.can_absorb(debt, gains) {
+            // The pool cancels the debt at once and takes the collateral
+            let debt = self.debt.take(debt_book, debt_config, clock);
+            let revenue = self.record_repayment(debt, debt, ledger);
+            savings_book.share_revenue(revenue, savings_config);
+            for r in self.reserves.iter_mut() {
+                r.take();
+            }
+            stability_pool.absorb(debt, gains);
+        } else {
+            let mut auction_market_prices = [Decimal::zero(); MAX_RESERVES];
+            for (i, (c, &o)) in collateral.iter_mut().zip(oracle_accounts).enumerate() {
+                auction_market_prices[i] = c.get_price(
+                    PricePurpose::Liquidate,
+                    o,
+                    dvd_price,
+                    dvd_interest_rate,
+                    clock,
+                );
+            }
+            self.auction = Some(Auction::new(auction_market_prices, Time::now(clock)));
+            let liquidation_penalty = debt * vault_config.liquidation_penalty_rate;
+            self.debt
+                .add(liquidation_penalty, debt_book, debt_config, clock);
+            ledger.record_liquidation_penalty(liquidation_penalty);
+            self.penalty = liquidation_penalty;
+        }
+        self.record_backed_debt(collateral, debt_book, debt_config, clock);
         let liquidation_reward =
             (debt * vault_config.liquidation_reward_rate).min(vault_config.liquidation_reward_cap);
         dvd.mint(
@@ -450,6 +743,86 @@ impl Vault {
         );
     }
 
+    pub const fn get_owner(&self) -> &Pubkey {
+        &self.owner
+    }
+
+    pub const fn has_debt(&self) -> bool {
+        !self.debt.is_zero()
+    }
+
+    /// Returns the collateral ratio of the vault, valuing each reserve at the
+    /// price of the matching entry of `collateral`, or `None` if the vault
+    /// can't be redeemed against because it has no debt, is liquidated or is
+    /// unhealthy.
+    pub fn get_redemption_ratio(
+        &mut self,
+        max_ltv: Decimal,
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        collateral: &[&mut Collateral],
+        prices: &[Decimal],
+        clock: &Clock,
+    ) -> Option<Decimal> {
+        let debt = self.debt.get_total(debt_book, debt_config, clock);
+        if self.auction.is_some() || debt.is_zero() {
+            return None;
+        }
+        let mut collateral_value = Decimal::zero();
+        for r in self.reserves.iter() {
+            let index = collateral
+                .iter()
+                .position(|c| c.get_mint() == r.get_mint())
+                .ok_or("Missing collateral account for reserve")
+                .unwrap();
+            collateral_value += r.get_balance() * prices[index];
+        }
+        (debt <= collateral_value * max_ltv).then(|| collateral_value / debt)
+    }
+
+    /// Cancels up to `requested_amount` of debt in exchange for collateral
+    /// from the reserve of `collateral`, valued at `price`.
+    /// Returns the debt cancelled and the collateral taken, which stays in the
+    /// safe for the caller to pay out. Vaults without a reserve of
+    /// `collateral` are skipped.
+    pub fn redeem(
+        &mut self,
+        requested_amount: Decimal,
+        collateral: &Collateral,
+        price: Decimal,
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        vault_config: &VaultConfig,
+        ledger: &mut Ledger,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
+        clock: &Clock,
+    ) -> (Decimal, Decimal) {
+        let reserve = match self
+            .reserves
+            .iter_mut()
+            .find(|r| r.get_mint() == collateral.get_mint())
+        {
+            Some(reserve) => reserve,
+            None => return (Decimal::zero(), Decimal::zero()),
+        };
+        let debt = self.debt.get_total(debt_book, debt_config, clock);
+        let amount = vault_config.cap_partial_repayment(
+            debt,
+            requested_amount
+                .min(debt)
+                .min(reserve.get_balance() * price),
+        );
+        let collateral_amount = (amount / price).min(reserve.get_balance());
+        reserve.release(collateral_amount);
+        self.update_fee_shares();
+        self.debt.subtract(amount, debt_book, debt_config, clock);
+        let revenue = self.record_repayment(amount, debt, ledger);
+        savings_book.share_revenue(revenue, savings_config);
+        self.cap_fixed_term(debt - amount);
+        (amount, collateral_amount)
+    }
+
     pub fn unliquidate(&mut self) {
         require(self.auction.is_some(), "Vault is not liquidated");
         if !self.debt.is_zero() {
@@ -458,13 +831,19 @@ impl Vault {
         self.auction = None;
     }
 
+    /// Writes off the remaining debt of a vault whose auction is over, and
+    /// seizes whatever collateral the auction did not sell.
+    /// The insurance fund covers as much of the bad debt as it can.
     pub fn fail_auction(
         &mut self,
         debt_book: &mut Book,
         debt_config: &BookConfig,
+        ledger: &mut Ledger,
+        insurance_fund: &mut InsuranceFund,
         vault_config: &VaultConfig,
         auction_config: &AuctionConfig,
         dvd: &mut Token,
+        collateral: &mut [&mut Collateral],
         dvd_mint_account: MintAccount<Writable>,
         dvd_account: TokenAccount<Writable>,
         token_program_account: TokenProgramAccount,
@@ -480,8 +859,21 @@ impl Vault {
         if !is_over {
             revert("Auction is not over");
         }
+        require(
+            collateral.len() == self.reserves.len(),
+            "Expected one collateral account per reserve",
+        );
         self.auction = None;
+        self.penalty = Decimal::zero();
+        for (r, c) in self.reserves.iter_mut().zip(collateral.iter_mut()) {
+            r.seize(c);
+        }
         let debt = self.debt.take(debt_book, debt_config, clock);
+        // The interest and fees are written off with the rest of the debt
+        self.record_repayment(debt, debt, ledger);
+        self.record_backed_debt(collateral, debt_book, debt_config, clock);
+        ledger.record_bad_debt(debt);
+        insurance_fund.absorb(debt);
         let auction_failure_reward = (debt * vault_config.auction_failure_reward_rate)
             .min(vault_config.auction_failure_reward_cap);
         dvd.mint(
@@ -493,6 +885,53 @@ impl Vault {
         );
     }
 
+    /// Covers the debt of the vault with its collateral at the prices frozen
+    /// by global settlement. The collateral is taken from every reserve in
+    /// proportion, any shortfall is written off as bad debt, and the rest
+    /// stays in the vault for its owner to withdraw.
+    pub fn settle(
+        &mut self,
+        debt_book: &mut Book,
+        debt_config: &BookConfig,
+        ledger: &mut Ledger,
+        collateral: &mut [&mut Collateral],
+        clock: &Clock,
+    ) {
+        require(
+            collateral.len() == self.reserves.len(),
+            "Expected one collateral account per reserve",
+        );
+        self.accrue_fixed_term(debt_book, debt_config, clock);
+        self.accrue_stability_fees(collateral, debt_book, debt_config, ledger, clock);
+        let debt = self.debt.take(debt_book, debt_config, clock);
+        require(!debt.is_zero(), "Vault has no debt");
+        self.record_repayment(debt, debt, ledger);
+        self.record_backed_debt(collateral, debt_book, debt_config, clock);
+        self.auction = None;
+        self.penalty = Decimal::zero();
+        self.fixed_term = None;
+        let mut collateral_value = Decimal::zero();
+        for (r, c) in self.reserves.iter().zip(collateral.iter()) {
+            require(
+                c.get_mint() == r.get_mint(),
+                "Collateral accounts do not match reserves",
+            );
+            collateral_value += r.get_balance() * c.get_settlement_price();
+        }
+        let share = if collateral_value > debt {
+            debt / collateral_value
+        } else {
+            ledger.record_bad_debt(debt - collateral_value);
+            Decimal::one()
+        };
+        for (r, c) in self.reserves.iter_mut().zip(collateral) {
+            let amount = r.get_balance() * share;
+            r.release(amount);
+            c.seize(amount);
+        }
+        self.update_fee_shares();
+    }
+
     pub fn buy_collateral(
         &mut self,
         requested_dvd_amount: Decimal,
@@ -502,8 +941,14 @@ impl Vault {
         collateral: &mut Collateral,
         debt_book: &mut Book,
         debt_config: &BookConfig,
+        insurance_fund: &mut InsuranceFund,
+        insurance_rate: Decimal,
         dvd: &mut Token,
         auction_config: &AuctionConfig,
+        vault_config: &VaultConfig,
+        ledger: &mut Ledger,
+        savings_book: &mut Book,
+        savings_config: &BookConfig,
 
         user_account: Signer,
 
@@ -512,8 +957,10 @@ impl Vault {
 
         safe_account: TokenAccount<Writable>,
         collateral_destination_token_account: TokenAccount<Writable>,
+        collateral_mint_account: MintAccount<Readonly>,
 
         token_program_account: TokenProgramAccount,
+        collateral_token_program_account: TokenProgramAccount,
 
         collateral_index: usize,
         authority: Authority,
@@ -544,8 +991,11 @@ impl Vault {
             // 2nd priority: repay all collateral, so we can fail later if necessary!
             (max_collateral_amount, max_collateral_amount * auction_price)
         } else {
-            // 3rd priority: buy a portion of the collateral and repay a portion of the debt
-            (requested_collateral_amount, requested_dvd_amount)
+            // 3rd priority: buy a portion of the collateral and repay a portion of the debt,
+            // leaving at least the minimum debt
+            let dvd_amount =
+                vault_config.cap_partial_repayment(max_dvd_amount, requested_dvd_amount);
+            (dvd_amount / auction_price, dvd_amount)
         };
 
         reserve.withdraw(
@@ -554,9 +1004,11 @@ impl Vault {
             collateral,
             safe_account,
             collateral_destination_token_account,
-            token_program_account,
+            collateral_mint_account,
+            collateral_token_program_account,
             authority,
         );
+        self.update_fee_shares();
 
         dvd.burn(
             dvd_amount,
@@ -568,6 +1020,15 @@ impl Vault {
 
         self.debt
             .subtract(dvd_amount, debt_book, debt_config, clock);
+        let revenue = self.record_repayment(dvd_amount, max_dvd_amount, ledger);
+        savings_book.share_revenue(revenue, savings_config);
+
+        // Proceeds realize the penalty last, once the debt it was added to is repaid
+        let realized_penalty = dvd_amount
+            .saturating_sub(max_dvd_amount.saturating_sub(self.penalty))
+            .min(self.penalty);
+        self.penalty -= realized_penalty;
+        insurance_fund.collect(realized_penalty, insurance_rate);
     }
 }
 
@@ -602,6 +1063,11 @@ impl Vault {
         self.debt
     }
 
+    #[wasm_bindgen(getter, js_name = fixedTerm)]
+    pub fn fixed_term(&self) -> Option<FixedTerm> {
+        self.fixed_term
+    }
+
     #[wasm_bindgen(getter, js_name = isDebtZero)]
     pub fn is_debt_zero(&self) -> bool {
         self.debt.is_zero()
@@ -659,3 +1125,142 @@ impl Vault {
 unsafe impl Pod for Vault {
     const NAME: &'static str = "Vault";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> VaultConfig {
+        VaultConfig {
+            min_debt: Decimal::from(100u64),
+            ..VaultConfig::zero()
+        }
+    }
+
+    #[test]
+    fn test_borrow_to_min_debt() {
+        config().require_min_debt(Decimal::from(100u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Vault debt is below the minimum")]
+    fn test_borrow_below_min_debt() {
+        config().require_min_debt(Decimal::from(99u64));
+    }
+
+    #[test]
+    fn test_repay_in_full() {
+        config().require_min_debt(Decimal::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "Vault debt is below the minimum")]
+    fn test_repay_leaving_dust() {
+        let debt = Decimal::from(150u64);
+        config().require_min_debt(debt - Decimal::from(60u64));
+    }
+
+    #[test]
+    fn test_partial_liquidation_leaves_min_debt() {
+        let debt = Decimal::from(150u64);
+        assert_eq!(
+            config().cap_partial_repayment(debt, Decimal::from(60u64)),
+            Decimal::from(50u64)
+        );
+        assert_eq!(
+            config().cap_partial_repayment(debt, Decimal::from(40u64)),
+            Decimal::from(40u64)
+        );
+    }
+
+    #[test]
+    fn test_full_repayment_is_not_capped() {
+        let debt = Decimal::from(150u64);
+        assert_eq!(config().cap_partial_repayment(debt, debt), debt);
+    }
+
+    #[test]
+    fn test_partial_repayment_below_min_debt() {
+        // A vault already at the minimum can only be repaid in full
+        let debt = Decimal::from(100u64);
+        assert!(config()
+            .cap_partial_repayment(debt, Decimal::from(10u64))
+            .is_zero());
+    }
+
+    fn vault(borrowed: u64) -> Vault {
+        Vault {
+            borrowed: Decimal::from(borrowed),
+            ..Vault::zero()
+        }
+    }
+
+    #[test]
+    fn test_repayment_realizes_revenue_pro_rata() {
+        let mut vault = vault(100);
+        let mut ledger = Ledger::new();
+        vault.record_repayment(Decimal::from(60u64), Decimal::from(120u64), &mut ledger);
+        assert_eq!(vault.borrowed, Decimal::from(50u64));
+        assert_eq!(ledger.get_net_realized_revenue(), Decimal::from(10u64));
+        vault.record_repayment(Decimal::from(60u64), Decimal::from(60u64), &mut ledger);
+        assert!(vault.borrowed.is_zero());
+        assert_eq!(ledger.get_net_realized_revenue(), Decimal::from(20u64));
+    }
+
+    #[test]
+    fn test_written_off_debt_loses_borrowed() {
+        let mut vault = vault(100);
+        let mut ledger = Ledger::new();
+        vault.record_repayment(Decimal::from(60u64), Decimal::from(120u64), &mut ledger);
+        let debt = Decimal::from(60u64);
+        vault.record_repayment(debt, debt, &mut ledger);
+        ledger.record_bad_debt(debt);
+        // The unpaid interest is never realized, the borrowed DVD is lost
+        assert!(ledger.get_net_realized_revenue().is_zero());
+    }
+
+    #[test]
+    fn test_repaid_penalty_is_not_shared() {
+        let mut vault = Vault {
+            penalty: Decimal::from(10u64),
+            ..vault(100)
+        };
+        let mut ledger = Ledger::new();
+        // 100 borrowed, 20 of interest and the 10 penalty, repaid last
+        let debt = Decimal::from(130u64);
+        assert_eq!(
+            vault.record_repayment(Decimal::from(65u64), debt, &mut ledger),
+            Decimal::from(15u64)
+        );
+        assert_eq!(
+            vault.record_repayment(Decimal::from(65u64), Decimal::from(65u64), &mut ledger),
+            Decimal::from(5u64)
+        );
+    }
+
+    #[test]
+    fn test_fee_shares_follow_reserve_values() {
+        let mut vault = Vault::zero();
+        vault
+            .reserves
+            .push(Reserve::valued(Decimal::from(3u64), Decimal::from(100u64)));
+        vault
+            .reserves
+            .push(Reserve::valued(Decimal::from(100u64), Decimal::one()));
+        vault.update_fee_shares();
+        assert_eq!(
+            vault.reserves[0].get_fee_share(),
+            Decimal::from(3u64) / 4u64
+        );
+        assert_eq!(vault.reserves[1].get_fee_share(), Decimal::one() / 4u64);
+        // Redeeming the first reserve moves its fees onto the second
+        vault.reserves[0].release(Decimal::from(3u64));
+        vault.update_fee_shares();
+        assert!(vault.reserves[0].get_fee_share().is_zero());
+        assert_eq!(vault.reserves[1].get_fee_share(), Decimal::one());
+        // An empty vault keeps its last shares
+        vault.reserves[1].release(Decimal::from(100u64));
+        vault.update_fee_shares();
+        assert_eq!(vault.reserves[1].get_fee_share(), Decimal::one());
+    }
+}
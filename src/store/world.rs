@@ -1,8 +1,12 @@
 use {
     crate::{
         accounts::{MintAccount, Readonly, Signer},
-        finance::{Book, Schedule},
-        state::{Config, DvdPrice, FlashMint, Offering, Sovereign, StableDvd, Vesting},
+        finance::{Book, Decimal, Pool, Schedule},
+        state::{
+            Config, DvdPrice, FlashMint, InsuranceFund, Ledger, MintLimiter, Offering, Recovery,
+            Redemption, Referrers, SavingsExit, SavingsShare, Settlement, Sovereign, StableDvd,
+            TermSavings, Vesting,
+        },
         store::Authority,
         token::Token,
         traits::{Pod, Store},
@@ -12,7 +16,10 @@ use {
 };
 
 #[cfg(feature = "wasm")]
-use wasm_bindgen::prelude::wasm_bindgen;
+use {
+    crate::{state::BalanceSheet, util::Time},
+    wasm_bindgen::prelude::wasm_bindgen,
+};
 
 /// The struct containing all global state.
 ///
@@ -30,13 +37,24 @@ pub struct World {
     pub dvd: Token,
     pub debt: Book,
     pub savings: Book,
+    pub savings_share: SavingsShare,
+    pub savings_exit: SavingsExit,
+    pub term_savings: TermSavings,
+    pub stability_pool: Pool,
 
     pub stable_dvd: StableDvd,
+    pub ledger: Ledger,
+    pub insurance_fund: InsuranceFund,
     pub dvd_price: DvdPrice,
 
     pub offering: Offering,
     pub flash_mint: FlashMint,
+    pub mint_limiter: MintLimiter,
+    pub redemption: Redemption,
+    pub recovery: Recovery,
+    pub referrers: Referrers,
     pub sovereign: Sovereign,
+    pub settlement: Settlement,
     pub vesting: Vesting,
 
     pub config: Config,
@@ -90,12 +108,23 @@ impl Store for World {
         );
         self.debt = Book::new(&params.clock);
         self.savings = Book::new(&params.clock);
+        self.savings_share = SavingsShare::new();
+        self.savings_exit = SavingsExit::new();
+        self.term_savings = TermSavings::new();
+        self.stability_pool = Pool::new();
         self.stable_dvd = StableDvd::new();
+        self.ledger = Ledger::new();
+        self.insurance_fund = InsuranceFund::new();
         self.dvd_price = DvdPrice::new(&params.clock);
         self.config = Config::zero();
         self.sovereign = Sovereign::new(params.sovereign_account);
+        self.settlement = Settlement::new();
         self.offering = Offering::new();
         self.flash_mint = FlashMint::new();
+        self.mint_limiter = MintLimiter::new(&params.clock);
+        self.redemption = Redemption::new(&params.clock);
+        self.recovery = Recovery::new();
+        self.referrers = Referrers::new();
         self.vesting = Vesting::new(
             &params.clock,
             params.vesting_recipient,
@@ -111,6 +140,17 @@ impl Store for World {
     }
 }
 
+impl World {
+    /// Returns the DVD deposited in savings, term deposits and the stability
+    /// pool, which is burned on deposit but still owed to depositors.
+    pub fn get_dvd_deposits(&mut self, clock: &Clock) -> Decimal {
+        self.savings
+            .get_total(self.config.get_savings_config(), clock)
+            + self.term_savings.get_liability(clock)
+            + self.stability_pool.get_total()
+    }
+}
+
 unsafe impl Pod for World {
     const NAME: &'static str = "World";
 }
@@ -150,4 +190,50 @@ impl World {
     pub fn flash_mint_wasm(&self) -> FlashMint {
         self.flash_mint
     }
+
+    #[wasm_bindgen(getter, js_name = mintLimiter)]
+    pub fn mint_limiter_wasm(&self) -> MintLimiter {
+        self.mint_limiter
+    }
+
+    #[wasm_bindgen(getter, js_name = savingsShare)]
+    pub fn savings_share_wasm(&self) -> SavingsShare {
+        self.savings_share
+    }
+
+    #[wasm_bindgen(getter, js_name = savingsExit)]
+    pub fn savings_exit_wasm(&self) -> SavingsExit {
+        self.savings_exit
+    }
+
+    #[wasm_bindgen(getter, js_name = termSavings)]
+    pub fn term_savings_wasm(&self) -> TermSavings {
+        self.term_savings
+    }
+
+    #[wasm_bindgen(getter, js_name = stabilityPool)]
+    pub fn stability_pool_wasm(&self) -> Pool {
+        self.stability_pool
+    }
+
+    #[wasm_bindgen(getter, js_name = insuranceFund)]
+    pub fn insurance_fund_wasm(&self) -> InsuranceFund {
+        self.insurance_fund
+    }
+
+    #[wasm_bindgen(js_name = balanceSheet)]
+    #[allow(non_snake_case)]
+    pub fn balance_sheet(&self, unixTimestamp: f64) -> BalanceSheet {
+        self.ledger.project_balance_sheet(
+            &self.debt,
+            self.config.get_debt_config(),
+            &self.savings,
+            self.config.get_savings_config(),
+            &self.term_savings,
+            self.stability_pool.get_total(),
+            self.dvd.get_supply(),
+            self.stable_dvd.get_circulating(),
+            Time::from_unix_timestamp(unixTimestamp as u64),
+        )
+    }
 }
@@ -1,18 +1,53 @@
 use {
+    super::MintMetadata,
     crate::{
-        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        accounts::{
+            MintAccount, Readonly, Signer, SystemProgramAccount, TokenAccount, TokenProgramAccount,
+            Writable,
+        },
+        state::SovereignAuth,
+        store::Authority,
         traits::Account,
         util::{require, Expect},
     },
     solana_program::{
         program::{invoke, invoke_signed},
         program_option::COption,
-        program_pack::Pack,
         pubkey::Pubkey,
+        rent::Rent,
+        system_instruction,
     },
-    spl_token::state::Mint as SplMint,
+    spl_token_2022::{
+        extension::{
+            metadata_pointer, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+        },
+        state::Mint as SplMint,
+    },
+    spl_token_metadata_interface::state::Field,
 };
 
+/// Token-2022 mint extensions that the protocol cannot hold safely:
+/// interest-bearing amounts drift from what oracles price, non-transferable
+/// tokens cannot leave a safe, a permanent delegate can move tokens out of a
+/// safe, a transfer hook can block or reenter transfers, a default account
+/// state can freeze new safes, and a closable mint can be replaced under the
+/// same address. Extensions newer than this version of the token program,
+/// such as pausable mints, fail to parse and are rejected with the mint.
+const UNSUPPORTED_EXTENSIONS: [ExtensionType; 6] = [
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::NonTransferable,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+    ExtensionType::DefaultAccountState,
+    ExtensionType::MintCloseAuthority,
+];
+
+fn is_supported(extensions: &[ExtensionType]) -> bool {
+    extensions
+        .iter()
+        .all(|e| !UNSUPPORTED_EXTENSIONS.contains(e))
+}
+
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Mint {
@@ -20,6 +55,13 @@ pub struct Mint {
 }
 
 impl Mint {
+    /// A placeholder for a mint that does not exist yet.
+    pub const fn zero() -> Self {
+        Self {
+            key: Pubkey::new_from_array([0; 32]),
+        }
+    }
+
     pub fn from_account(
         mint_account: MintAccount<Readonly>,
         expected_mint_authority: Expect<Readonly>,
@@ -29,9 +71,18 @@ impl Mint {
         supply_out: &mut u64
     ) -> Self {
         let mint_data = mint_account.get_info().data.borrow();
-        let mint = SplMint::unpack(&mint_data)
+        let mint_state = StateWithExtensions::<SplMint>::unpack(&mint_data)
             .map_err(|_| "couldn't unpack mint data")
             .unwrap();
+        let extensions = mint_state
+            .get_extension_types()
+            .map_err(|_| "couldn't read mint extensions")
+            .unwrap();
+        require(
+            is_supported(&extensions),
+            "Mint has an unsupported token extension",
+        );
+        let mint = mint_state.base;
         match expected_mint_authority {
             Expect::Some(mint_authority) => {
                 let actual = mint.mint_authority;
@@ -105,10 +156,10 @@ impl Mint {
         let token_account_info = token_account.get_info();
         let mint_authority_info = mint_authority.get_info();
         require(mint_info.key == &self.key, "mint_info key mismatch");
-        _ = token_program_account; // must be included when calling into spl_token
+        token_program_account.check_owner(mint_info);
         invoke_signed(
-            &spl_token::instruction::mint_to(
-                &spl_token::ID,
+            &spl_token_2022::instruction::mint_to(
+                token_program_account.get_info().key,
                 &mint_info.key,
                 token_account_info.key,
                 mint_authority_info.key,
@@ -140,10 +191,10 @@ impl Mint {
         let token_account_info = token_account.get_info();
         let user_info = user_account.get_info();
         require(mint_info.key == &self.key, "mint_info key mismatch");
-        _ = token_program_account; // must be included when calling into spl_token
+        token_program_account.check_owner(mint_info);
         invoke(
-            &spl_token::instruction::burn(
-                &spl_token::ID,
+            &spl_token_2022::instruction::burn(
+                token_program_account.get_info().key,
                 token_account_info.key,
                 &mint_info.key,
                 user_info.key,
@@ -162,3 +213,214 @@ impl Mint {
         .unwrap()
     }
 }
+
+impl Mint {
+    const SEED_PREFIX: &'static [u8] = b"mint";
+    pub const DVD_SEED: &'static [u8] = b"dvd";
+    pub const DOVE_SEED: &'static [u8] = b"dove";
+    pub const SDVD_SEED: &'static [u8] = b"sdvd";
+
+    /// Creates a Token-2022 mint at the address derived from `seed`.
+    /// The authority is the mint authority and the metadata update authority,
+    /// there is no freeze authority, and the metadata is stored in the mint.
+    pub fn create(
+        program_id: &Pubkey,
+        seed: &[u8],
+
+        user_account: Signer,
+        mint_account: Writable,
+        system_program_account: SystemProgramAccount,
+        token_program_account: TokenProgramAccount,
+
+        authority: Authority,
+
+        decimals: u8,
+        metadata: &MintMetadata,
+        rent: &Rent,
+    ) -> MintAccount<Readonly> {
+        _ = system_program_account; // required for the instruction
+        let user_info = user_account.get_info();
+        let mint_info = mint_account.get_info();
+        let authority_info = authority.get_account().get_info();
+        let token_program_id = token_program_account.get_info().key;
+        require(
+            token_program_id == &spl_token_2022::ID,
+            "Program-created mints require the Token-2022 program",
+        );
+        let (key, nonce) = Pubkey::find_program_address(&[Self::SEED_PREFIX, seed], program_id);
+        require(&key == mint_info.key, "Invalid mint account address");
+        let size =
+            ExtensionType::try_calculate_account_len::<SplMint>(&[ExtensionType::MetadataPointer])
+                .map_err(|_| "couldn't calculate mint account size")
+                .unwrap();
+        // The token program grows the account for the metadata, but does not fund it
+        invoke_signed(
+            &system_instruction::create_account(
+                user_info.key,
+                mint_info.key,
+                rent.minimum_balance(size + MintMetadata::SPACE),
+                size as u64,
+                token_program_id,
+            ),
+            &[user_info.clone(), mint_info.clone()],
+            &[&[Self::SEED_PREFIX, seed, &[nonce]]],
+        )
+        .map_err(|_| "Failed to create mint account")
+        .unwrap();
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                token_program_id,
+                mint_info.key,
+                Some(*authority_info.key),
+                Some(*mint_info.key),
+            )
+            .map_err(|_| "couldn't create initialize metadata pointer instruction")
+            .unwrap(),
+            &[mint_info.clone()],
+        )
+        .map_err(|_| "Failed to initialize metadata pointer")
+        .unwrap();
+        invoke(
+            &spl_token_2022::instruction::initialize_mint2(
+                token_program_id,
+                mint_info.key,
+                authority_info.key,
+                None,
+                decimals,
+            )
+            .map_err(|_| "couldn't create initialize mint instruction")
+            .unwrap(),
+            &[mint_info.clone()],
+        )
+        .map_err(|_| "Failed to initialize mint")
+        .unwrap();
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                token_program_id,
+                mint_info.key,
+                authority_info.key,
+                mint_info.key,
+                authority_info.key,
+                metadata.get_name(),
+                metadata.get_symbol(),
+                metadata.get_uri(),
+            ),
+            &[mint_info.clone(), authority_info.clone()],
+            &[&authority.get_seeds()],
+        )
+        .map_err(|_| "Failed to initialize mint metadata")
+        .unwrap();
+        MintAccount::new(Readonly::new(mint_info))
+    }
+
+    /// Replaces the name, symbol and URI of a mint created by `create`.
+    pub fn update_metadata(
+        &self,
+        user_account: Signer,
+        mint_account: MintAccount<Writable>,
+        system_program_account: SystemProgramAccount,
+        token_program_account: TokenProgramAccount,
+        authority: Authority,
+        metadata: &MintMetadata,
+        rent: &Rent,
+        sovereign_auth: SovereignAuth,
+    ) {
+        _ = system_program_account; // required for the instruction
+        _ = sovereign_auth; // ensure that we have sovereign_auth authorization
+        let user_info = user_account.get_info();
+        let mint_info = mint_account.get_info();
+        let authority_info = authority.get_account().get_info();
+        require(mint_info.key == &self.key, "mint_info key mismatch");
+        token_program_account.check_owner(mint_info);
+        // Longer values grow the account, top it up so it stays rent-exempt
+        let required_lamports = rent.minimum_balance(mint_info.data_len() + MintMetadata::SPACE);
+        if mint_info.lamports() < required_lamports {
+            invoke(
+                &system_instruction::transfer(
+                    user_info.key,
+                    mint_info.key,
+                    required_lamports - mint_info.lamports(),
+                ),
+                &[user_info.clone(), mint_info.clone()],
+            )
+            .map_err(|_| "Failed to fund mint account")
+            .unwrap();
+        }
+        let fields = [
+            (Field::Name, metadata.get_name()),
+            (Field::Symbol, metadata.get_symbol()),
+            (Field::Uri, metadata.get_uri()),
+        ];
+        for (field, value) in fields {
+            invoke_signed(
+                &spl_token_metadata_interface::instruction::update_field(
+                    token_program_account.get_info().key,
+                    mint_info.key,
+                    authority_info.key,
+                    field,
+                    value,
+                ),
+                &[mint_info.clone(), authority_info.clone()],
+                &[&authority.get_seeds()],
+            )
+            .map_err(|_| "Failed to update mint metadata")
+            .unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Mint {
+    pub fn derive_address(program_id: &Pubkey, seed: &[u8]) -> Pubkey {
+        let (key, _) = Pubkey::find_program_address(&[Self::SEED_PREFIX, seed], program_id);
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_extensions() {
+        assert!(is_supported(&[]));
+        assert!(is_supported(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MetadataPointer,
+            ExtensionType::TokenMetadata,
+        ]));
+    }
+
+    #[test]
+    fn test_rejects_interest_bearing() {
+        assert!(!is_supported(&[ExtensionType::InterestBearingConfig]));
+    }
+
+    #[test]
+    fn test_rejects_non_transferable() {
+        assert!(!is_supported(&[ExtensionType::NonTransferable]));
+    }
+
+    #[test]
+    fn test_rejects_permanent_delegate() {
+        assert!(!is_supported(&[ExtensionType::PermanentDelegate]));
+    }
+
+    #[test]
+    fn test_rejects_transfer_hook() {
+        assert!(!is_supported(&[ExtensionType::TransferHook]));
+    }
+
+    #[test]
+    fn test_rejects_default_account_state() {
+        assert!(!is_supported(&[ExtensionType::DefaultAccountState]));
+    }
+
+    #[test]
+    fn test_rejects_mint_close_authority() {
+        assert!(!is_supported(&[
+            ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
+        ]));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::util::require;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+const NAME_LEN: usize = 32;
+const SYMBOL_LEN: usize = 10;
+const URI_LEN: usize = 200;
+
+/// Name, symbol and URI of a program-created mint, stored in the mint itself
+/// through the Token-2022 metadata extension.
+///
+/// Fields are UTF-8, padded with zeroes to a fixed length.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct MintMetadata {
+    name: [u8; NAME_LEN],
+    symbol: [u8; SYMBOL_LEN],
+    uri: [u8; URI_LEN],
+}
+
+impl MintMetadata {
+    /// Upper bound on the space taken by the metadata in a mint: the
+    /// type-length header, update authority, mint, three length-prefixed
+    /// strings and an empty list of additional metadata.
+    pub const SPACE: usize = 2 + 2 + 32 + 32 + 4 * 3 + NAME_LEN + SYMBOL_LEN + URI_LEN + 4;
+
+    pub fn get_name(&self) -> String {
+        decode(&self.name)
+    }
+    pub fn get_symbol(&self) -> String {
+        decode(&self.symbol)
+    }
+    pub fn get_uri(&self) -> String {
+        decode(&self.uri)
+    }
+}
+
+/// Returns the string up to the first zero byte, reverting if it is not UTF-8.
+fn decode(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let value = std::str::from_utf8(&bytes[..len])
+        .map_err(|_| "Mint metadata is not valid UTF-8")
+        .unwrap();
+    require(!value.is_empty(), "Mint metadata field is empty");
+    value.to_string()
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl MintMetadata {
+    #[wasm_bindgen(constructor)]
+    pub fn new(name: String, symbol: String, uri: String) -> Result<MintMetadata, String> {
+        Ok(Self {
+            name: encode(&name, "Name")?,
+            symbol: encode(&symbol, "Symbol")?,
+            uri: encode(&uri, "URI")?,
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.get_name()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn symbol(&self) -> String {
+        self.get_symbol()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn uri(&self) -> String {
+        self.get_uri()
+    }
+}
+
+#[cfg(feature = "wasm")]
+fn encode<const N: usize>(value: &str, field: &str) -> Result<[u8; N], String> {
+    if value.is_empty() || value.len() > N || value.contains('\0') {
+        return Err(format!("{} must be 1 to {} bytes long", field, N));
+    }
+    let mut bytes = [0; N];
+    bytes[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn padded<const N: usize>(value: &str) -> [u8; N] {
+        let mut bytes = [0; N];
+        bytes[..value.len()].copy_from_slice(value.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_decode_trims_padding() {
+        let metadata = MintMetadata {
+            name: padded("Dove Dollar"),
+            symbol: padded("DVD"),
+            uri: padded("https://example.com/dvd.json"),
+        };
+        assert_eq!(metadata.get_name(), "Dove Dollar");
+        assert_eq!(metadata.get_symbol(), "DVD");
+        assert_eq!(metadata.get_uri(), "https://example.com/dvd.json");
+    }
+
+    #[test]
+    fn test_decode_full_length() {
+        let symbol = "ABCDEFGHIJ";
+        assert_eq!(decode(&padded::<SYMBOL_LEN>(symbol)), symbol);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decode_rejects_empty() {
+        decode(&[0; NAME_LEN]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decode_rejects_invalid_utf8() {
+        decode(&[0xff, 0xfe, 0]);
+    }
+}
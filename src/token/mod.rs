@@ -1,6 +1,15 @@
 mod mint;
+mod mint_metadata;
 mod reserve;
 mod safe;
+mod safe_audit;
 mod token;
 
-pub use {mint::Mint, reserve::Reserve, safe::Safe, token::Token};
+pub use {
+    mint::Mint,
+    mint_metadata::MintMetadata,
+    reserve::Reserve,
+    safe::Safe,
+    safe_audit::{SafeAudit, SafeOwner},
+    token::Token,
+};
@@ -1,10 +1,10 @@
-use crate::{finance::InterestRate, state::DvdPrice};
+use crate::{finance::InterestRate, oracle::PricePurpose, state::DvdPrice};
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use {
     crate::{
-        accounts::{Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
+        accounts::{MintAccount, Readonly, Signer, TokenAccount, TokenProgramAccount, Writable},
         finance::Decimal,
         store::{Authority, Collateral},
         token::Mint,
@@ -19,6 +19,16 @@ use {
 pub struct Reserve {
     mint: Mint,
     balance: Decimal,
+    // The price, in DVD, the reserve was last valued at. Zero until then.
+    price: Decimal,
+    // The share of the vault debt charged the stability fee of this collateral.
+    fee_share: Decimal,
+    // The stability fee multiplier of the collateral when the fee was last charged.
+    // Zero until then.
+    fee_multiplier: Decimal,
+    // The part of the vault debt principal backed by this collateral, as
+    // recorded in its `Collateral`.
+    backed_debt: Decimal,
 }
 
 impl Reserve {
@@ -27,6 +37,10 @@ impl Reserve {
         Reserve {
             mint: *mint,
             balance: Decimal::zero(),
+            price: Decimal::zero(),
+            fee_share: Decimal::zero(),
+            fee_multiplier: Decimal::zero(),
+            backed_debt: Decimal::zero(),
         }
     }
     pub fn deposit(
@@ -39,18 +53,20 @@ impl Reserve {
         user_account: Signer,
         source_token_account: TokenAccount<Writable>,
         program_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
     ) {
         require(collateral.get_mint() == &self.mint, "mint mismatch");
-        collateral.receive(
+        // Transfer fees are withheld from the deposit, credit what arrived
+        self.balance += collateral.receive(
             amount,
             program_id,
             user_account,
             source_token_account,
             program_token_account,
+            mint_account,
             token_program_account,
         );
-        self.balance += amount;
     }
     pub fn withdraw(
         &mut self,
@@ -61,6 +77,7 @@ impl Reserve {
 
         safe_account: TokenAccount<Writable>,
         destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
 
         authority: Authority,
@@ -72,21 +89,73 @@ impl Reserve {
             program_id,
             safe_account,
             destination_token_account,
+            mint_account,
             token_program_account,
             authority,
         );
         self.balance -= amount;
     }
+    /// Hands the whole balance over to the protocol.
+    pub fn seize(&mut self, collateral: &mut Collateral) {
+        require(collateral.get_mint() == &self.mint, "mint mismatch");
+        collateral.seize(self.balance.take());
+    }
+    /// Takes `amount` out of the reserve. The tokens stay in the safe.
+    pub fn release(&mut self, amount: Decimal) {
+        require(amount <= self.balance, "amount too large");
+        self.balance -= amount;
+    }
+    /// Empties the reserve, returning its mint and former balance.
+    pub fn take(&mut self) -> (Pubkey, Decimal) {
+        (*self.mint.get_key(), self.balance.take())
+    }
+    /// Returns the stability fee accrued on this reserve's share of `debt`
+    /// since it was last charged.
+    pub fn accrue_stability_fee(
+        &mut self,
+        collateral: &mut Collateral,
+        debt: Decimal,
+        clock: &Clock,
+    ) -> Decimal {
+        require(collateral.get_mint() == &self.mint, "mint mismatch");
+        let multiplier = collateral.get_stability_fee_multiplier(clock);
+        let fee = if self.fee_multiplier.is_zero() {
+            Decimal::zero()
+        } else {
+            debt * self.fee_share
+                * (multiplier / self.fee_multiplier).saturating_sub(Decimal::one())
+        };
+        self.fee_multiplier = multiplier;
+        fee
+    }
+    pub fn set_fee_share(&mut self, fee_share: Decimal) {
+        self.fee_share = fee_share;
+    }
+    /// Records this reserve's share of the vault debt `principal` as backed
+    /// by `collateral`, by the share the stability fee is charged on.
+    pub fn record_backed_debt(&mut self, collateral: &mut Collateral, principal: Decimal) {
+        require(collateral.get_mint() == &self.mint, "mint mismatch");
+        let backed_debt = principal * self.fee_share;
+        collateral.record_backed_debt(self.backed_debt, backed_debt);
+        self.backed_debt = backed_debt;
+    }
     pub fn get_value(
-        &self,
-        collateral: &Collateral,
+        &mut self,
+        collateral: &mut Collateral,
+        purpose: PricePurpose,
         oracle_account: Readonly,
         dvd_price: &mut DvdPrice,
         dvd_interest_rate: &InterestRate,
         clock: &Clock,
     ) -> Decimal {
         require(collateral.get_mint() == &self.mint, "mint mismatch");
-        collateral.get_price(oracle_account, dvd_price, dvd_interest_rate, clock) * self.balance
+        self.price =
+            collateral.get_price(purpose, oracle_account, dvd_price, dvd_interest_rate, clock);
+        self.price * self.balance
+    }
+    /// Returns the value of the reserve at the price it was last valued at.
+    pub fn get_last_value(&self) -> Decimal {
+        self.balance * self.price
     }
     pub const fn get_mint(&self) -> &Mint {
         &self.mint
@@ -96,6 +165,21 @@ impl Reserve {
     }
 }
 
+#[cfg(test)]
+impl Reserve {
+    pub fn valued(balance: Decimal, price: Decimal) -> Self {
+        Reserve {
+            balance,
+            price,
+            // Safety: a reserve is plain data, zero is a valid empty reserve.
+            ..unsafe { std::mem::zeroed() }
+        }
+    }
+    pub const fn get_fee_share(&self) -> Decimal {
+        self.fee_share
+    }
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 impl Reserve {
@@ -104,6 +188,11 @@ impl Reserve {
         self.balance.to_f64()
     }
 
+    #[wasm_bindgen(getter, js_name = "feeShare")]
+    pub fn fee_share(&self) -> f64 {
+        self.fee_share.to_f64()
+    }
+
     #[wasm_bindgen(getter, js_name = "mintKey")]
     pub fn mint_key(&self) -> Vec<u8> {
         self.mint.get_key().to_bytes().to_vec()
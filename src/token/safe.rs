@@ -4,23 +4,29 @@ use {
             MintAccount, Readonly, Signer, SystemProgramAccount, TokenAccount, TokenProgramAccount,
             Writable,
         },
+        finance::Decimal,
         state::SovereignAuth,
         store::Authority,
         token::Mint,
-        traits::{Account, Pod},
+        traits::{Account, Pod, Store},
         util::require,
     },
     solana_program::{
         program::{invoke, invoke_signed},
-        program_pack::Pack,
         pubkey::Pubkey,
         rent::Rent,
         system_instruction,
     },
+    spl_token_2022::{
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        state::{Account as TokenAccountState, Mint as SplMint},
+    },
 };
 
 /// A safe place to store tokens.
-/// The accounts where the protocol stores collateral.
+///
+/// Safes are namespaced by the type of the store that owns them, so that one
+/// mint can back several stores without mixing their tokens.
 #[derive(Clone, Copy)]
 pub struct Safe {
     account: TokenAccount<Writable>,
@@ -29,8 +35,7 @@ pub struct Safe {
 
 impl Safe {
     const SEED_PREFIX: &'static [u8] = b"safe";
-    const SIZE: usize = spl_token::state::Account::LEN;
-    pub fn create(
+    pub fn create<S: Store>(
         program_id: &Pubkey,
 
         user_account: Signer,
@@ -46,13 +51,19 @@ impl Safe {
         sovereign_auth: SovereignAuth,
     ) -> Self {
         _ = system_program_account; // required for the instruction
-        _ = token_program_account; // required for the instruction
         _ = sovereign_auth; // ensure that we have sovereign_auth authorization
         let user_info = user_account.get_info();
         let safe_account_info = safe_account.get_info();
         let mint_info = mint_account.get_info();
+        let token_program_id = token_program_account.get_info().key;
+        token_program_account.check_owner(mint_info);
+        let size = Self::get_size(mint_account);
         let (key, nonce) = Pubkey::find_program_address(
-            &[Self::SEED_PREFIX, mint_info.key.as_bytes()],
+            &[
+                Self::SEED_PREFIX,
+                S::SEED_PREFIX.as_bytes(),
+                mint_info.key.as_bytes(),
+            ],
             program_id,
         );
         require(
@@ -63,18 +74,23 @@ impl Safe {
             &system_instruction::create_account(
                 user_info.key,
                 safe_account_info.key,
-                rent.minimum_balance(Self::SIZE),
-                Self::SIZE as u64,
-                &spl_token::ID,
+                rent.minimum_balance(size),
+                size as u64,
+                token_program_id,
             ),
             &[user_info.clone(), safe_account_info.clone()],
-            &[&[Self::SEED_PREFIX, mint_info.key.as_bytes(), &[nonce]]],
+            &[&[
+                Self::SEED_PREFIX,
+                S::SEED_PREFIX.as_bytes(),
+                mint_info.key.as_bytes(),
+                &[nonce],
+            ]],
         )
         .map_err(|_| "Failed to create safe account")
         .unwrap();
         invoke_signed(
-            &spl_token::instruction::initialize_account3(
-                &spl_token::ID,
+            &spl_token_2022::instruction::initialize_account3(
+                token_program_id,
                 safe_account_info.key,
                 mint_info.key,
                 authority.get_account().get_info().key,
@@ -82,12 +98,17 @@ impl Safe {
             .map_err(|_| "Failed to create initialize token account instruction")
             .unwrap(),
             &[safe_account_info.clone(), mint_info.clone()],
-            &[&[Self::SEED_PREFIX, mint_info.key.as_bytes(), &[nonce]]],
+            &[&[
+                Self::SEED_PREFIX,
+                S::SEED_PREFIX.as_bytes(),
+                mint_info.key.as_bytes(),
+                &[nonce],
+            ]],
         )
         .map_err(|_| "Failed to initialize safe as token account")
         .unwrap();
         require(
-            rent.is_exempt(safe_account_info.lamports(), Self::SIZE),
+            rent.is_exempt(safe_account_info.lamports(), size),
             "Safe account is not rent-exempt",
         );
         Self {
@@ -98,7 +119,7 @@ impl Safe {
     pub fn get_nonce(&self) -> u8 {
         self.nonce
     }
-    pub fn get(
+    pub fn get<S: Store>(
         program_id: &Pubkey,
         safe_account: TokenAccount<Writable>,
         safe_account_nonce: u8,
@@ -107,6 +128,7 @@ impl Safe {
         let key = Pubkey::create_program_address(
             &[
                 Self::SEED_PREFIX,
+                S::SEED_PREFIX.as_bytes(),
                 mint.get_key().as_bytes(),
                 &[safe_account_nonce],
             ],
@@ -123,61 +145,78 @@ impl Safe {
             nonce: safe_account_nonce,
         }
     }
+    /// Transfers `amount` tokens into the safe.
+    /// Returns the amount actually received, which is lower than `amount`
+    /// for Token-2022 mints that charge a transfer fee.
     pub fn receive(
         &self,
         amount: u64,
+        decimals: u8,
         user_account: Signer,
         source_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
-    ) {
+    ) -> u64 {
         let user_account_info = user_account.get_info();
         let source_token_account_info = source_token_account.get_info();
         let token_account_info = self.account.get_info();
-        _ = token_program_account; // required for the instruction
+        let mint_info = mint_account.get_info();
+        token_program_account.check_owner(mint_info);
+        let balance_before = self.get_balance();
         invoke(
-            &spl_token::instruction::transfer(
-                &spl_token::ID,
+            &spl_token_2022::instruction::transfer_checked(
+                token_program_account.get_info().key,
                 source_token_account_info.key,
+                mint_info.key,
                 token_account_info.key,
                 user_account_info.key,
                 &[],
                 amount,
+                decimals,
             )
             .map_err(|_| "couldn't create transfer instruction")
             .unwrap(),
             &[
                 source_token_account_info.clone(),
+                mint_info.clone(),
                 token_account_info.clone(),
                 user_account_info.clone(),
             ],
         )
         .map_err(|_| "couldn't transfer tokens")
-        .unwrap()
+        .unwrap();
+        self.get_balance() - balance_before
     }
     pub fn send(
         &self,
         amount: u64,
+        decimals: u8,
         destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
         token_program_account: TokenProgramAccount,
         authority: Authority,
     ) {
         let authority_info = authority.get_account().get_info();
         let token_account_info = self.account.get_info();
         let destination_token_account_info = destination_token_account.get_info();
-        _ = token_program_account; // required for the instruction
+        let mint_info = mint_account.get_info();
+        token_program_account.check_owner(mint_info);
         invoke_signed(
-            &spl_token::instruction::transfer(
-                &spl_token::ID,
+            &spl_token_2022::instruction::transfer_checked(
+                token_program_account.get_info().key,
                 token_account_info.key,
+                mint_info.key,
                 destination_token_account_info.key,
                 authority_info.key,
                 &[],
                 amount,
+                decimals,
             )
             .map_err(|_| "couldn't create transfer instruction")
             .unwrap(),
             &[
                 token_account_info.clone(),
+                mint_info.clone(),
                 destination_token_account_info.clone(),
                 authority_info.clone(),
             ],
@@ -186,13 +225,111 @@ impl Safe {
         .map_err(|_| "couldn't transfer tokens")
         .unwrap()
     }
+    /// Sends the tokens in the safe that `deposited` does not account for,
+    /// and returns the amount sent.
+    ///
+    /// `deposited` is rounded up to whole token units, so that the sweep can
+    /// never reach into deposits.
+    pub fn sweep(
+        &self,
+        deposited: Decimal,
+        decimals: u8,
+        destination_token_account: TokenAccount<Writable>,
+        mint_account: MintAccount<Readonly>,
+        token_program_account: TokenProgramAccount,
+        authority: Authority,
+    ) -> u64 {
+        let surplus = self.get_surplus(deposited, decimals);
+        require(surplus > 0, "Safe has no surplus to sweep");
+        self.send(
+            surplus,
+            decimals,
+            destination_token_account,
+            mint_account,
+            token_program_account,
+            authority,
+        );
+        surplus
+    }
+    pub fn get_balance(&self) -> u64 {
+        let data = self.account.get_info().data.borrow();
+        StateWithExtensions::<TokenAccountState>::unpack(&data)
+            .map_err(|_| "couldn't unpack safe account data")
+            .unwrap()
+            .base
+            .amount
+    }
+}
+
+// For internal use only
+impl Safe {
+    /// Returns the size of a token account for the mint, including the
+    /// extensions that Token-2022 requires for it.
+    fn get_size(mint_account: MintAccount<Readonly>) -> usize {
+        let mint_data = mint_account.get_info().data.borrow();
+        let mint = StateWithExtensions::<SplMint>::unpack(&mint_data)
+            .map_err(|_| "couldn't unpack mint data")
+            .unwrap();
+        let mint_extensions = mint
+            .get_extension_types()
+            .map_err(|_| "couldn't read mint extensions")
+            .unwrap();
+        let account_extensions =
+            ExtensionType::get_required_init_account_extensions(&mint_extensions);
+        ExtensionType::try_calculate_account_len::<TokenAccountState>(&account_extensions)
+            .map_err(|_| "couldn't calculate safe account size")
+            .unwrap()
+    }
+    fn get_surplus(&self, deposited: Decimal, decimals: u8) -> u64 {
+        surplus(self.get_balance(), deposited, decimals)
+    }
+}
+
+/// Returns the part of `balance` that exceeds `deposited` rounded up.
+fn surplus(balance: u64, deposited: Decimal, decimals: u8) -> u64 {
+    let mut deposited_amount = deposited.to_token_amount(decimals);
+    if Decimal::from_token_amount(deposited_amount, decimals) < deposited {
+        deposited_amount += 1;
+    }
+    balance.saturating_sub(deposited_amount)
 }
 
 #[cfg(feature = "wasm")]
 impl Safe {
-    pub fn derive_address(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
-        let (key, _) =
-            Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_bytes()], program_id);
+    pub fn derive_address<S: Store>(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let (key, _) = Pubkey::find_program_address(
+            &[
+                Self::SEED_PREFIX,
+                S::SEED_PREFIX.as_bytes(),
+                mint.as_bytes(),
+            ],
+            program_id,
+        );
         key
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surplus_is_balance_minus_deposited() {
+        let deposited = Decimal::from_token_amount(1_500_000, 6);
+        assert_eq!(surplus(2_000_000, deposited, 6), 500_000);
+        assert_eq!(surplus(1_500_000, deposited, 6), 0);
+    }
+
+    #[test]
+    fn test_surplus_rounds_deposited_up() {
+        let deposited =
+            Decimal::from_token_amount(1_500_000, 6) + Decimal::from(1u64) / 10u64.pow(9);
+        assert_eq!(surplus(2_000_000, deposited, 6), 499_999);
+    }
+
+    #[test]
+    fn test_surplus_saturates_on_deficit() {
+        let deposited = Decimal::from_token_amount(2_000_000, 6);
+        assert_eq!(surplus(1_000_000, deposited, 6), 0);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{finance::Decimal, util::Time};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// The type of store that owns a safe.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum SafeOwner {
+    Collateral = 0,
+    Stability = 1,
+}
+
+/// The token balance of a safe as last observed by `SafeReconcile`, next to
+/// the amount that its owning store accounted for at that time.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SafeAudit {
+    balance: Decimal,
+    deposited: Decimal,
+    time: Time,
+}
+
+impl SafeAudit {
+    pub const fn new() -> Self {
+        Self {
+            balance: Decimal::zero(),
+            deposited: Decimal::zero(),
+            time: Time::from_unix_timestamp(0),
+        }
+    }
+
+    pub fn record(&mut self, balance: Decimal, deposited: Decimal, time: Time) {
+        self.balance = balance;
+        self.deposited = deposited;
+        self.time = time;
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SafeAudit {
+    #[wasm_bindgen(getter)]
+    pub fn balance(&self) -> f64 {
+        self.balance.to_f64()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn deposited(&self) -> f64 {
+        self.deposited.to_f64()
+    }
+
+    /// Tokens in the safe that no deposit accounts for.
+    #[wasm_bindgen(getter)]
+    pub fn surplus(&self) -> f64 {
+        self.balance.saturating_sub(self.deposited).to_f64()
+    }
+
+    /// Deposits that the safe does not hold the tokens for.
+    #[wasm_bindgen(getter)]
+    pub fn deficit(&self) -> f64 {
+        self.deposited.saturating_sub(self.balance).to_f64()
+    }
+
+    #[wasm_bindgen(getter, js_name = unixTimestamp)]
+    pub fn unix_timestamp(&self) -> f64 {
+        self.time.to_unix_timestamp() as f64
+    }
+}
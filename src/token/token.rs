@@ -33,6 +33,15 @@ impl Token {
 }
 
 impl Token {
+    /// A placeholder for a token that does not exist yet.
+    pub const fn zero() -> Self {
+        Self {
+            mint: Mint::zero(),
+            supply: Decimal::zero(),
+            decimals: 0,
+        }
+    }
+
     pub fn from_account(
         account: MintAccount<Readonly>,
         authority: Authority,
@@ -56,6 +65,10 @@ impl Token {
         }
     }
 
+    pub const fn get_mint(&self) -> &Mint {
+        &self.mint
+    }
+
     pub const fn get_supply(&self) -> Decimal {
         self.supply
     }
@@ -32,6 +32,21 @@ impl<T: Copy, const C: usize> List<T, C> {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
         self.data[..self.len].iter_mut()
     }
+    pub fn insert(&mut self, index: usize, item: T) {
+        if self.len < C {
+            self.data.copy_within(index..self.len, index + 1);
+            self.data[index] = item;
+            self.len += 1;
+        } else {
+            revert("List is full")
+        }
+    }
+    pub fn remove(&mut self, index: usize) -> T {
+        let item = self.data[index];
+        self.data.copy_within(index + 1..self.len, index);
+        self.len -= 1;
+        item
+    }
     pub fn swap_remove(&mut self, index: usize) -> T {
         let last_index = self.len - 1;
         self.data.swap(index, last_index);
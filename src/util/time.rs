@@ -4,7 +4,7 @@ use {
 };
 
 #[repr(transparent)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct Time {
     unix_timestamp: u64,
 }
@@ -22,10 +22,18 @@ impl Time {
     pub const fn secs_elapsed(self, clock: &Clock) -> u64 {
         Self::now(clock).secs_since(self)
     }
+    pub const fn add_secs(self, secs: u64) -> Self {
+        Self {
+            unix_timestamp: self.unix_timestamp.saturating_add(secs),
+        }
+    }
+    /// Returns the index of the `epoch_length`-second epoch containing this time.
+    pub const fn get_epoch(self, epoch_length: u64) -> u64 {
+        self.unix_timestamp / epoch_length
+    }
     pub const fn from_unix_timestamp(unix_timestamp: u64) -> Self {
         Self { unix_timestamp }
     }
-    #[cfg(feature = "wasm")]
     pub const fn to_unix_timestamp(&self) -> u64 {
         self.unix_timestamp
     }